@@ -12,6 +12,7 @@ use std::fmt;
 /// [`Poll::poll`]: crate::Poll::poll
 /// [`Poll`]: crate::Poll
 /// [`Token`]: crate::Token
+#[derive(Clone, Copy)]
 pub struct Event {
     inner: sys::Event,
 }
@@ -24,12 +25,51 @@ impl Event {
     }
 
     /// Returns true if the event contains readable readiness.
+    ///
+    /// This matches [`Interests::READABLE`]: sources registered with that
+    /// interest receive events for which this returns true.
+    ///
+    /// [`Interests::READABLE`]: crate::Interests::READABLE
+    ///
+    /// # Notes
+    ///
+    /// The table below shows what flags are checked on what OS.
+    ///
+    /// | [OS selector] | Flag(s) checked |
+    /// |---------------|-----------------|
+    /// | [epoll]       | `EPOLLIN`, `EPOLLPRI` |
+    /// | [kqueue]      | `EVFILT_READ`, `EVFILT_USER` (used by [`Waker`]) |
+    /// | IOCP          | `AFD_POLL_RECEIVE`, `AFD_POLL_DISCONNECT`, `AFD_POLL_ACCEPT`, `AFD_POLL_CONNECT_FAIL` |
+    ///
+    /// [OS selector]: ../struct.Poll.html#implementation-notes
+    /// [epoll]: http://man7.org/linux/man-pages/man7/epoll.7.html
+    /// [kqueue]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
+    /// [`Waker`]: crate::Waker
     #[inline]
     pub fn is_readable(&self) -> bool {
         sys::event::is_readable(&self.inner)
     }
 
     /// Returns true if the event contains writable readiness.
+    ///
+    /// This matches [`Interests::WRITABLE`]: sources registered with that
+    /// interest receive events for which this returns true.
+    ///
+    /// [`Interests::WRITABLE`]: crate::Interests::WRITABLE
+    ///
+    /// # Notes
+    ///
+    /// The table below shows what flags are checked on what OS.
+    ///
+    /// | [OS selector] | Flag(s) checked |
+    /// |---------------|-----------------|
+    /// | [epoll]       | `EPOLLOUT`      |
+    /// | [kqueue]      | `EVFILT_WRITE`  |
+    /// | IOCP          | `AFD_POLL_SEND`, `AFD_POLL_CONNECT_FAIL` |
+    ///
+    /// [OS selector]: ../struct.Poll.html#implementation-notes
+    /// [epoll]: http://man7.org/linux/man-pages/man7/epoll.7.html
+    /// [kqueue]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
     #[inline]
     pub fn is_writable(&self) -> bool {
         sys::event::is_writable(&self.inner)
@@ -41,6 +81,14 @@ impl Event {
     /// the socket will also receive a readable or writable event. Reading or
     /// writing to the socket will result in an error.
     ///
+    /// The event doesn't contain the error itself, to retrieve it call the
+    /// `take_error` method of the source, e.g. [`TcpStream::take_error`],
+    /// which returns (and clears) the pending error of the socket. For
+    /// example a failed non-blocking connect is reported as an error event,
+    /// after which `take_error` returns the `ConnectionRefused` error.
+    ///
+    /// [`TcpStream::take_error`]: crate::net::TcpStream::take_error
+    ///
     /// # Notes
     ///
     /// Method is available on all platforms, but not all platforms trigger the
@@ -51,7 +99,8 @@ impl Event {
     /// | [OS selector] | Flag(s) checked |
     /// |---------------|-----------------|
     /// | [epoll]       | `EPOLLERR`      |
-    /// | [kqueue]      | `EV_ERROR` and `EV_EOF` with `fflags` set to `0`. |
+    /// | [kqueue]      | `EV_ERROR`, or `EV_EOF` with `fflags` set to a non-zero error. |
+    /// | IOCP          | `AFD_POLL_CONNECT_FAIL` |
     ///
     /// [OS selector]: ../struct.Poll.html#implementation-notes
     /// [epoll]: http://man7.org/linux/man-pages/man7/epoll.7.html
@@ -61,6 +110,38 @@ impl Event {
         sys::event::is_error(&self.inner)
     }
 
+    /// Returns true if the event contains hang up readiness.
+    ///
+    /// A hang up means both halves of the connection are closed, e.g. because
+    /// the peer reset the connection. This differs from [`is_read_closed`] and
+    /// [`is_write_closed`], which are also set when only one half of the
+    /// connection is closed.
+    ///
+    /// [`is_read_closed`]: Event::is_read_closed
+    /// [`is_write_closed`]: Event::is_write_closed
+    ///
+    /// # Notes
+    ///
+    /// Method is available on all platforms, but not all platforms trigger the
+    /// hang up event. On platforms that don't, use [`is_read_closed`] and
+    /// [`is_write_closed`] instead.
+    ///
+    /// The table below shows what flags are checked on what OS.
+    ///
+    /// | [OS selector] | Flag(s) checked |
+    /// |---------------|-----------------|
+    /// | [epoll]       | `EPOLLHUP`      |
+    /// | [kqueue]      | *Not supported* |
+    /// | IOCP          | `AFD_POLL_ABORT` |
+    ///
+    /// [OS selector]: ../struct.Poll.html#implementation-notes
+    /// [epoll]: http://man7.org/linux/man-pages/man7/epoll.7.html
+    /// [kqueue]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
+    #[inline]
+    pub fn is_hup(&self) -> bool {
+        sys::event::is_hup(&self.inner)
+    }
+
     /// Returns true if the event contains read closed readiness.
     ///
     /// # Notes
@@ -127,7 +208,8 @@ impl Event {
     /// # Notes
     ///
     /// Method is available on all platforms, but not all platforms trigger the
-    /// priority event.
+    /// priority event. On platforms that do, priority events are only
+    /// returned for sources registered with [`Interests::PRIORITY`].
     ///
     /// The table below shows what flags are checked on what OS.
     ///
@@ -136,6 +218,8 @@ impl Event {
     /// | [epoll]       | `EPOLLPRI`      |
     /// | [kqueue]      | *Not supported* |
     ///
+    /// [`Interests::PRIORITY`]: crate::Interests::PRIORITY
+    ///
     /// [OS selector]: ../struct.Poll.html#implementation-notes
     /// [epoll]: http://man7.org/linux/man-pages/man7/epoll.7.html
     /// [kqueue]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
@@ -178,6 +262,55 @@ impl Event {
         sys::event::is_lio(&self.inner)
     }
 
+    /// Returns true if the event is a timeout set using
+    /// [`Registry::set_timeout`].
+    ///
+    /// Timeout events don't have any readiness set.
+    ///
+    /// Only available when the `timers` feature is enabled.
+    ///
+    /// [`Registry::set_timeout`]: crate::Registry::set_timeout
+    #[cfg(feature = "timers")]
+    #[inline]
+    pub fn is_timeout(&self) -> bool {
+        sys::event::is_timeout(&self.inner)
+    }
+
+    /// Returns the raw readiness of the event, as returned by the OS selector.
+    ///
+    /// This is intended for debugging platform specific behaviour, the value
+    /// is not portable. Use the other methods of `Event` to determine the
+    /// readiness.
+    ///
+    /// The table below shows what is returned on what OS.
+    ///
+    /// | [OS selector] | Value returned |
+    /// |---------------|----------------|
+    /// | [epoll]       | The `events` bitmask, e.g. `EPOLLIN` and `EPOLLRDHUP` |
+    /// | [kqueue]      | The `flags` field, e.g. `EV_EOF` and `EV_ERROR` |
+    /// | IOCP          | The AFD poll flags |
+    ///
+    /// Note that the kqueue filter, e.g. `EVFILT_READ`, is not included; it
+    /// determines whether the event is [readable] or [writable].
+    ///
+    /// Only available when the `os-ext` feature is enabled.
+    ///
+    /// [OS selector]: ../struct.Poll.html#implementation-notes
+    /// [epoll]: http://man7.org/linux/man-pages/man7/epoll.7.html
+    /// [kqueue]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
+    /// [readable]: Event::is_readable
+    /// [writable]: Event::is_writable
+    #[cfg(feature = "os-ext")]
+    #[inline]
+    pub fn raw_ready(&self) -> u32 {
+        sys::event::raw_ready(&self.inner)
+    }
+
+    /// Create an `Event` from a platform specific event.
+    pub(crate) fn from_sys_event(inner: sys::Event) -> Event {
+        Event { inner }
+    }
+
     /// Create a reference to an `Event` from a platform specific event.
     pub(crate) fn from_sys_event_ref(sys_event: &sys::Event) -> &Event {
         unsafe {
@@ -195,6 +328,7 @@ impl fmt::Debug for Event {
             .field("readable", &self.is_readable())
             .field("writable", &self.is_writable())
             .field("error", &self.is_error())
+            .field("hup", &self.is_hup())
             .field("read_closed", &self.is_read_closed())
             .field("write_closed", &self.is_write_closed())
             .field("priority", &self.is_priority())
@@ -1,5 +1,5 @@
 use crate::event::Event;
-use crate::sys;
+use crate::{sys, Interests, Token};
 
 use std::fmt;
 
@@ -40,6 +40,8 @@ use std::fmt;
 /// ```
 pub struct Events {
     inner: sys::Events,
+    /// Start offset of the next iterator returned by `iter_rotating`.
+    rotation: usize,
 }
 
 /// [`Events`] iterator.
@@ -76,6 +78,44 @@ pub struct Iter<'a> {
     pos: usize,
 }
 
+/// Rotating [`Events`] iterator.
+///
+/// This struct is created by the [`iter_rotating`] method on [`Events`].
+///
+/// [`Events`]: crate::event::Events
+/// [`iter_rotating`]: crate::event::Events::iter_rotating
+#[derive(Debug, Clone)]
+pub struct IterRotating<'a> {
+    inner: &'a Events,
+    start: usize,
+    pos: usize,
+}
+
+/// [`Events`] iterator yielding the token and ready interests of the events.
+///
+/// This struct is created by the [`iter_ready`] method on [`Events`].
+///
+/// [`Events`]: crate::event::Events
+/// [`iter_ready`]: crate::event::Events::iter_ready
+#[derive(Debug, Clone)]
+pub struct IterReady<'a> {
+    inner: Iter<'a>,
+}
+
+/// Draining [`Events`] iterator.
+///
+/// This struct is created by the [`drain`] method on [`Events`]. When dropped
+/// all events are removed from `Events`, including the events that weren't
+/// yielded.
+///
+/// [`Events`]: crate::event::Events
+/// [`drain`]: crate::event::Events::drain
+#[derive(Debug)]
+pub struct Drain<'a> {
+    inner: &'a mut Events,
+    pos: usize,
+}
+
 impl Events {
     /// Return a new `Events` capable of holding up to `capacity` events.
     ///
@@ -90,6 +130,7 @@ impl Events {
     pub fn with_capacity(capacity: usize) -> Events {
         Events {
             inner: sys::Events::with_capacity(capacity),
+            rotation: 0,
         }
     }
 
@@ -105,6 +146,20 @@ impl Events {
         self.inner.capacity()
     }
 
+    /// Returns the number of `Event` values in `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio::Events;
+    ///
+    /// let events = Events::with_capacity(1024);
+    /// assert_eq!(0, events.len());
+    /// ```
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
     /// Returns `true` if `self` contains no `Event` values.
     ///
     /// # Examples
@@ -119,6 +174,81 @@ impl Events {
         self.inner.is_empty()
     }
 
+    /// Returns the `Event` at `index`, or `None` if `index` is out of bounds,
+    /// i.e. not smaller than [`len`].
+    ///
+    /// [`len`]: Events::len
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    /// use std::time::Duration;
+    ///
+    /// let mut events = Events::with_capacity(1024);
+    /// let mut poll = Poll::new()?;
+    ///
+    /// // Register handles with `poll`.
+    ///
+    /// poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+    ///
+    /// // Handle the readable events first.
+    /// for event in events.iter().filter(|event| event.is_readable()) {
+    ///     println!("Readable event for {:?}", event.token());
+    /// }
+    /// // Followed by the writable events.
+    /// for index in 0..events.len() {
+    ///     let event = events.get(index).unwrap();
+    ///     if event.is_writable() {
+    ///         println!("Writable event for {:?}", event.token());
+    ///     }
+    /// }
+    /// assert!(events.get(events.len()).is_none());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&Event> {
+        self.inner.get(index).map(Event::from_sys_event_ref)
+    }
+
+    /// Returns `true` if `self` contains at least one `Event` and all events
+    /// are for `token`.
+    ///
+    /// This can be used to detect a poll that was only woken by a [`Waker`],
+    /// in which case there is no I/O to handle.
+    ///
+    /// [`Waker`]: crate::Waker
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll, Token, Waker};
+    ///
+    /// const WAKER: Token = Token(0);
+    ///
+    /// let mut events = Events::with_capacity(1024);
+    /// let mut poll = Poll::new()?;
+    /// let waker = Waker::new(poll.registry(), WAKER)?;
+    ///
+    /// // Register handles with `poll`.
+    ///
+    /// waker.wake()?;
+    /// poll.poll(&mut events, None)?;
+    ///
+    /// if events.contains_only(WAKER) {
+    ///     // Only woken up, no I/O to handle.
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn contains_only(&self, token: Token) -> bool {
+        !self.is_empty() && self.iter().all(|event| event.token() == token)
+    }
+
     /// Returns an iterator over the `Event` values.
     ///
     /// # Examples
@@ -149,11 +279,149 @@ impl Events {
         }
     }
 
+    /// Returns an iterator over the `Event` values, starting at a different
+    /// event on each call.
+    ///
+    /// Each call starts one event further than the previous call, wrapping
+    /// around to the first event after the last one. This spreads the
+    /// processing evenly over the sources when the events are processed in
+    /// order and processing sometimes stops early, e.g. to limit the time
+    /// spent handling events. With [`iter`] sources that are returned first
+    /// could starve the others.
+    ///
+    /// [`iter`]: Events::iter
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    /// use std::time::Duration;
+    ///
+    /// let mut events = Events::with_capacity(1024);
+    /// let mut poll = Poll::new()?;
+    ///
+    /// // Register handles with `poll`.
+    ///
+    /// loop {
+    ///     poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+    ///
+    ///     // Only handle the first 16 events, starting at a different event
+    ///     // each time.
+    ///     for event in events.iter_rotating().take(16) {
+    ///         println!("Got an event for {:?}", event.token());
+    ///     }
+    /// #   return Ok(());
+    /// }
+    /// # }
+    /// ```
+    pub fn iter_rotating(&mut self) -> IterRotating<'_> {
+        let len = self.len();
+        let start = if len == 0 { 0 } else { self.rotation % len };
+        self.rotation = self.rotation.wrapping_add(1);
+        IterRotating {
+            inner: self,
+            start,
+            pos: 0,
+        }
+    }
+
+    /// Returns an iterator over the `Event` values, yielding the token and the
+    /// readiness of each event as `Interests`.
+    ///
+    /// The interests contain [`Interests::READABLE`] if the event is readable
+    /// and [`Interests::WRITABLE`] if the event is writable. Events for which
+    /// the read or write side is closed, or which report an error, are also
+    /// reported as readable respectively writable, since the operation won't
+    /// block (it will return the end of the stream or the error instead).
+    /// Events that are neither readable nor writable, e.g. AIO completion
+    /// events, are skipped; use [`iter`] to handle those.
+    ///
+    /// [`iter`]: Events::iter
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    /// use std::time::Duration;
+    ///
+    /// let mut events = Events::with_capacity(1024);
+    /// let mut poll = Poll::new()?;
+    ///
+    /// // Register handles with `poll`.
+    ///
+    /// poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+    ///
+    /// for (token, interests) in events.iter_ready() {
+    ///     if interests.is_readable() {
+    ///         println!("{:?} is readable", token);
+    ///     }
+    ///     if interests.is_writable() {
+    ///         println!("{:?} is writable", token);
+    ///     }
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn iter_ready(&self) -> IterReady<'_> {
+        IterReady { inner: self.iter() }
+    }
+
+    /// Returns an iterator that removes all `Event`s from `self`, yielding
+    /// them by value.
+    ///
+    /// Once the iterator is dropped `self` is empty, even if not all events
+    /// were yielded. The capacity of `self` remains the same, so it can be
+    /// reused in the next call to [`poll`].
+    ///
+    /// [`poll`]: crate::Poll::poll
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    /// use std::time::Duration;
+    ///
+    /// let mut events = Events::with_capacity(1024);
+    /// let mut poll = Poll::new()?;
+    ///
+    /// // Register handles with `poll`.
+    ///
+    /// poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+    ///
+    /// for event in events.drain() {
+    ///     println!("Got an event for {:?}", event.token());
+    /// }
+    /// assert!(events.is_empty());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_> {
+        Drain {
+            inner: self,
+            pos: 0,
+        }
+    }
+
     /// Clearing all `Event` values from container explicitly.
     ///
+    /// This doesn't change the [`capacity`] of `self`.
+    ///
+    /// [`capacity`]: Events::capacity
+    ///
     /// # Notes
     ///
-    /// Events are cleared before every `poll`, so this is not required.
+    /// Events are cleared before every [`poll`], so this is not required. It
+    /// is however required when using [`poll_append`], which doesn't clear the
+    /// events.
+    ///
+    /// [`poll`]: crate::Poll::poll
+    /// [`poll_append`]: crate::Poll::poll_append
     ///
     /// # Examples
     ///
@@ -173,6 +441,7 @@ impl Events {
     /// // Clear all events.
     /// events.clear();
     /// assert!(events.is_empty());
+    /// assert_eq!(1024, events.capacity());
     /// #     Ok(())
     /// # }
     /// ```
@@ -197,15 +466,75 @@ impl<'a> IntoIterator for &'a Events {
 impl<'a> Iterator for Iter<'a> {
     type Item = &'a Event;
 
+    fn next(&mut self) -> Option<Self::Item> {
+        let ret = self.inner.get(self.pos);
+        self.pos += 1;
+        ret
+    }
+}
+
+impl<'a> Iterator for IterReady<'a> {
+    type Item = (Token, Interests);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.inner.next() {
+            let error = event.is_error();
+            let readable = event.is_readable() || event.is_read_closed() || error;
+            let writable = event.is_writable() || event.is_write_closed() || error;
+            let interests = match (readable, writable) {
+                (true, true) => Interests::READABLE | Interests::WRITABLE,
+                (true, false) => Interests::READABLE,
+                (false, true) => Interests::WRITABLE,
+                (false, false) => continue,
+            };
+            return Some((event.token(), interests));
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for IterRotating<'a> {
+    type Item = &'a Event;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.inner.len();
+        if self.pos >= len {
+            return None;
+        }
+        let index = (self.start + self.pos) % len;
+        self.pos += 1;
+        self.inner.get(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.inner.len().saturating_sub(self.pos);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> Iterator for Drain<'a> {
+    type Item = Event;
+
     fn next(&mut self) -> Option<Self::Item> {
         let ret = self
             .inner
             .inner
             .get(self.pos)
-            .map(Event::from_sys_event_ref);
+            .map(|sys_event| Event::from_sys_event(*sys_event));
         self.pos += 1;
         ret
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.inner.len().saturating_sub(self.pos);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> Drop for Drain<'a> {
+    fn drop(&mut self) {
+        self.inner.clear();
+    }
 }
 
 impl fmt::Debug for Events {
@@ -32,6 +32,16 @@ const WRITABLE: u8 = 0b0_010;
 const AIO: u8 = 0b0_100;
 #[cfg_attr(not(target_os = "freebsd"), allow(dead_code))]
 const LIO: u8 = 0b1_000;
+#[cfg_attr(
+    not(any(target_os = "android", target_os = "linux", target_os = "solaris")),
+    allow(dead_code)
+)]
+const PRIORITY: u8 = 0b1_0000;
+// All interests that can be used with any kind of source, see `Interests::all`.
+#[cfg(any(target_os = "android", target_os = "linux", target_os = "solaris"))]
+const ALL: u8 = READABLE | WRITABLE | PRIORITY;
+#[cfg(not(any(target_os = "android", target_os = "linux", target_os = "solaris")))]
+const ALL: u8 = READABLE | WRITABLE;
 
 impl Interests {
     /// Returns a `Interests` set representing readable interests.
@@ -53,6 +63,29 @@ impl Interests {
     #[cfg(target_os = "freebsd")]
     pub const LIO: Interests = Interests(unsafe { NonZeroU8::new_unchecked(LIO) });
 
+    /// Returns a `Interests` set representing priority interests, e.g. for
+    /// out-of-band TCP data.
+    #[cfg(any(target_os = "android", target_os = "linux", target_os = "solaris"))]
+    pub const PRIORITY: Interests = Interests(unsafe { NonZeroU8::new_unchecked(PRIORITY) });
+
+    /// Returns a `Interests` set representing all interests supported on the
+    /// current platform.
+    ///
+    /// This includes readable and writable interests, and priority interests
+    /// on platforms that support them. The AIO and LIO interests are not
+    /// included, they only apply to specific kinds of sources.
+    ///
+    /// ```
+    /// use mio::Interests;
+    ///
+    /// let interests = Interests::all();
+    /// assert!(interests.is_readable());
+    /// assert!(interests.is_writable());
+    /// ```
+    pub const fn all() -> Interests {
+        Interests(unsafe { NonZeroU8::new_unchecked(ALL) })
+    }
+
     /// Add together two `Interests`.
     ///
     /// This does the same thing as the `BitOr` implementation, but is a
@@ -70,12 +103,65 @@ impl Interests {
         Interests(unsafe { NonZeroU8::new_unchecked(self.0.get() | other.0.get()) })
     }
 
+    /// Removes `other` `Interests` from `self`.
+    ///
+    /// Returns `None` if the set would be empty after removing `other`, as
+    /// an empty `Interests` set can't be used in registering.
+    ///
+    /// ```
+    /// use mio::Interests;
+    ///
+    /// let interests = Interests::READABLE | Interests::WRITABLE;
+    /// assert_eq!(interests.remove(Interests::WRITABLE), Some(Interests::READABLE));
+    ///
+    /// // Removing all interests results in `None`.
+    /// assert!(interests.remove(interests).is_none());
+    /// ```
+    pub fn remove(self, other: Interests) -> Option<Interests> {
+        NonZeroU8::new(self.0.get() & !other.0.get()).map(Interests)
+    }
+
+    /// Returns an iterator over the individual interests in the set.
+    ///
+    /// ```
+    /// use mio::Interests;
+    ///
+    /// let interests = Interests::READABLE | Interests::WRITABLE;
+    /// let all: Vec<Interests> = interests.iter().collect();
+    /// assert_eq!(all, [Interests::READABLE, Interests::WRITABLE]);
+    /// ```
+    pub fn iter(self) -> impl Iterator<Item = Interests> {
+        let bits = self.0.get();
+        (0..8)
+            .map(|shift| 1u8 << shift)
+            .filter(move |bit| bits & bit != 0)
+            .map(|bit| Interests(unsafe { NonZeroU8::new_unchecked(bit) }))
+    }
+
     /// Returns true if the value includes readable readiness.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio::Interests;
+    ///
+    /// assert!((Interests::READABLE | Interests::WRITABLE).is_readable());
+    /// assert!(!Interests::WRITABLE.is_readable());
+    /// ```
     pub const fn is_readable(self) -> bool {
         (self.0.get() & READABLE) != 0
     }
 
     /// Returns true if the value includes writable readiness.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio::Interests;
+    ///
+    /// assert!((Interests::READABLE | Interests::WRITABLE).is_writable());
+    /// assert!(!Interests::READABLE.is_writable());
+    /// ```
     pub const fn is_writable(self) -> bool {
         (self.0.get() & WRITABLE) != 0
     }
@@ -89,6 +175,11 @@ impl Interests {
     pub const fn is_lio(self) -> bool {
         (self.0.get() & LIO) != 0
     }
+
+    /// Returns true if `Interests` contains priority readiness.
+    pub const fn is_priority(self) -> bool {
+        (self.0.get() & PRIORITY) != 0
+    }
 }
 
 impl ops::BitOr for Interests {
@@ -139,6 +230,16 @@ impl fmt::Debug for Interests {
                 one = true
             }
         }
+        #[cfg(any(target_os = "android", target_os = "linux", target_os = "solaris"))]
+        {
+            if self.is_priority() {
+                if one {
+                    write!(fmt, " | ")?
+                }
+                write!(fmt, "PRIORITY")?;
+                one = true
+            }
+        }
         #[cfg(any(target_os = "freebsd"))]
         {
             if self.is_lio() {
@@ -153,3 +254,9 @@ impl fmt::Debug for Interests {
         Ok(())
     }
 }
+
+impl fmt::Display for Interests {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, fmt)
+    }
+}
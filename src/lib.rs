@@ -121,13 +121,26 @@ pub mod net;
 #[doc(no_inline)]
 pub use event::Events;
 pub use interests::Interests;
-pub use poll::{Poll, Registry};
+#[cfg(feature = "poll-stats")]
+pub use poll::PollStats;
+pub use poll::{is_fatal_poll_error, Poll, Registration, Registry, Trigger};
 pub use token::Token;
-pub use waker::Waker;
+pub use waker::{PayloadWaker, Payloads, WakeReason, Waker, WakerSet};
 
 #[cfg(unix)]
 pub mod unix {
     //! Unix only extensions.
     pub use crate::sys::SocketAddr;
     pub use crate::sys::SourceFd;
+    pub use crate::sys::UCred;
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub use crate::sys::{EventFd, SignalFd, SignalInfo, TimerFd};
+
+    pub mod pipe {
+        //! Unix pipe.
+        //!
+        //! See the [`new`] function for documentation.
+
+        pub use crate::sys::pipe::{new, Receiver, Sender};
+    }
 }
@@ -7,11 +7,88 @@
 //!
 //! [portability guidelines]: ../struct.Poll.html#portability
 
+use std::io::{self, IoSlice};
+use std::{mem, slice};
+
+/// Advance `bufs` past the first `n` bytes, e.g. after a partial
+/// `write_vectored`.
+///
+/// Buffers that were written completely are removed from `bufs` and the
+/// first remaining buffer is shortened to start at the first byte that wasn't
+/// written. After writing all bytes `bufs` is empty.
+///
+/// # Panics
+///
+/// This panics if `n` is larger than the combined length of `bufs`.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::{advance_slices, TcpStream};
+/// use std::io::{self, IoSlice, Write};
+///
+/// fn write_all_vectored(
+///     stream: &mut TcpStream,
+///     mut bufs: &mut [IoSlice<'_>],
+/// ) -> io::Result<()> {
+///     while !bufs.is_empty() {
+///         // In a real program `WouldBlock` errors should be handled by
+///         // waiting for a writable event before continuing.
+///         let n = stream.write_vectored(bufs)?;
+///         advance_slices(&mut bufs, n);
+///     }
+///     Ok(())
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+pub fn advance_slices<'a>(bufs: &mut &mut [IoSlice<'a>], n: usize) {
+    // Number of buffers written completely and their combined length.
+    let mut remove = 0;
+    let mut removed_len = 0;
+    for buf in bufs.iter() {
+        if removed_len + buf.len() > n {
+            break;
+        }
+        removed_len += buf.len();
+        remove += 1;
+    }
+
+    *bufs = &mut mem::replace(bufs, &mut [])[remove..];
+    let skip = n - removed_len;
+    if let Some(first) = bufs.first_mut() {
+        // This is safe because the bytes are valid for `'a`, which is what
+        // `IoSlice<'a>` guarantees, `Deref` only ties them to `first`.
+        let rest = unsafe { slice::from_raw_parts(first.as_ptr().add(skip), first.len() - skip) };
+        *first = IoSlice::new(rest);
+    } else {
+        assert!(skip == 0, "advancing beyond the length of the slices");
+    }
+}
+
+/// Maps a [`WouldBlock`] error to `Ok(None)`, used by the `try_*` methods.
+///
+/// [`WouldBlock`]: io::ErrorKind::WouldBlock
+fn try_io<T>(result: io::Result<T>) -> io::Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
 mod tcp;
-pub use self::tcp::{TcpListener, TcpStream};
+pub use self::tcp::{
+    is_transient_accept_error, AcceptIter, TcpKeepalive, TcpListener, TcpListenerOpts, TcpSocket,
+    TcpStream,
+};
 
 mod udp;
-pub use self::udp::UdpSocket;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use self::udp::{PktInfo, RecvFlags, RecvResult};
+pub use self::udp::{RecvMsg, SendMsg, UdpSocket, UdpSocketOpts};
 
 #[cfg(unix)]
 mod uds;
@@ -43,7 +43,56 @@ pub struct TcpListener {
     selector_id: SelectorId,
 }
 
+/// Options set on the socket of a [`TcpListener`] before it's bound, see
+/// [`TcpListener::bind_with`].
+///
+/// The default value mirrors the options set by [`TcpListener::bind`].
+#[derive(Copy, Clone, Debug)]
+pub struct TcpListenerOpts {
+    /// Set the `SO_REUSEPORT` option on the socket, allowing multiple
+    /// listeners to be bound to the same address.
+    ///
+    /// On Linux the incoming connections are balanced between all listeners
+    /// bound to the address. Not supported on Solaris and Windows, where
+    /// binding with this option enabled will return an error.
+    pub reuse_port: bool,
+    /// The maximum number of pending connections passed to `listen`, defaults
+    /// to 1024.
+    ///
+    /// The OS silently truncates this to its own maximum, e.g. `SOMAXCONN` or
+    /// `net.core.somaxconn` on Linux. Values larger than `i32::MAX` are
+    /// truncated to `i32::MAX` before being passed to the OS.
+    pub backlog: u32,
+    /// Set the `IPV6_V6ONLY` option on the socket, defaults to `None`.
+    ///
+    /// If `Some(false)` a listener bound to an IPv6 address, e.g. `[::]`, also
+    /// accepts IPv4 connections, using IPv4-mapped IPv6 addresses. If
+    /// `Some(true)` it only accepts IPv6 connections. If `None` the option is
+    /// not set and the platform default is used, which differs between
+    /// platforms, e.g. Linux allows IPv4 connections by default while
+    /// OpenBSD and Windows don't. Ignored for IPv4 addresses.
+    pub only_v6: Option<bool>,
+}
+
+impl Default for TcpListenerOpts {
+    fn default() -> TcpListenerOpts {
+        TcpListenerOpts {
+            reuse_port: false,
+            backlog: 1024,
+            only_v6: None,
+        }
+    }
+}
+
 impl TcpListener {
+    pub(crate) fn new(sys: sys::TcpListener) -> TcpListener {
+        TcpListener {
+            sys,
+            #[cfg(debug_assertions)]
+            selector_id: SelectorId::new(),
+        }
+    }
+
     /// Convenience method to bind a new TCP listener to the specified address
     /// to receive new connections.
     ///
@@ -53,27 +102,57 @@ impl TcpListener {
     /// 2. Set the `SO_REUSEADDR` option on the socket on Unix.
     /// 3. Bind the socket to the specified address.
     /// 4. Calls `listen` on the socket to prepare it to receive new connections.
+    ///
+    /// To change the options set on the socket before binding it, see
+    /// [`bind_with`].
+    ///
+    /// [`bind_with`]: TcpListener::bind_with
     pub fn bind(addr: SocketAddr) -> io::Result<TcpListener> {
-        sys::TcpListener::bind(addr).map(|sys| TcpListener {
-            sys,
-            #[cfg(debug_assertions)]
-            selector_id: SelectorId::new(),
-        })
+        TcpListener::bind_with(addr, TcpListenerOpts::default())
+    }
+
+    /// Bind a new TCP listener to the specified address, setting the options
+    /// in `opts` on the socket before binding it.
+    ///
+    /// This takes the same steps as [`bind`], see [`TcpListenerOpts`] for the
+    /// available options.
+    ///
+    /// [`bind`]: TcpListener::bind
+    ///
+    /// # Examples
+    ///
+    /// Binding two listeners to the same address, letting the OS balance the
+    /// incoming connections between them.
+    ///
+    #[cfg_attr(not(any(windows, target_os = "solaris")), doc = " ```")]
+    #[cfg_attr(any(windows, target_os = "solaris"), doc = " ```no_run")]
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::{TcpListener, TcpListenerOpts};
+    ///
+    /// let opts = TcpListenerOpts {
+    ///     reuse_port: true,
+    ///     ..TcpListenerOpts::default()
+    /// };
+    ///
+    /// let listener1 = TcpListener::bind_with("127.0.0.1:0".parse()?, opts)?;
+    /// let listener2 = TcpListener::bind_with(listener1.local_addr()?, opts)?;
+    /// # drop((listener1, listener2));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn bind_with(addr: SocketAddr, opts: TcpListenerOpts) -> io::Result<TcpListener> {
+        sys::TcpListener::bind(addr, &opts).map(TcpListener::new)
     }
 
     /// Creates a new `TcpListener` from a standard `net::TcpListener`.
     ///
     /// This function is intended to be used to wrap a TCP listener from the
-    /// standard library in the Mio equivalent. The conversion assumes nothing
-    /// about the underlying listener; ; it is left up to the user to set it
-    /// in non-blocking mode.
-    pub fn from_std(listener: net::TcpListener) -> TcpListener {
-        let sys = sys::TcpListener::from_std(listener);
-        TcpListener {
-            sys,
-            #[cfg(debug_assertions)]
-            selector_id: SelectorId::new(),
-        }
+    /// standard library in the Mio equivalent. The listener is put in
+    /// non-blocking mode, returning an error if that fails.
+    pub fn from_std(listener: net::TcpListener) -> io::Result<TcpListener> {
+        listener.set_nonblocking(true)?;
+        Ok(TcpListener::new(sys::TcpListener::from_std(listener)))
     }
 
     /// Accepts a new `TcpStream`.
@@ -84,12 +163,84 @@ impl TcpListener {
     ///
     /// If an accepted stream is returned, the remote address of the peer is
     /// returned along with it.
+    ///
+    /// Some errors only affect a single incoming connection, or are caused by
+    /// a temporary lack of resources, and leave the listener usable. Use
+    /// [`is_transient_accept_error`] to determine whether or not an error
+    /// should stop the accept loop.
+    ///
+    /// [`is_transient_accept_error`]: crate::net::is_transient_accept_error
     pub fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
         self.sys
             .accept()
             .map(|(sys, addr)| (TcpStream::new(sys), addr))
     }
 
+    /// Accepts a new `TcpStream`, setting the close-on-exec flag and
+    /// non-blocking mode of the accepted stream as requested.
+    ///
+    /// [`accept`] always sets both, which is the safe default. Where
+    /// available both are set atomically using `accept4(2)`, on other
+    /// platforms (e.g. macOS) they are set using `fcntl(2)` right after the
+    /// stream is accepted.
+    ///
+    /// Without the close-on-exec flag the stream's file descriptor is
+    /// inherited by processes started using `exec`, e.g. by
+    /// [`std::process::Command`], which can be used to hand the connection to
+    /// another program. It is inherited by *every* such process though,
+    /// including those started by other threads, keeping the connection open
+    /// for as long as any of them is running. A stream accepted in blocking
+    /// mode should not be registered with [`Poll`], as reading from or writing
+    /// to it would block the event loop.
+    ///
+    /// Only available on Unix.
+    ///
+    /// [`accept`]: TcpListener::accept
+    /// [`Poll`]: crate::Poll
+    #[cfg(unix)]
+    pub fn accept_with_flags(
+        &self,
+        cloexec: bool,
+        nonblocking: bool,
+    ) -> io::Result<(TcpStream, SocketAddr)> {
+        self.sys
+            .accept_with_flags(cloexec, nonblocking)
+            .map(|(sys, addr)| (TcpStream::new(sys), addr))
+    }
+
+    /// Returns an iterator that accepts all pending connections.
+    ///
+    /// The iterator calls [`accept`] for every item and stops, returning
+    /// `None`, once it returns a `WouldBlock` error, i.e. once all pending
+    /// connections are accepted. This matches the "accept until `WouldBlock`"
+    /// pattern required after a readable event for the listener. Other
+    /// errors are returned as `Some(Err(..))`, after which the iteration can
+    /// continue if the error is [transient].
+    ///
+    /// [`accept`]: TcpListener::accept
+    /// [transient]: crate::net::is_transient_accept_error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::TcpListener;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// // Accept all connections that are ready.
+    /// for result in listener.accept_iter() {
+    ///     let (_stream, address) = result?;
+    ///     println!("accepted connection from {}", address);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn accept_iter(&self) -> AcceptIter<'_> {
+        AcceptIter { listener: self }
+    }
+
     /// Returns the local socket address of this listener.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         self.sys.local_addr()
@@ -135,6 +286,73 @@ impl TcpListener {
     }
 }
 
+/// Returns `true` if `err`, returned by [`TcpListener::accept`], is transient.
+///
+/// A transient error doesn't affect the listener itself, e.g. the incoming
+/// connection was aborted by the peer before it was accepted (`ECONNABORTED`)
+/// or the process ran out of file descriptors (`EMFILE` and `ENFILE`). After
+/// such an error the listener can continue to be used to accept connections.
+/// Errors that are not transient usually mean the listener is no longer
+/// usable.
+///
+/// The set of errors differs per platform, e.g. on Linux pending network
+/// errors of the new connection (such as `ENETUNREACH`) are also returned by
+/// `accept`. `WouldBlock` is not considered a transient error, it should be
+/// handled by waiting for the next readiness event.
+///
+/// Note that on running out of file descriptors the connection remains in the
+/// backlog. Calling `accept` again right away will likely return the same
+/// error and, when using edge-triggered notifications, no new event will be
+/// returned for it. So it's advised to back off for some time before accepting
+/// again.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::{is_transient_accept_error, TcpListener};
+/// use std::io;
+///
+/// let listener = TcpListener::bind("127.0.0.1:0".parse()?)?;
+///
+/// // Accept all connections that are ready.
+/// loop {
+///     match listener.accept() {
+///         Ok((_stream, address)) => println!("accepted connection from {}", address),
+///         Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+///         Err(ref err) if is_transient_accept_error(err) => continue,
+///         Err(err) => return Err(err.into()),
+///     }
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+pub fn is_transient_accept_error(err: &io::Error) -> bool {
+    sys::is_transient_accept_error(err)
+}
+
+/// Iterator accepting the pending connections of a [`TcpListener`].
+///
+/// This struct is created by the [`accept_iter`] method on [`TcpListener`].
+///
+/// [`accept_iter`]: TcpListener::accept_iter
+#[derive(Debug)]
+pub struct AcceptIter<'a> {
+    listener: &'a TcpListener,
+}
+
+impl<'a> Iterator for AcceptIter<'a> {
+    type Item = io::Result<(TcpStream, SocketAddr)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.listener.accept() {
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => None,
+            result => Some(result),
+        }
+    }
+}
+
 impl event::Source for TcpListener {
     fn register(&self, registry: &Registry, token: Token, interests: Interests) -> io::Result<()> {
         #[cfg(debug_assertions)]
@@ -1,5 +1,8 @@
 mod listener;
-pub use self::listener::TcpListener;
+pub use self::listener::{is_transient_accept_error, AcceptIter, TcpListener, TcpListenerOpts};
+
+mod socket;
+pub use self::socket::TcpSocket;
 
 mod stream;
-pub use self::stream::TcpStream;
+pub use self::stream::{TcpKeepalive, TcpStream};
@@ -0,0 +1,158 @@
+use super::{TcpListener, TcpStream};
+use crate::sys;
+
+use std::io;
+use std::net::SocketAddr;
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+#[cfg(windows)]
+use std::os::windows::io::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
+
+/// A non-blocking TCP socket used to configure a stream or listener before
+/// connecting or binding it.
+///
+/// [`TcpStream::connect`] and [`TcpListener::bind`] create the socket and
+/// connect or bind it in a single call. `TcpSocket` separates the two steps,
+/// allowing options to be set, e.g. using [`set_reuseaddr`], and the socket to
+/// be bound to a specific local address before it's turned into a
+/// [`TcpListener`] using [`listen`], or into a [`TcpStream`] using
+/// [`connect`].
+///
+/// The socket will be closed when the value is dropped.
+///
+/// [`set_reuseaddr`]: TcpSocket::set_reuseaddr
+/// [`listen`]: TcpSocket::listen
+/// [`connect`]: TcpSocket::connect
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::net::TcpSocket;
+///
+/// let socket = TcpSocket::new_v4()?;
+/// socket.set_reuseaddr(true)?;
+/// socket.bind("127.0.0.1:0".parse()?)?;
+/// let listener = socket.listen(128)?;
+///
+/// // Connect to the listener from a specific local address.
+/// let socket = TcpSocket::new_v4()?;
+/// socket.bind("127.0.0.1:0".parse()?)?;
+/// let stream = socket.connect(listener.local_addr()?)?;
+/// #     drop(stream);
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TcpSocket {
+    sys: sys::TcpSocket,
+}
+
+impl TcpSocket {
+    /// Create a new IPv4 TCP socket.
+    pub fn new_v4() -> io::Result<TcpSocket> {
+        sys::TcpSocket::new_v4().map(|sys| TcpSocket { sys })
+    }
+
+    /// Create a new IPv6 TCP socket.
+    pub fn new_v6() -> io::Result<TcpSocket> {
+        sys::TcpSocket::new_v6().map(|sys| TcpSocket { sys })
+    }
+
+    /// Sets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// On Unix this allows a listener to bind to an address that still has
+    /// connections in the `TIME_WAIT` state, [`TcpListener::bind`] always
+    /// sets it. On Windows it allows multiple sockets to bind to the same
+    /// address, which is usually not desired.
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        self.sys.set_reuseaddr(reuseaddr)
+    }
+
+    /// Sets the value of the `SO_REUSEPORT` option on this socket, allowing
+    /// multiple sockets to be bound to the same address.
+    ///
+    /// Not supported on Solaris and Windows, where enabling the option returns
+    /// an error.
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        self.sys.set_reuseport(reuseport)
+    }
+
+    /// Bind the socket to `addr`.
+    ///
+    /// For a socket that is turned into a [`TcpStream`] this determines the
+    /// local address of the connection. The address family must match the
+    /// family the socket was created with.
+    pub fn bind(&self, addr: SocketAddr) -> io::Result<()> {
+        self.sys.bind(addr)
+    }
+
+    /// Start listening for incoming connections, turning the socket into a
+    /// [`TcpListener`].
+    ///
+    /// The socket must be bound using [`bind`] first. `backlog` is the
+    /// maximum number of pending connections, see
+    /// [`TcpListenerOpts::backlog`].
+    ///
+    /// [`bind`]: TcpSocket::bind
+    /// [`TcpListenerOpts::backlog`]: crate::net::TcpListenerOpts::backlog
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        self.sys.listen(backlog).map(TcpListener::new)
+    }
+
+    /// Issue a non-blocking connect to `addr`, turning the socket into a
+    /// [`TcpStream`].
+    ///
+    /// Like [`TcpStream::connect`] the connection is not yet established when
+    /// this returns, see its documentation for how to determine when it is.
+    pub fn connect(self, addr: SocketAddr) -> io::Result<TcpStream> {
+        self.sys.connect(addr).map(TcpStream::new)
+    }
+}
+
+#[cfg(unix)]
+impl AsRawFd for TcpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.sys.as_raw_fd()
+    }
+}
+
+#[cfg(unix)]
+impl FromRawFd for TcpSocket {
+    unsafe fn from_raw_fd(fd: RawFd) -> TcpSocket {
+        TcpSocket {
+            sys: FromRawFd::from_raw_fd(fd),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl IntoRawFd for TcpSocket {
+    fn into_raw_fd(self) -> RawFd {
+        self.sys.into_raw_fd()
+    }
+}
+
+#[cfg(windows)]
+impl AsRawSocket for TcpSocket {
+    fn as_raw_socket(&self) -> RawSocket {
+        self.sys.as_raw_socket()
+    }
+}
+
+#[cfg(windows)]
+impl FromRawSocket for TcpSocket {
+    unsafe fn from_raw_socket(socket: RawSocket) -> TcpSocket {
+        TcpSocket {
+            sys: FromRawSocket::from_raw_socket(socket),
+        }
+    }
+}
+
+#[cfg(windows)]
+impl IntoRawSocket for TcpSocket {
+    fn into_raw_socket(self) -> RawSocket {
+        self.sys.into_raw_socket()
+    }
+}
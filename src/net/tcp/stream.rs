@@ -6,7 +6,10 @@ use std::net::SocketAddr;
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 #[cfg(windows)]
 use std::os::windows::io::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
+use std::sync::Mutex;
+use std::time::Duration;
 
+use crate::net::{try_io, TcpSocket};
 #[cfg(debug_assertions)]
 use crate::poll::SelectorId;
 use crate::{event, sys, Interests, Registry, Token};
@@ -43,16 +46,102 @@ use crate::{event, sys, Interests, Registry, Token};
 /// ```
 pub struct TcpStream {
     sys: sys::TcpStream,
+    /// Peer address cached by `cached_peer_addr`.
+    peer_addr: Mutex<Option<SocketAddr>>,
     #[cfg(debug_assertions)]
     selector_id: SelectorId,
 }
 
+/// TCP keepalive parameters, see [`TcpStream::set_keepalive`].
+///
+/// Parameters that are not set use the defaults of the OS, e.g. on Linux the
+/// `net.ipv4.tcp_keepalive_*` sysctls.
+///
+/// Not all parameters are supported on all platforms, unsupported parameters
+/// are ignored:
+///
+/// * `time`: supported on all platforms except OpenBSD and Solaris.
+/// * `interval`: supported on Android, DragonFly BSD, FreeBSD, Linux, NetBSD
+///   and Windows.
+/// * `retries`: supported on Android, DragonFly BSD, FreeBSD, Linux and
+///   NetBSD.
+///
+/// On Windows `time` and `interval` can only be set together, if only one of
+/// them is set the other defaults to the Windows default (2 hours and 1 second
+/// respectively).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct TcpKeepalive {
+    time: Option<Duration>,
+    interval: Option<Duration>,
+    retries: Option<u32>,
+}
+
+impl TcpKeepalive {
+    /// Returns new keepalive parameters, using the defaults of the OS.
+    pub fn new() -> TcpKeepalive {
+        TcpKeepalive::default()
+    }
+
+    /// Set the amount of time the connection must be idle before the first
+    /// keepalive probe is sent.
+    ///
+    /// This maps to the `TCP_KEEPIDLE` option, or `TCP_KEEPALIVE` on macOS
+    /// and iOS. It has a granularity of whole seconds (milliseconds on
+    /// Windows), durations with a sub-second part are rounded up.
+    pub fn with_time(self, time: Duration) -> TcpKeepalive {
+        TcpKeepalive {
+            time: Some(time),
+            ..self
+        }
+    }
+
+    /// Set the amount of time between keepalive probes.
+    ///
+    /// This maps to the `TCP_KEEPINTVL` option. It has the same granularity
+    /// as [`with_time`].
+    ///
+    /// [`with_time`]: TcpKeepalive::with_time
+    pub fn with_interval(self, interval: Duration) -> TcpKeepalive {
+        TcpKeepalive {
+            interval: Some(interval),
+            ..self
+        }
+    }
+
+    /// Set the number of unanswered keepalive probes after which the
+    /// connection is dropped.
+    ///
+    /// This maps to the `TCP_KEEPCNT` option.
+    pub fn with_retries(self, retries: u32) -> TcpKeepalive {
+        TcpKeepalive {
+            retries: Some(retries),
+            ..self
+        }
+    }
+
+    /// Returns the idle time before the first keepalive probe, if set.
+    pub fn time(&self) -> Option<Duration> {
+        self.time
+    }
+
+    /// Returns the interval between keepalive probes, if set.
+    pub fn interval(&self) -> Option<Duration> {
+        self.interval
+    }
+
+    /// Returns the number of keepalive probes, if set.
+    pub fn retries(&self) -> Option<u32> {
+        self.retries
+    }
+}
+
 use std::net::Shutdown;
 
 impl TcpStream {
     pub(crate) fn new(sys: sys::TcpStream) -> TcpStream {
         TcpStream {
             sys,
+            peer_addr: Mutex::new(None),
             #[cfg(debug_assertions)]
             selector_id: SelectorId::new(),
         }
@@ -60,33 +149,61 @@ impl TcpStream {
 
     /// Create a new TCP stream and issue a non-blocking connect to the
     /// specified address.
+    ///
+    /// The connect is usually still in progress when this returns. Register
+    /// the stream with writable interests and, after each writable event,
+    /// use [`is_connected`] to determine whether the connect has completed
+    /// or failed.
+    ///
+    /// [`is_connected`]: TcpStream::is_connected
     pub fn connect(addr: SocketAddr) -> io::Result<TcpStream> {
-        sys::TcpStream::connect(addr).map(|sys| TcpStream {
-            sys,
-            #[cfg(debug_assertions)]
-            selector_id: SelectorId::new(),
-        })
+        sys::TcpStream::connect(addr).map(TcpStream::new)
+    }
+
+    /// Create a new TCP stream bound to the `local` address and issue a
+    /// non-blocking connect to the `remote` address.
+    ///
+    /// This is useful to select the source address of the connection on a
+    /// machine with multiple addresses. Use [`TcpSocket`] to set other
+    /// options before connecting.
+    ///
+    /// [`TcpSocket`]: crate::net::TcpSocket
+    pub fn connect_from(local: SocketAddr, remote: SocketAddr) -> io::Result<TcpStream> {
+        let socket = match local {
+            SocketAddr::V4(..) => TcpSocket::new_v4()?,
+            SocketAddr::V6(..) => TcpSocket::new_v6()?,
+        };
+        socket.bind(local)?;
+        socket.connect(remote)
     }
 
     /// Creates a new `TcpStream` from a standard `net::TcpStream`.
     ///
     /// This function is intended to be used to wrap a TCP stream from the
-    /// standard library in the Mio equivalent. The conversion assumes nothing
-    /// about the underlying stream; it is left up to the user to set it in
-    /// non-blocking mode.
+    /// standard library in the Mio equivalent. The stream is put in
+    /// non-blocking mode, returning an error if that fails.
     ///
     /// # Note
     ///
     /// The TCP stream here will not have `connect` called on it, so it
     /// should already be connected via some other means (be it manually, or
     /// the standard library).
-    pub fn from_std(stream: net::TcpStream) -> TcpStream {
-        let sys = sys::TcpStream::from_std(stream);
-        TcpStream {
-            sys,
-            #[cfg(debug_assertions)]
-            selector_id: SelectorId::new(),
-        }
+    pub fn from_std(stream: net::TcpStream) -> io::Result<TcpStream> {
+        stream.set_nonblocking(true)?;
+        sys::TcpStream::from_std(stream).map(TcpStream::new)
+    }
+
+    /// Converts the `TcpStream` into a standard `net::TcpStream`, the
+    /// reverse of [`from_std`].
+    ///
+    /// This can be used to hand the connection off to code using blocking
+    /// I/O. The socket is left in non-blocking mode, use
+    /// `net::TcpStream::set_nonblocking(false)` to make it blocking. The
+    /// stream should be deregistered before it's converted.
+    ///
+    /// [`from_std`]: TcpStream::from_std
+    pub fn into_std(self) -> io::Result<net::TcpStream> {
+        Ok(self.sys.into_std())
     }
 
     /// Returns the socket address of the remote peer of this TCP connection.
@@ -94,6 +211,25 @@ impl TcpStream {
         self.sys.peer_addr()
     }
 
+    /// Returns the socket address of the remote peer of this TCP connection,
+    /// caching it after the first successful call.
+    ///
+    /// The peer address of a connected stream never changes, so this avoids
+    /// a system call on every call after the first. Errors, e.g. when the
+    /// stream isn't connected yet, are not cached. Once cached the address is
+    /// returned even if the connection is reset later.
+    pub fn cached_peer_addr(&self) -> io::Result<SocketAddr> {
+        let mut cached = self.peer_addr.lock().unwrap();
+        match *cached {
+            Some(addr) => Ok(addr),
+            None => {
+                let addr = self.sys.peer_addr()?;
+                *cached = Some(addr);
+                Ok(addr)
+            }
+        }
+    }
+
     /// Returns the socket address of the local half of this TCP connection.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         self.sys.local_addr()
@@ -108,6 +244,7 @@ impl TcpStream {
     pub fn try_clone(&self) -> io::Result<TcpStream> {
         self.sys.try_clone().map(|s| TcpStream {
             sys: s,
+            peer_addr: Mutex::new(*self.peer_addr.lock().unwrap()),
             #[cfg(debug_assertions)]
             selector_id: self.selector_id.clone(),
         })
@@ -118,8 +255,17 @@ impl TcpStream {
     /// This function will cause all pending and future I/O on the specified
     /// portions to return immediately with an appropriate value (see the
     /// documentation of `Shutdown`).
+    ///
+    /// Shutting down a connection that is already shut down is not an error,
+    /// i.e. calling this function multiple times is fine. Some platforms
+    /// return a `NotConnected` error in this case, e.g. macOS after the peer
+    /// closed the connection, so this function maps `NotConnected` errors to
+    /// success on all platforms.
     pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
-        self.sys.shutdown(how)
+        match self.sys.shutdown(how) {
+            Err(ref err) if err.kind() == io::ErrorKind::NotConnected => Ok(()),
+            result => result,
+        }
     }
 
     /// Sets the value of the `TCP_NODELAY` option on this socket.
@@ -132,9 +278,14 @@ impl TcpStream {
     ///
     /// # Notes
     ///
+    /// On Unix this may be called directly after [`connect`], before the
+    /// connection is established.
+    ///
     /// On Windows make sure the stream is connected before calling this method,
     /// by receiving an (writable) event. Trying to set `nodelay` on an
     /// unconnected `TcpStream` is undefined behavior.
+    ///
+    /// [`connect`]: TcpStream::connect
     pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
         self.sys.set_nodelay(nodelay)
     }
@@ -154,10 +305,67 @@ impl TcpStream {
         self.sys.nodelay()
     }
 
-    /// Sets the value for the `IP_TTL` option on this socket.
+    /// Sets the value of the `TCP_QUICKACK` option on this socket.
+    ///
+    /// If set, acknowledgments are sent immediately rather than delayed,
+    /// which can reduce the latency of request/response protocols.
+    ///
+    /// Only supported on Android and Linux, on other platforms this returns
+    /// an error.
+    ///
+    /// # Notes
+    ///
+    /// The option isn't permanent: the kernel may reset it, e.g. after
+    /// receiving data, depending on its internal state. To keep acknowledging
+    /// quickly the option must be set again, e.g. after each read.
+    pub fn set_quickack(&self, quickack: bool) -> io::Result<()> {
+        self.sys.set_quickack(quickack)
+    }
+
+    /// Gets the value of the `TCP_QUICKACK` option on this socket.
+    ///
+    /// For more information about this option, see [`set_quickack`].
+    ///
+    /// [`set_quickack`]: TcpStream::set_quickack
+    pub fn quickack(&self) -> io::Result<bool> {
+        self.sys.quickack()
+    }
+
+    /// Sets the value of the `SO_MARK` option on this socket.
+    ///
+    /// The mark is set on every packet sent by the socket, which can be used
+    /// by netfilter (e.g. `iptables`) to classify the packets and for policy
+    /// routing.
+    ///
+    /// Setting the mark requires the `CAP_NET_ADMIN` capability, without it
+    /// this returns an error of kind `PermissionDenied`.
+    ///
+    /// Only available on Android and Linux.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_mark(&self, mark: u32) -> io::Result<()> {
+        self.sys.set_mark(mark)
+    }
+
+    /// Gets the value of the `SO_MARK` option on this socket.
+    ///
+    /// For more information about this option, see [`set_mark`].
+    ///
+    /// Only available on Android and Linux.
+    ///
+    /// [`set_mark`]: TcpStream::set_mark
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn mark(&self) -> io::Result<u32> {
+        self.sys.mark()
+    }
+
+    /// Sets the value for the `IP_TTL` option on this socket, or the
+    /// `IPV6_UNICAST_HOPS` option for IPv6 sockets.
     ///
     /// This value sets the time-to-live field that is used in every packet sent
-    /// from this socket.
+    /// from this socket. Values larger than 255 return an error with kind
+    /// [`InvalidInput`].
+    ///
+    /// [`InvalidInput`]: std::io::ErrorKind::InvalidInput
     ///
     /// # Notes
     ///
@@ -168,7 +376,8 @@ impl TcpStream {
         self.sys.set_ttl(ttl)
     }
 
-    /// Gets the value of the `IP_TTL` option for this socket.
+    /// Gets the value of the `IP_TTL` option for this socket, or the
+    /// `IPV6_UNICAST_HOPS` option for IPv6 sockets.
     ///
     /// For more information about this option, see [`set_ttl`][link].
     ///
@@ -183,6 +392,157 @@ impl TcpStream {
         self.sys.ttl()
     }
 
+    /// Sets the value of the `SO_LINGER` option on this socket.
+    ///
+    /// This value controls how the socket is closed when data remains to be
+    /// sent. If `dur` is `Some`, closing the socket blocks until all data is
+    /// sent or the duration has passed. A duration of zero causes the
+    /// connection to be reset, sending a RST rather than a FIN, when the
+    /// socket is closed. If `dur` is `None` lingering is disabled and the
+    /// socket is closed in the background.
+    ///
+    /// The option has a granularity of whole seconds, durations with a
+    /// sub-second part are rounded up to the next second.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::TcpStream;
+    /// use std::net::TcpListener;
+    /// use std::time::Duration;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// let stream = TcpStream::connect(listener.local_addr()?)?;
+    ///
+    /// // Reset the connection when the stream is dropped.
+    /// stream.set_linger(Some(Duration::from_secs(0)))?;
+    /// assert_eq!(stream.linger()?, Some(Duration::from_secs(0)));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn set_linger(&self, dur: Option<Duration>) -> io::Result<()> {
+        self.sys.set_linger(dur)
+    }
+
+    /// Gets the value of the `SO_LINGER` option on this socket.
+    ///
+    /// For more information about this option, see [`set_linger`].
+    ///
+    /// [`set_linger`]: TcpStream::set_linger
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        self.sys.linger()
+    }
+
+    /// Enables or disables TCP keepalive on this socket.
+    ///
+    /// If `keepalive` is `Some` the `SO_KEEPALIVE` option is enabled and the
+    /// parameters that are set are applied, parameters not supported by the
+    /// platform are ignored (see [`TcpKeepalive`]). If `keepalive` is `None`
+    /// `SO_KEEPALIVE` is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::{TcpKeepalive, TcpStream};
+    /// use std::net::TcpListener;
+    /// use std::time::Duration;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// let stream = TcpStream::connect(listener.local_addr()?)?;
+    ///
+    /// // Start probing after a minute of inactivity, sending a probe every 10
+    /// // seconds.
+    /// let keepalive = TcpKeepalive::new()
+    ///     .with_time(Duration::from_secs(60))
+    ///     .with_interval(Duration::from_secs(10));
+    /// stream.set_keepalive(Some(keepalive))?;
+    /// assert!(stream.keepalive()?.is_some());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn set_keepalive(&self, keepalive: Option<TcpKeepalive>) -> io::Result<()> {
+        self.sys.set_keepalive(keepalive)
+    }
+
+    /// Returns the TCP keepalive parameters of this socket, or `None` if the
+    /// `SO_KEEPALIVE` option is disabled.
+    ///
+    /// Only the parameters that can be retrieved on the platform are set in
+    /// the returned value. On Windows none of the parameters can be retrieved.
+    ///
+    /// For more information about this option, see [`set_keepalive`].
+    ///
+    /// [`set_keepalive`]: TcpStream::set_keepalive
+    pub fn keepalive(&self) -> io::Result<Option<TcpKeepalive>> {
+        self.sys.keepalive()
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// Changes the size of the operating system's receive buffer associated
+    /// with the socket. Sizes larger than `i32::MAX` are truncated.
+    ///
+    /// The OS may adjust the size, e.g. Linux doubles the value to allow
+    /// space for bookkeeping overhead and limits it to `net.core.rmem_max`.
+    /// Use [`recv_buffer_size`] to retrieve the actual size.
+    ///
+    /// [`recv_buffer_size`]: TcpStream::recv_buffer_size
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::TcpStream;
+    /// use std::net::TcpListener;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// let socket = TcpStream::connect(listener.local_addr()?)?;
+    ///
+    /// socket.set_recv_buffer_size(256 * 1024)?;
+    /// println!("receive buffer size: {}", socket.recv_buffer_size()?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.sys.set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// This returns the size as reported by the OS, which may differ from
+    /// the size set using [`set_recv_buffer_size`].
+    ///
+    /// [`set_recv_buffer_size`]: TcpStream::set_recv_buffer_size
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.sys.recv_buffer_size()
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// Changes the size of the operating system's send buffer associated
+    /// with the socket. Like the receive buffer the OS may adjust the size,
+    /// see [`set_recv_buffer_size`].
+    ///
+    /// [`set_recv_buffer_size`]: TcpStream::set_recv_buffer_size
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.sys.set_send_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// This returns the size as reported by the OS, which may differ from
+    /// the size set using [`set_send_buffer_size`].
+    ///
+    /// [`set_send_buffer_size`]: TcpStream::set_send_buffer_size
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.sys.send_buffer_size()
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
@@ -192,6 +552,77 @@ impl TcpStream {
         self.sys.take_error()
     }
 
+    /// Returns `true` if the non-blocking connect issued by [`connect`] has
+    /// completed, or `false` if it's still in progress.
+    ///
+    /// If the connect failed the error is retrieved from the `SO_ERROR` option
+    /// (see [`take_error`]) and returned. This doesn't block and can be used
+    /// after receiving a writable event to determine whether or not the
+    /// stream is actually connected, as events may be spurious. `Ok(false)`
+    /// means the same as a `WouldBlock` error would: wait for the next
+    /// writable event and call this again.
+    ///
+    /// Mio doesn't provide timers, to limit the time a connect may take the
+    /// caller should keep track of when [`connect`] was called and drop the
+    /// stream once this still returns `false` after the deadline has passed.
+    ///
+    /// [`connect`]: TcpStream::connect
+    /// [`take_error`]: TcpStream::take_error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::TcpStream;
+    /// use mio::{Events, Interests, Poll, Token};
+    /// use std::net::TcpListener;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:0")?;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(128);
+    ///
+    /// let stream = TcpStream::connect(listener.local_addr()?)?;
+    /// poll.registry().register(&stream, Token(0), Interests::WRITABLE)?;
+    ///
+    /// let deadline = Instant::now() + Duration::from_secs(5);
+    /// while !stream.is_connected()? {
+    ///     let now = Instant::now();
+    ///     if now >= deadline {
+    ///         return Err("connect timed out".into());
+    ///     }
+    ///     poll.poll(&mut events, Some(deadline - now))?;
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn is_connected(&self) -> io::Result<bool> {
+        if let Some(err) = self.take_error()? {
+            return Err(err);
+        }
+
+        match self.peer_addr() {
+            Ok(..) => Ok(true),
+            Err(ref err) if err.kind() == io::ErrorKind::NotConnected => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Resolves the non-blocking connect issued by [`connect`], to be called
+    /// after each writable event.
+    ///
+    /// Returns `Ok(true)` once the connect completed successfully, `Ok(false)`
+    /// while it's still in progress and the error stored in `SO_ERROR` if it
+    /// failed. This is the same as [`is_connected`].
+    ///
+    /// [`connect`]: TcpStream::connect
+    /// [`is_connected`]: TcpStream::is_connected
+    pub fn poll_connected(&self) -> io::Result<bool> {
+        self.is_connected()
+    }
+
     /// Receives data on the socket from the remote address to which it is
     /// connected, without removing that data from the queue. On success,
     /// returns the number of bytes peeked.
@@ -201,6 +632,120 @@ impl TcpStream {
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.sys.peek(buf)
     }
+
+    /// Like [`peek`], except that it peeks into a slice of buffers.
+    ///
+    /// The buffers are filled in order, without removing the data from the
+    /// queue. A subsequent read, e.g. using [`read_vectored`], returns the
+    /// same data.
+    ///
+    /// [`peek`]: TcpStream::peek
+    /// [`read_vectored`]: std::io::Read::read_vectored
+    pub fn peek_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.sys.peek_vectored(bufs)
+    }
+
+    /// Reads data from the socket, returning `Ok(None)` instead of an error
+    /// with kind [`WouldBlock`] if no data is available.
+    ///
+    /// All other errors are returned as is. Note that `Ok(Some(0))` means the
+    /// peer closed the writing side of the connection.
+    ///
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::{TcpListener, TcpStream};
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:0".parse()?)?;
+    /// let stream = TcpStream::connect(listener.local_addr()?)?;
+    ///
+    /// let mut buf = [0; 1024];
+    /// match stream.try_read(&mut buf)? {
+    ///     Some(n) => println!("read {} bytes", n),
+    ///     // Wait for a readable event before trying again.
+    ///     None => println!("no data available"),
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn try_read(&self, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        try_io((&self.sys).read(buf))
+    }
+
+    /// Reads all data that is currently available, appending it to `buf`.
+    ///
+    /// Unlike [`Read::read_to_end`] this doesn't treat [`WouldBlock`] as an
+    /// error: it reads until the socket would block, or until the peer closed
+    /// the writing side of the connection, and returns the total number of
+    /// bytes read. `buf` is grown as needed. All other errors are returned as
+    /// is, the bytes read before the error are kept in `buf`.
+    ///
+    /// Note that `Ok(0)` is returned both if no data is available and if the
+    /// peer closed the connection, use [`try_read`] to tell the two apart.
+    ///
+    /// [`Read::read_to_end`]: std::io::Read::read_to_end
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+    /// [`try_read`]: TcpStream::try_read
+    pub fn read_available(&self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        const READ_SIZE: usize = 4096;
+        let start = buf.len();
+        loop {
+            let len = buf.len();
+            buf.resize(len + READ_SIZE, 0);
+            match (&self.sys).read(&mut buf[len..]) {
+                Ok(0) => {
+                    buf.truncate(len);
+                    break;
+                }
+                Ok(n) => buf.truncate(len + n),
+                Err(err) => {
+                    buf.truncate(len);
+                    match err.kind() {
+                        io::ErrorKind::WouldBlock => break,
+                        io::ErrorKind::Interrupted => continue,
+                        _ => return Err(err),
+                    }
+                }
+            }
+        }
+        Ok(buf.len() - start)
+    }
+
+    /// Writes data to the socket, returning `Ok(None)` instead of an error
+    /// with kind [`WouldBlock`] if the socket's send buffer is full.
+    ///
+    /// All other errors are returned as is.
+    ///
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+    pub fn try_write(&self, buf: &[u8]) -> io::Result<Option<usize>> {
+        try_io((&self.sys).write(buf))
+    }
+
+    /// Reads data from the socket, blocking until data is available.
+    ///
+    /// This temporarily puts the socket in blocking mode for a single read,
+    /// restoring non-blocking mode afterwards, even if the read fails. It's
+    /// meant for setup code, e.g. reading a handshake, before switching to
+    /// event-driven reads using [`Poll`].
+    ///
+    /// [`Poll`]: crate::Poll
+    ///
+    /// # Notes
+    ///
+    /// The blocking mode is a property of the socket, not of this call. While
+    /// this function is blocked any other operation on the same socket, e.g.
+    /// a write from another thread using `&TcpStream` or a clone created by
+    /// [`try_clone`], is blocking as well. Only use this function when no
+    /// other thread uses the socket.
+    ///
+    /// [`try_clone`]: TcpStream::try_clone
+    pub fn read_blocking(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.sys.read_blocking(buf)
+    }
 }
 
 impl Read for TcpStream {
@@ -295,11 +840,7 @@ impl AsRawFd for TcpStream {
 #[cfg(unix)]
 impl FromRawFd for TcpStream {
     unsafe fn from_raw_fd(fd: RawFd) -> TcpStream {
-        TcpStream {
-            sys: FromRawFd::from_raw_fd(fd),
-            #[cfg(debug_assertions)]
-            selector_id: SelectorId::new(),
-        }
+        TcpStream::new(FromRawFd::from_raw_fd(fd))
     }
 }
 
@@ -313,11 +854,7 @@ impl AsRawSocket for TcpStream {
 #[cfg(windows)]
 impl FromRawSocket for TcpStream {
     unsafe fn from_raw_socket(socket: RawSocket) -> TcpStream {
-        TcpStream {
-            sys: FromRawSocket::from_raw_socket(socket),
-            #[cfg(debug_assertions)]
-            selector_id: SelectorId::new(),
-        }
+        TcpStream::new(FromRawSocket::from_raw_socket(socket))
     }
 }
 
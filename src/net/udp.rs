@@ -7,14 +7,19 @@
 //!
 //! [portability guidelines]: ../struct.Poll.html#portability
 
+use crate::net::try_io;
 #[cfg(debug_assertions)]
 use crate::poll::SelectorId;
 use crate::{event, sys, Interests, Registry, Token};
 
 use std::fmt;
-use std::io;
+use std::io::{self, IoSlice, IoSliceMut};
 use std::net;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::net::IpAddr;
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::ops;
 #[cfg(unix)]
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 #[cfg(windows)]
@@ -94,6 +99,170 @@ pub struct UdpSocket {
     selector_id: SelectorId,
 }
 
+/// Information about a received datagram, see
+/// [`UdpSocket::recv_from_with_info`].
+///
+/// Only available on Android and Linux.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PktInfo {
+    pub(crate) destination: IpAddr,
+    pub(crate) interface_index: u32,
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl PktInfo {
+    /// Returns the destination address of the datagram, i.e. the local
+    /// address the sender sent the datagram to.
+    pub fn destination(&self) -> IpAddr {
+        self.destination
+    }
+
+    /// Returns the index of the interface on which the datagram was received.
+    pub fn interface_index(&self) -> u32 {
+        self.interface_index
+    }
+}
+
+/// Flags for [`UdpSocket::recv_from_with_flags`].
+///
+/// Flags can be combined using the `|` operator.
+///
+/// Only available on Android and Linux.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RecvFlags(u8);
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl RecvFlags {
+    /// Receive the datagram without removing it from the input queue
+    /// (`MSG_PEEK`).
+    pub const PEEK: RecvFlags = RecvFlags(0b01);
+
+    /// Return the full length of the datagram, even if it was truncated
+    /// because the buffer is too small (`MSG_TRUNC`).
+    pub const TRUNC: RecvFlags = RecvFlags(0b10);
+
+    /// Returns true if `self` contains the `PEEK` flag.
+    pub fn is_peek(self) -> bool {
+        self.0 & RecvFlags::PEEK.0 != 0
+    }
+
+    /// Returns true if `self` contains the `TRUNC` flag.
+    pub fn is_trunc(self) -> bool {
+        self.0 & RecvFlags::TRUNC.0 != 0
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl ops::BitOr for RecvFlags {
+    type Output = RecvFlags;
+
+    fn bitor(self, other: RecvFlags) -> RecvFlags {
+        RecvFlags(self.0 | other.0)
+    }
+}
+
+/// Result of [`UdpSocket::recv_from_with_flags`].
+///
+/// Only available on Android and Linux.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RecvResult {
+    pub(crate) truncated: bool,
+    pub(crate) datagram_len: Option<usize>,
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl RecvResult {
+    /// Returns true if the datagram didn't fit in the buffer, in which case
+    /// the remainder of the datagram is discarded (unless [`RecvFlags::PEEK`]
+    /// was used).
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Returns the full length of the datagram.
+    ///
+    /// Returns `None` if the datagram was truncated and [`RecvFlags::TRUNC`]
+    /// wasn't used, in which case the length is unknown.
+    pub fn datagram_len(&self) -> Option<usize> {
+        self.datagram_len
+    }
+}
+
+/// A datagram received by [`UdpSocket::recv_mmsg`].
+#[derive(Debug)]
+pub struct RecvMsg<'a> {
+    pub(crate) buf: &'a mut [u8],
+    pub(crate) len: usize,
+    pub(crate) addr: Option<SocketAddr>,
+}
+
+impl<'a> RecvMsg<'a> {
+    /// Create a new message which receives a datagram into `buf`.
+    pub fn new(buf: &'a mut [u8]) -> RecvMsg<'a> {
+        RecvMsg {
+            buf,
+            len: 0,
+            addr: None,
+        }
+    }
+
+    /// Returns the received data.
+    ///
+    /// This is empty if no datagram was received into this message.
+    pub fn data(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Returns the address the datagram was received from.
+    ///
+    /// Returns `None` if no datagram was received into this message.
+    pub fn addr(&self) -> Option<SocketAddr> {
+        self.addr
+    }
+}
+
+/// A datagram to send using [`UdpSocket::send_mmsg`].
+#[derive(Copy, Clone, Debug)]
+pub struct SendMsg<'a> {
+    pub(crate) buf: &'a [u8],
+    pub(crate) addr: SocketAddr,
+}
+
+impl<'a> SendMsg<'a> {
+    /// Create a new message which sends `buf` to `addr`.
+    pub fn new(buf: &'a [u8], addr: SocketAddr) -> SendMsg<'a> {
+        SendMsg { buf, addr }
+    }
+}
+
+/// Options set on the socket of a [`UdpSocket`] before it's bound, see
+/// [`UdpSocket::bind_with`].
+///
+/// The default value mirrors the options set by [`UdpSocket::bind`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct UdpSocketOpts {
+    /// Set the `IPV6_V6ONLY` option on the socket, defaults to `None`.
+    ///
+    /// If `Some(false)` a socket bound to an IPv6 address, e.g. `[::]`, also
+    /// receives IPv4 datagrams, using IPv4-mapped IPv6 addresses. If
+    /// `Some(true)` it only receives IPv6 datagrams. If `None` the option is
+    /// not set and the platform default is used. Ignored for IPv4 addresses.
+    pub only_v6: Option<bool>,
+    /// Set the `SO_REUSEADDR` option on the socket, defaults to `false`.
+    ///
+    /// This allows multiple sockets to be bound to the same multicast address,
+    /// e.g. for mDNS.
+    pub reuse_addr: bool,
+    /// Set the `SO_REUSEPORT` option on the socket, defaults to `false`.
+    ///
+    /// Not supported on Solaris and Windows, where binding with this option
+    /// enabled will return an error.
+    pub reuse_port: bool,
+}
+
 impl UdpSocket {
     /// Creates a UDP socket from the given address.
     ///
@@ -120,7 +289,34 @@ impl UdpSocket {
     /// # }
     /// ```
     pub fn bind(addr: SocketAddr) -> io::Result<UdpSocket> {
-        sys::UdpSocket::bind(addr).map(|sys| UdpSocket {
+        UdpSocket::bind_with(addr, UdpSocketOpts::default())
+    }
+
+    /// Creates a UDP socket from the given address, setting the options in
+    /// `opts` on the socket before binding it.
+    ///
+    /// See [`UdpSocketOpts`] for the available options.
+    ///
+    /// # Examples
+    ///
+    /// Binding a dual-stack socket, receiving both IPv4 and IPv6 datagrams.
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::{UdpSocket, UdpSocketOpts};
+    ///
+    /// let opts = UdpSocketOpts {
+    ///     only_v6: Some(false),
+    ///     ..UdpSocketOpts::default()
+    /// };
+    /// let socket = UdpSocket::bind_with("[::]:0".parse()?, opts)?;
+    /// # drop(socket);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn bind_with(addr: SocketAddr, opts: UdpSocketOpts) -> io::Result<UdpSocket> {
+        sys::UdpSocket::bind(addr, &opts).map(|sys| UdpSocket {
             sys,
             #[cfg(debug_assertions)]
             selector_id: SelectorId::new(),
@@ -130,16 +326,16 @@ impl UdpSocket {
     /// Creates a new `UdpSocket` from a standard `net::UdpSocket`.
     ///
     /// This function is intended to be used to wrap a UDP socket from the
-    /// standard library in the Mio equivalent. The conversion assumes nothing
-    /// about the underlying socket; it is left up to the user to set it in
-    /// non-blocking mode.
-    pub fn from_std(socket: net::UdpSocket) -> UdpSocket {
-        let sys = sys::UdpSocket::from_std(socket);
-        UdpSocket {
-            sys,
+    /// standard library in the Mio equivalent, e.g. a socket created with
+    /// options Mio doesn't support. The socket is put in non-blocking mode,
+    /// returning an error if that fails.
+    pub fn from_std(socket: net::UdpSocket) -> io::Result<UdpSocket> {
+        socket.set_nonblocking(true)?;
+        Ok(UdpSocket {
+            sys: sys::UdpSocket::from_std(socket),
             #[cfg(debug_assertions)]
             selector_id: SelectorId::new(),
-        }
+        })
     }
 
     /// Returns the socket address that this socket was created from.
@@ -252,10 +448,268 @@ impl UdpSocket {
         self.sys.recv_from(buf)
     }
 
+    /// Sends data on the socket to the given address, like [`send_to`], but
+    /// gathering the data from multiple buffers. On success, returns the
+    /// number of bytes written.
+    ///
+    /// All buffers are sent as a single datagram.
+    ///
+    /// [`send_to`]: UdpSocket::send_to
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UdpSocket;
+    /// use std::io::IoSlice;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// // We must check if the socket is writable before calling
+    /// // send_to_vectored, or we could run into a WouldBlock error.
+    ///
+    /// let bufs = [IoSlice::new(b"header"), IoSlice::new(b"body")];
+    /// let bytes_sent = socket.send_to_vectored(&bufs, "127.0.0.1:11100".parse()?)?;
+    /// assert_eq!(bytes_sent, 10);
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn send_to_vectored(&self, bufs: &[IoSlice<'_>], target: SocketAddr) -> io::Result<usize> {
+        self.sys.send_to_vectored(bufs, target)
+    }
+
+    /// Receives data from the socket, like [`recv_from`], but scattering the
+    /// data into multiple buffers. On success, returns the number of bytes
+    /// read and the address from whence the data came.
+    ///
+    /// A single call receives at most a single datagram, the buffers are
+    /// filled in order.
+    ///
+    /// [`recv_from`]: UdpSocket::recv_from
+    pub fn recv_from_vectored(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, SocketAddr)> {
+        self.sys.recv_from_vectored(bufs)
+    }
+
+    /// Enables or disables receiving the packet information used by
+    /// [`recv_from_with_info`].
+    ///
+    /// This sets the `IP_PKTINFO` option for IPv4 sockets, and both the
+    /// `IPV6_RECVPKTINFO` and `IP_PKTINFO` options for IPv6 sockets (the
+    /// latter for IPv4 datagrams received on a dual-stack socket). The socket
+    /// must be bound.
+    ///
+    /// Only available on Android and Linux.
+    ///
+    /// [`recv_from_with_info`]: UdpSocket::recv_from_with_info
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_recv_pktinfo(&self, on: bool) -> io::Result<()> {
+        self.sys.set_recv_pktinfo(on)
+    }
+
+    /// Receives data from the socket, like [`recv_from`], also returning the
+    /// [`PktInfo`] of the datagram.
+    ///
+    /// The packet information contains the local address the datagram was
+    /// sent to, which allows a socket bound to an unspecified address, e.g.
+    /// `0.0.0.0`, to reply from the address the sender expects on a host with
+    /// multiple addresses.
+    ///
+    /// Receiving the packet information must be enabled using
+    /// [`set_recv_pktinfo`], otherwise this returns an error, discarding the
+    /// datagram.
+    ///
+    /// Only available on Android and Linux.
+    ///
+    /// [`recv_from`]: UdpSocket::recv_from
+    /// [`set_recv_pktinfo`]: UdpSocket::set_recv_pktinfo
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("0.0.0.0:0".parse()?)?;
+    /// socket.set_recv_pktinfo(true)?;
+    ///
+    /// let sender = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    /// let target = format!("127.0.0.1:{}", socket.local_addr()?.port()).parse()?;
+    /// sender.send_to(b"hello", target)?;
+    /// # std::thread::sleep(std::time::Duration::from_millis(10));
+    ///
+    /// let mut buf = [0; 16];
+    /// let (n, from, info) = socket.recv_from_with_info(&mut buf)?;
+    /// assert_eq!(&buf[..n], b"hello");
+    /// assert_eq!(from, sender.local_addr()?);
+    /// assert_eq!(info.destination(), target.ip());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_from_with_info(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr, PktInfo)> {
+        self.sys.recv_from_with_info(buf)
+    }
+
+    /// Receives data from the socket, like [`recv_from`], using `flags`.
+    ///
+    /// On success returns the number of bytes read into `buf`, the address
+    /// the datagram was sent from and a [`RecvResult`]. The result reports
+    /// whether the datagram was truncated because `buf` is too small, and if
+    /// [`RecvFlags::TRUNC`] is used the full length of the datagram. This
+    /// allows the caller to grow its buffer and, using [`RecvFlags::PEEK`],
+    /// receive the datagram again.
+    ///
+    /// Only available on Android and Linux.
+    ///
+    /// [`recv_from`]: UdpSocket::recv_from
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::{RecvFlags, UdpSocket};
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    /// let sender = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    /// sender.send_to(&[1; 100], socket.local_addr()?)?;
+    /// # std::thread::sleep(std::time::Duration::from_millis(10));
+    ///
+    /// let mut buf = vec![0; 10];
+    /// let flags = RecvFlags::PEEK | RecvFlags::TRUNC;
+    /// let (_, _, result) = socket.recv_from_with_flags(&mut buf, flags)?;
+    /// if let Some(len) = result.datagram_len() {
+    ///     // Grow the buffer to fit the entire datagram.
+    ///     buf.resize(len, 0);
+    /// }
+    ///
+    /// let (n, from, result) = socket.recv_from_with_flags(&mut buf, RecvFlags::default())?;
+    /// assert_eq!(n, 100);
+    /// assert_eq!(from, sender.local_addr()?);
+    /// assert!(!result.is_truncated());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_from_with_flags(
+        &self,
+        buf: &mut [u8],
+        flags: RecvFlags,
+    ) -> io::Result<(usize, SocketAddr, RecvResult)> {
+        self.sys.recv_from_with_flags(buf, flags)
+    }
+
+    /// Receives multiple datagrams from the socket in a single call. On
+    /// success, returns the number of messages received.
+    ///
+    /// The datagrams are received into the messages in order, after which
+    /// [`RecvMsg::data`] and [`RecvMsg::addr`] return the received data and
+    /// the address it came from. If fewer datagrams are available than there
+    /// are messages, the remaining messages are left untouched. If no
+    /// datagram is available at all this returns a `WouldBlock` error.
+    ///
+    /// On Android and Linux this uses `recvmmsg`, on other platforms this
+    /// calls [`recv_from`] once per message.
+    ///
+    /// [`recv_from`]: UdpSocket::recv_from
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::{RecvMsg, UdpSocket};
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// // We must check if the socket is readable before calling recv_mmsg,
+    /// // or we could run into a WouldBlock error.
+    ///
+    /// let mut bufs = [[0; 1500]; 4];
+    /// let mut msgs: Vec<RecvMsg> = bufs.iter_mut().map(|buf| RecvMsg::new(buf)).collect();
+    /// let n = socket.recv_mmsg(&mut msgs)?;
+    /// for msg in &msgs[..n] {
+    ///     println!("received {:?} from {:?}", msg.data(), msg.addr());
+    /// }
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn recv_mmsg(&self, msgs: &mut [RecvMsg<'_>]) -> io::Result<usize> {
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        {
+            self.sys.recv_mmsg(msgs)
+        }
+
+        #[cfg(not(any(target_os = "android", target_os = "linux")))]
+        {
+            let mut received = 0;
+            for msg in msgs.iter_mut() {
+                match self.sys.recv_from(msg.buf) {
+                    Ok((n, addr)) => {
+                        msg.len = n;
+                        msg.addr = Some(addr);
+                        received += 1;
+                    }
+                    // Report the messages already received, the error (if
+                    // persistent) is returned by the next call.
+                    Err(_) if received > 0 => break,
+                    Err(err) => return Err(err),
+                }
+            }
+            Ok(received)
+        }
+    }
+
+    /// Sends multiple datagrams on the socket in a single call. On success,
+    /// returns the number of messages sent.
+    ///
+    /// Each message is sent as a single datagram to its address, in order.
+    /// If not all messages could be sent, e.g. because the send buffer is
+    /// full, the number of messages sent is returned and the remaining
+    /// messages must be sent again later. If no message could be sent at all
+    /// this returns an error, e.g. `WouldBlock`.
+    ///
+    /// On Android and Linux this uses `sendmmsg`, on other platforms this
+    /// calls [`send_to`] once per message.
+    ///
+    /// [`send_to`]: UdpSocket::send_to
+    pub fn send_mmsg(&self, msgs: &[SendMsg<'_>]) -> io::Result<usize> {
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        {
+            self.sys.send_mmsg(msgs)
+        }
+
+        #[cfg(not(any(target_os = "android", target_os = "linux")))]
+        {
+            let mut sent = 0;
+            for msg in msgs {
+                match self.sys.send_to(msg.buf, msg.addr) {
+                    Ok(_) => sent += 1,
+                    // See `recv_mmsg`.
+                    Err(_) if sent > 0 => break,
+                    Err(err) => return Err(err),
+                }
+            }
+            Ok(sent)
+        }
+    }
+
     /// Receives data from the socket, without removing it from the input queue.
     /// On success, returns the number of bytes read and the address from whence
     /// the data came.
     ///
+    /// This uses `recvfrom` with the `MSG_PEEK` flag, the datagram stays in the
+    /// queue so the next call to `peek_from` or [`recv_from`] returns the same
+    /// datagram. This can be used to inspect the start of a datagram, e.g. to
+    /// decide how to handle it, before actually receiving it.
+    ///
+    /// [`recv_from`]: UdpSocket::recv_from
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -280,18 +734,66 @@ impl UdpSocket {
         self.sys.peek_from(buf)
     }
 
-    /// Sends data on the socket to the address previously bound via connect(). On success,
-    /// returns the number of bytes written.
+    /// Sends data on the socket to the address previously bound via
+    /// [`connect`]. On success, returns the number of bytes written.
+    ///
+    /// If the socket is not ready for writing this returns an error with kind
+    /// [`WouldBlock`].
+    ///
+    /// [`connect`]: UdpSocket::connect
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
     pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
         self.sys.send(buf)
     }
 
-    /// Receives data from the socket previously bound with connect(). On success, returns
-    /// the number of bytes read.
+    /// Receives data from the socket previously bound with [`connect`]. On
+    /// success, returns the number of bytes read.
+    ///
+    /// If no datagram is available this returns an error with kind
+    /// [`WouldBlock`].
+    ///
+    /// [`connect`]: UdpSocket::connect
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
     pub fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.sys.recv(buf)
     }
 
+    /// Like [`send_to`], but returns `Ok(None)` instead of an error with kind
+    /// [`WouldBlock`] if the socket is not ready for writing.
+    ///
+    /// [`send_to`]: UdpSocket::send_to
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+    pub fn try_send_to(&self, buf: &[u8], target: SocketAddr) -> io::Result<Option<usize>> {
+        try_io(self.send_to(buf, target))
+    }
+
+    /// Like [`recv_from`], but returns `Ok(None)` instead of an error with
+    /// kind [`WouldBlock`] if no datagram is available.
+    ///
+    /// [`recv_from`]: UdpSocket::recv_from
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+    pub fn try_recv_from(&self, buf: &mut [u8]) -> io::Result<Option<(usize, SocketAddr)>> {
+        try_io(self.recv_from(buf))
+    }
+
+    /// Like [`send`], but returns `Ok(None)` instead of an error with kind
+    /// [`WouldBlock`] if the socket is not ready for writing.
+    ///
+    /// [`send`]: UdpSocket::send
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+    pub fn try_send(&self, buf: &[u8]) -> io::Result<Option<usize>> {
+        try_io(self.send(buf))
+    }
+
+    /// Like [`recv`], but returns `Ok(None)` instead of an error with kind
+    /// [`WouldBlock`] if no datagram is available.
+    ///
+    /// [`recv`]: UdpSocket::recv
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+    pub fn try_recv(&self, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        try_io(self.recv(buf))
+    }
+
     /// Receives data from the socket, without removing it from the input queue.
     /// On success, returns the number of bytes read.
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
@@ -301,6 +803,32 @@ impl UdpSocket {
     /// Connects the UDP socket setting the default destination for `send()`
     /// and limiting packets that are read via `recv` from the address specified
     /// in `addr`.
+    ///
+    /// Connecting a UDP socket doesn't involve the remote, so unlike
+    /// [`TcpStream::connect`] this returns immediately.
+    ///
+    /// [`TcpStream::connect`]: crate::net::TcpStream::connect
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UdpSocket;
+    ///
+    /// let socket1 = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    /// let socket2 = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// socket1.connect(socket2.local_addr()?)?;
+    /// socket2.connect(socket1.local_addr()?)?;
+    ///
+    /// // Both sockets can now use `send` and `recv`, rather than `send_to`
+    /// // and `recv_from`.
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
     pub fn connect(&self, addr: SocketAddr) -> io::Result<()> {
         self.sys.connect(addr)
     }
@@ -310,6 +838,9 @@ impl UdpSocket {
     /// When enabled, this socket is allowed to send packets to a broadcast
     /// address.
     ///
+    /// This can be changed while the socket is registered, it doesn't need to
+    /// be reregistered afterwards.
+    ///
     /// # Examples
     ///
     /// ```
@@ -414,10 +945,52 @@ impl UdpSocket {
         self.sys.multicast_loop_v6()
     }
 
-    /// Sets the value for the `IP_TTL` option on this socket.
+    /// Sets the value of the `IP_MULTICAST_IF` option for this socket.
+    ///
+    /// This sets the interface used to send IPv4 multicast packets, using
+    /// the address of the interface. If it's equal to `INADDR_ANY` the OS
+    /// chooses an appropriate interface.
+    pub fn set_multicast_if_v4(&self, interface: Ipv4Addr) -> io::Result<()> {
+        self.sys.set_multicast_if_v4(interface)
+    }
+
+    /// Gets the value of the `IP_MULTICAST_IF` option for this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_multicast_if_v4`][link].
+    ///
+    /// [link]: #method.set_multicast_if_v4
+    pub fn multicast_if_v4(&self) -> io::Result<Ipv4Addr> {
+        self.sys.multicast_if_v4()
+    }
+
+    /// Sets the value of the `IPV6_MULTICAST_IF` option for this socket.
+    ///
+    /// This sets the interface used to send IPv6 multicast packets, using
+    /// the index of the interface. If it's `0` the OS chooses an appropriate
+    /// interface.
+    pub fn set_multicast_if_v6(&self, index: u32) -> io::Result<()> {
+        self.sys.set_multicast_if_v6(index)
+    }
+
+    /// Gets the value of the `IPV6_MULTICAST_IF` option for this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_multicast_if_v6`][link].
+    ///
+    /// [link]: #method.set_multicast_if_v6
+    pub fn multicast_if_v6(&self) -> io::Result<u32> {
+        self.sys.multicast_if_v6()
+    }
+
+    /// Sets the value for the `IP_TTL` option on this socket, or the
+    /// `IPV6_UNICAST_HOPS` option for IPv6 sockets.
     ///
     /// This value sets the time-to-live field that is used in every packet sent
-    /// from this socket.
+    /// from this socket. Values larger than 255 return an error with kind
+    /// [`InvalidInput`].
+    ///
+    /// [`InvalidInput`]: std::io::ErrorKind::InvalidInput
     ///
     /// # Examples
     ///
@@ -441,7 +1014,8 @@ impl UdpSocket {
         self.sys.set_ttl(ttl)
     }
 
-    /// Gets the value of the `IP_TTL` option for this socket.
+    /// Gets the value of the `IP_TTL` option for this socket, or the
+    /// `IPV6_UNICAST_HOPS` option for IPv6 sockets.
     ///
     /// For more information about this option, see [`set_ttl`][link].
     ///
@@ -467,6 +1041,127 @@ impl UdpSocket {
         self.sys.ttl()
     }
 
+    /// Sets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// This option only has an effect when it's set before the socket is
+    /// bound, which is not possible for sockets created using
+    /// [`UdpSocket::bind`]. Use [`UdpSocket::bind_with`] with
+    /// [`UdpSocketOpts::reuse_addr`] to create a socket with this option set.
+    ///
+    /// [`UdpSocketOpts::reuse_addr`]: crate::net::UdpSocketOpts::reuse_addr
+    pub fn set_reuse_address(&self, on: bool) -> io::Result<()> {
+        self.sys.set_reuse_address(on)
+    }
+
+    /// Gets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// This can be used to inspect the option on a socket created using
+    /// [`UdpSocket::from_std`]. For more information about this option, see
+    /// [`set_reuse_address`][link].
+    ///
+    /// [link]: #method.set_reuse_address
+    pub fn reuse_address(&self) -> io::Result<bool> {
+        self.sys.reuse_address()
+    }
+
+    /// Sets the value for the `IP_TOS` option on this socket, or the
+    /// `IPV6_TCLASS` option for IPv6 sockets.
+    ///
+    /// This value sets the type of service (traffic class for IPv6) field that
+    /// is used in every packet sent from this socket, e.g. to mark packets
+    /// with a DSCP value. The DSCP value occupies the upper six bits, so it
+    /// must be shifted left by two. Values larger than 255 return an error
+    /// with kind [`InvalidInput`].
+    ///
+    /// [`InvalidInput`]: std::io::ErrorKind::InvalidInput
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    /// // Expedited Forwarding (DSCP 46).
+    /// socket.set_tos(46 << 2)?;
+    ///
+    /// assert_eq!(socket.tos()?, 46 << 2);
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn set_tos(&self, tos: u32) -> io::Result<()> {
+        self.sys.set_tos(tos)
+    }
+
+    /// Gets the value of the `IP_TOS` option for this socket, or the
+    /// `IPV6_TCLASS` option for IPv6 sockets.
+    ///
+    /// For more information about this option, see [`set_tos`][link].
+    ///
+    /// [link]: #method.set_tos
+    pub fn tos(&self) -> io::Result<u32> {
+        self.sys.tos()
+    }
+
+    /// Sets the value of the `SO_MARK` option on this socket.
+    ///
+    /// The mark is set on every packet sent by the socket, which can be used
+    /// by netfilter (e.g. `iptables`) to classify the packets and for policy
+    /// routing.
+    ///
+    /// Setting the mark requires the `CAP_NET_ADMIN` capability, without it
+    /// this returns an error of kind `PermissionDenied`.
+    ///
+    /// Only available on Android and Linux.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_mark(&self, mark: u32) -> io::Result<()> {
+        self.sys.set_mark(mark)
+    }
+
+    /// Gets the value of the `SO_MARK` option on this socket.
+    ///
+    /// For more information about this option, see [`set_mark`].
+    ///
+    /// Only available on Android and Linux.
+    ///
+    /// [`set_mark`]: UdpSocket::set_mark
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn mark(&self) -> io::Result<u32> {
+        self.sys.mark()
+    }
+
+    /// Binds this socket to the network device `interface`, e.g. `"eth1"`,
+    /// using the `SO_BINDTODEVICE` option.
+    ///
+    /// Once bound, only packets received on that device are processed by the
+    /// socket, and packets sent are routed through it. Passing `None` removes
+    /// the binding.
+    ///
+    /// Binding to a device usually requires the `CAP_NET_RAW` capability,
+    /// without it this returns an error of kind `PermissionDenied`.
+    ///
+    /// Only available on Android and Linux.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn bind_device(&self, interface: Option<&str>) -> io::Result<()> {
+        self.sys.bind_device(interface.map(str::as_bytes))
+    }
+
+    /// Returns the name of the network device this socket is bound to, or
+    /// `None` if it isn't bound to a device.
+    ///
+    /// For more information about this option, see [`bind_device`].
+    ///
+    /// Only available on Android and Linux.
+    ///
+    /// [`bind_device`]: UdpSocket::bind_device
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn device(&self) -> io::Result<Option<Vec<u8>>> {
+        self.sys.device()
+    }
+
     /// Executes an operation of the `IP_ADD_MEMBERSHIP` type.
     ///
     /// This function specifies a new multicast group for this socket to join.
@@ -474,6 +1169,12 @@ impl UdpSocket {
     /// address of the local interface with which the system should join the
     /// multicast group. If it's equal to `INADDR_ANY` then an appropriate
     /// interface is chosen by the system.
+    ///
+    /// This may be called while the socket is registered with a [`Registry`],
+    /// datagrams sent to the group will trigger readable events without the
+    /// socket needing to be reregistered.
+    ///
+    /// [`Registry`]: crate::Registry
     pub fn join_multicast_v4(&self, multiaddr: Ipv4Addr, interface: Ipv4Addr) -> io::Result<()> {
         self.sys.join_multicast_v4(multiaddr, interface)
     }
@@ -507,6 +1208,66 @@ impl UdpSocket {
         self.sys.leave_multicast_v6(multiaddr, interface)
     }
 
+    /// Sets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// Changes the size of the operating system's receive buffer associated
+    /// with the socket. Sizes larger than `i32::MAX` are truncated.
+    ///
+    /// The OS may adjust the size, e.g. Linux doubles the value to allow
+    /// space for bookkeeping overhead and limits it to `net.core.rmem_max`.
+    /// Use [`recv_buffer_size`] to retrieve the actual size.
+    ///
+    /// [`recv_buffer_size`]: UdpSocket::recv_buffer_size
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// socket.set_recv_buffer_size(256 * 1024)?;
+    /// println!("receive buffer size: {}", socket.recv_buffer_size()?);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.sys.set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// This returns the size as reported by the OS, which may differ from
+    /// the size set using [`set_recv_buffer_size`].
+    ///
+    /// [`set_recv_buffer_size`]: UdpSocket::set_recv_buffer_size
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.sys.recv_buffer_size()
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// Changes the size of the operating system's send buffer associated
+    /// with the socket. Like the receive buffer the OS may adjust the size,
+    /// see [`set_recv_buffer_size`].
+    ///
+    /// [`set_recv_buffer_size`]: UdpSocket::set_recv_buffer_size
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.sys.set_send_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// This returns the size as reported by the OS, which may differ from
+    /// the size set using [`set_send_buffer_size`].
+    ///
+    /// [`set_send_buffer_size`]: UdpSocket::set_send_buffer_size
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.sys.send_buffer_size()
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
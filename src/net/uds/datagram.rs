@@ -9,7 +9,10 @@ use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use std::os::unix::net;
 use std::path::Path;
 
-/// A Unix datagram socket.
+/// A non-blocking Unix datagram socket.
+///
+/// Message boundaries are preserved and no connection needs to be established
+/// before sending, see [`std::os::unix::net::UnixDatagram`].
 #[derive(Debug)]
 pub struct UnixDatagram {
     sys: sys::UnixDatagram,
@@ -36,8 +39,8 @@ impl UnixDatagram {
     ///
     /// This function is intended to be used to wrap a Unix datagram from the
     /// standard library in the Mio equivalent. The conversion assumes nothing
-    /// about the underlying datagram; ; it is left up to the user to set it
-    /// in non-blocking mode.
+    /// about the underlying datagram; it is left up to the user to set it in
+    /// non-blocking mode.
     pub fn from_std(datagram: net::UnixDatagram) -> UnixDatagram {
         let sys = sys::UnixDatagram::from_std(datagram);
         UnixDatagram {
@@ -68,9 +71,9 @@ impl UnixDatagram {
 
     /// Creates a new independently owned handle to the underlying socket.
     ///
-    /// The returned `UnixListener` is a reference to the same socket that this
-    /// object references. Both handles can be used to accept incoming
-    /// connections and options set on one listener will affect the other.
+    /// The returned `UnixDatagram` is a reference to the same socket that this
+    /// object references. Both handles can be used to send and receive
+    /// datagrams and options set on one socket will affect the other.
     pub fn try_clone(&self) -> io::Result<UnixDatagram> {
         let sys = self.sys.try_clone()?;
         Ok(UnixDatagram::new(sys))
@@ -9,11 +9,14 @@ use std::io;
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use std::os::unix::net;
 use std::path::Path;
+use std::sync::Mutex;
 
 /// A non-blocking Unix domain socket server.
 #[derive(Debug)]
 pub struct UnixListener {
     sys: sys::UnixListener,
+    /// Local address cached by `accept_with_local`.
+    local_addr: Mutex<Option<SocketAddr>>,
     #[cfg(debug_assertions)]
     selector_id: SelectorId,
 }
@@ -22,6 +25,7 @@ impl UnixListener {
     fn new(sys: sys::UnixListener) -> UnixListener {
         UnixListener {
             sys,
+            local_addr: Mutex::new(None),
             #[cfg(debug_assertions)]
             selector_id: SelectorId::new(),
         }
@@ -33,30 +37,89 @@ impl UnixListener {
         Ok(UnixListener::new(sys))
     }
 
+    /// Creates a new `UnixListener` bound to `address`.
+    ///
+    /// Unlike [`bind`] this can bind to addresses in the abstract namespace,
+    /// see [`SocketAddr::from_abstract_name`].
+    ///
+    /// [`bind`]: UnixListener::bind
+    /// [`SocketAddr::from_abstract_name`]: crate::unix::SocketAddr::from_abstract_name
+    pub fn bind_addr(address: &SocketAddr) -> io::Result<UnixListener> {
+        let sys = sys::UnixListener::bind_addr(address)?;
+        Ok(UnixListener::new(sys))
+    }
+
     /// Creates a new `UnixListener` from a standard `net::UnixListener`.
     ///
     /// This function is intended to be used to wrap a Unix listener from the
-    /// standard library in the Mio equivalent. The conversion assumes nothing
-    /// about the underlying listener; it is left up to the user to set it in
-    /// non-blocking mode.
-    pub fn from_std(listener: net::UnixListener) -> UnixListener {
+    /// standard library in the Mio equivalent. The listener is put in
+    /// non-blocking mode, returning an error if that fails.
+    ///
+    /// This can be used to wrap a listener that is already bound, e.g. one
+    /// passed to the process by systemd's socket activation.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UnixListener;
+    /// use std::os::unix::io::FromRawFd;
+    /// use std::os::unix::net;
+    ///
+    /// // The first socket passed by systemd is file descriptor 3.
+    /// let listener = unsafe { net::UnixListener::from_raw_fd(3) };
+    /// let listener = UnixListener::from_std(listener)?;
+    /// # drop(listener);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_std(listener: net::UnixListener) -> io::Result<UnixListener> {
+        listener.set_nonblocking(true)?;
         let sys = sys::UnixListener::from_std(listener);
-        UnixListener {
-            sys,
-            #[cfg(debug_assertions)]
-            selector_id: SelectorId::new(),
-        }
+        Ok(UnixListener::new(sys))
     }
 
     /// Accepts a new incoming connection to this listener.
     ///
     /// The call is responsible for ensuring that the listening socket is in
     /// non-blocking mode.
+    ///
+    /// The returned `UnixStream` is always in non-blocking mode and has the
+    /// close-on-exec flag set. Where available both are set atomically using
+    /// `accept4(2)`, on other platforms (e.g. macOS) they are set using
+    /// `fcntl(2)` right after the stream is accepted.
     pub fn accept(&self) -> io::Result<(UnixStream, SocketAddr)> {
         let (sys, sockaddr) = self.sys.accept()?;
         Ok((UnixStream::new(sys), sockaddr))
     }
 
+    /// Accepts a new incoming connection to this listener, returning the
+    /// local address of the listener along with the peer's address.
+    ///
+    /// This works the same as [`accept`], but also returns the address the
+    /// listener is bound to, which can be used to tell apart connections
+    /// accepted from multiple listeners. The local address is cached after
+    /// the first call, so this doesn't require an additional system call per
+    /// accepted connection.
+    ///
+    /// [`accept`]: UnixListener::accept
+    pub fn accept_with_local(&self) -> io::Result<(UnixStream, SocketAddr, SocketAddr)> {
+        let local = {
+            let mut cached = self.local_addr.lock().unwrap();
+            match *cached {
+                Some(ref addr) => addr.clone(),
+                None => {
+                    let addr = self.sys.local_addr()?;
+                    *cached = Some(addr.clone());
+                    addr
+                }
+            }
+        };
+        let (stream, peer) = self.accept()?;
+        Ok((stream, peer, local))
+    }
+
     /// Creates a new independently owned handle to the underlying socket.
     ///
     /// The returned `UnixListener` is a reference to the same socket that this
@@ -1,9 +1,11 @@
 use crate::event::Source;
+use crate::net::try_io;
 #[cfg(debug_assertions)]
 use crate::poll::SelectorId;
+use crate::unix::{SocketAddr, UCred};
 use crate::{sys, Interests, Registry, Token};
 
-use std::io::{self, IoSlice, IoSliceMut};
+use std::io::{self, IoSlice, IoSliceMut, Read, Write};
 use std::net::Shutdown;
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use std::os::unix::net;
@@ -32,6 +34,18 @@ impl UnixStream {
         Ok(UnixStream::new(sys))
     }
 
+    /// Connects to the socket at `address`.
+    ///
+    /// Unlike [`connect`] this can connect to addresses in the abstract
+    /// namespace, see [`SocketAddr::from_abstract_name`].
+    ///
+    /// [`connect`]: UnixStream::connect
+    /// [`SocketAddr::from_abstract_name`]: crate::unix::SocketAddr::from_abstract_name
+    pub fn connect_addr(address: &SocketAddr) -> io::Result<UnixStream> {
+        let sys = sys::UnixStream::connect_addr(address)?;
+        Ok(UnixStream::new(sys))
+    }
+
     /// Creates a new `UnixStream` from a standard `net::UnixStream`.
     ///
     /// This function is intended to be used to wrap a Unix stream from the
@@ -44,6 +58,10 @@ impl UnixStream {
     /// The Unix stream here will not have `connect` called on it, so it
     /// should already be connected via some other means (be it manually, or
     /// the standard library).
+    ///
+    /// On macOS and iOS writing to the returned stream after the connection
+    /// is closed raises `SIGPIPE`, unless `SO_NOSIGPIPE` is set on the stream
+    /// beforehand.
     pub fn from_std(stream: net::UnixStream) -> UnixStream {
         let sys = sys::UnixStream::from_std(stream);
         UnixStream {
@@ -63,6 +81,48 @@ impl UnixStream {
         Ok((a, b))
     }
 
+    /// Creates an unnamed pair of connected sockets of type
+    /// `SOCK_SEQPACKET`.
+    ///
+    /// Like [`pair`] this returns two connected `UnixStream`s, but the
+    /// sockets preserve message boundaries: every write is received by a
+    /// single read on the other side. If the buffer passed to read is too
+    /// small for a message the remainder of the message is discarded.
+    ///
+    /// Not all platforms support `SOCK_SEQPACKET` for Unix sockets, e.g.
+    /// macOS. On those platforms the error returned by the OS is returned
+    /// (usually `EPROTONOSUPPORT`).
+    ///
+    /// [`pair`]: UnixStream::pair
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UnixStream;
+    /// use std::io::{Read, Write};
+    ///
+    /// # if cfg!(any(target_os = "ios", target_os = "macos")) { return Ok(()); }
+    /// let (mut a, mut b) = UnixStream::seqpacket_pair()?;
+    ///
+    /// a.write(b"Hello")?;
+    /// a.write(b"world")?;
+    ///
+    /// // Both messages are read separately.
+    /// let mut buf = [0; 32];
+    /// assert_eq!(b.read(&mut buf)?, 5);
+    /// assert_eq!(b.read(&mut buf)?, 5);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn seqpacket_pair() -> io::Result<(UnixStream, UnixStream)> {
+        let (a, b) = sys::UnixStream::seqpacket_pair()?;
+        let a = UnixStream::new(a);
+        let b = UnixStream::new(b);
+        Ok((a, b))
+    }
+
     /// Creates a new independently owned handle to the underlying socket.
     ///
     /// The returned `UnixStream` is a reference to the same stream that this
@@ -84,6 +144,30 @@ impl UnixStream {
         self.sys.peer_addr()
     }
 
+    /// Returns the credentials of the process on the other side of this
+    /// connection.
+    ///
+    /// This uses `SO_PEERCRED` on Android and Linux, which also returns the
+    /// process id of the peer, and `getpeereid` on the BSDs and macOS. Not
+    /// supported on Solaris, where this returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UnixStream;
+    ///
+    /// let (stream, _) = UnixStream::pair()?;
+    /// let cred = stream.peer_cred()?;
+    /// println!("peer uid: {}, gid: {}", cred.uid, cred.gid);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn peer_cred(&self) -> io::Result<UCred> {
+        self.sys.peer_cred()
+    }
+
     /// Returns the value of the `SO_ERROR` option.
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.sys.take_error()
@@ -97,6 +181,37 @@ impl UnixStream {
     pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
         self.sys.shutdown(how)
     }
+
+    /// Receives data on the socket from the remote address to which it is
+    /// connected, without removing that data from the queue. On success,
+    /// returns the number of bytes peeked.
+    ///
+    /// Successive calls return the same data. This is accomplished by passing
+    /// `MSG_PEEK` as a flag to the underlying recv system call.
+    pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.sys.peek(buf)
+    }
+
+    /// Reads data from the socket, returning `Ok(None)` instead of an error
+    /// with kind [`WouldBlock`] if no data is available.
+    ///
+    /// All other errors are returned as is. Note that `Ok(Some(0))` means the
+    /// peer closed the writing side of the connection.
+    ///
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+    pub fn try_read(&self, buf: &mut [u8]) -> io::Result<Option<usize>> {
+        try_io((&self.sys).read(buf))
+    }
+
+    /// Writes data to the socket, returning `Ok(None)` instead of an error
+    /// with kind [`WouldBlock`] if the socket's send buffer is full.
+    ///
+    /// All other errors are returned as is.
+    ///
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+    pub fn try_write(&self, buf: &[u8]) -> io::Result<Option<usize>> {
+        try_io((&self.sys).write(buf))
+    }
 }
 
 impl Source for UnixStream {
@@ -1,11 +1,17 @@
-use crate::{event, sys, Events, Interests, Token};
+use crate::{event, sys, Events, Interests, Token, Waker};
 
-use log::trace;
+use log::{error, trace};
+#[cfg(feature = "timers")]
+use std::cmp::{self, Reverse};
+#[cfg(feature = "timers")]
+use std::collections::BinaryHeap;
 #[cfg(unix)]
 use std::os::unix::io::{AsRawFd, RawFd};
-#[cfg(debug_assertions)]
+#[cfg(windows)]
+use std::os::windows::io::{AsRawHandle, RawHandle};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::time::Duration;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
 use std::{fmt, io};
 
 /// Polls for readiness events on all registered values.
@@ -197,13 +203,62 @@ use std::{fmt, io};
 /// [`Poll::poll`]: struct.Poll.html#method.poll
 pub struct Poll {
     registry: Registry,
+    #[cfg(feature = "poll-stats")]
+    stats: PollStats,
+}
+
+/// Statistics about the calls to [`Poll::poll`], see [`Poll::stats`].
+///
+/// Only available when the `poll-stats` feature is enabled.
+#[cfg(feature = "poll-stats")]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct PollStats {
+    /// Number of successful calls to poll.
+    pub polls: u64,
+    /// Total number of events returned by all polls.
+    pub events_returned: u64,
+    /// Number of polls after which the `Events` was filled to capacity.
+    ///
+    /// If this happens often it could be beneficial to increase the capacity
+    /// of `Events`, as more events might be ready to be returned by the next
+    /// poll.
+    pub buffer_full_count: u64,
 }
 
 /// Registers I/O resources.
 pub struct Registry {
-    selector: sys::Selector,
+    /// Shared with `Registration`s, see `Registry::share`. Registries created
+    /// using `try_clone` use their own selector handle instead.
+    selector: Arc<sys::Selector>,
+    /// Number of registered sources, shared between all cloned registries.
+    sources: Arc<AtomicUsize>,
+    /// Timeouts set using `set_timeout`, shared between all cloned registries.
+    #[cfg(feature = "timers")]
+    timers: Arc<Mutex<Timers>>,
+    /// The `Waker` and its token, drained after polling. Shared between all
+    /// cloned registries.
+    waker: Arc<Mutex<Option<(Token, Weak<sys::Waker>)>>>,
+    /// Trigger mode used by `SourceFd` to (re)register file descriptors.
+    /// Always `Trigger::Edge`, except for the registry passed to the source by
+    /// `register_with_mode` and `reregister_with_mode`.
+    trigger: Trigger,
+}
+
+/// Registration of an [`event::Source`] which deregisters the source when
+/// dropped, see [`Registry::register_scoped`].
+///
+/// Errors deregistering the source when dropped are logged and otherwise
+/// ignored.
+pub struct Registration<'s, S: event::Source + ?Sized> {
+    registry: Registry,
+    source: &'s S,
 }
 
+/// Deadlines of the timeouts set using `Registry::set_timeout`, the earliest
+/// deadline first.
+#[cfg(feature = "timers")]
+type Timers = BinaryHeap<Reverse<(Instant, Token)>>;
+
 /// Used to associate an IO type with a Selector
 #[derive(Debug)]
 #[cfg(debug_assertions)]
@@ -252,17 +307,202 @@ impl Poll {
     /// # }
     /// ```
     pub fn new() -> io::Result<Poll> {
-        sys::Selector::new().map(|selector| Poll {
-            registry: Registry { selector },
+        Registry::new().map(|registry| Poll {
+            registry,
+            #[cfg(feature = "poll-stats")]
+            stats: PollStats::default(),
         })
     }
 
     /// Create a separate `Registry` which can be used to register
     /// `event::Source`s.
+    ///
+    /// The returned reference borrows from `Poll`, which conflicts with the
+    /// mutable borrow needed by [`poll`]. If event sources need to be
+    /// registered while handling the events returned by `poll`, e.g. when
+    /// accepting new connections, create an owned `Registry` once using
+    /// [`Registry::try_clone`] and store it alongside `Poll`.
+    ///
+    /// [`poll`]: Poll::poll
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::TcpListener;
+    /// use mio::{Events, Interests, Poll, Token};
+    /// use std::time::Duration;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// // Owned `Registry`, not borrowing from `poll`.
+    /// let registry = poll.registry().try_clone()?;
+    /// let mut events = Events::with_capacity(128);
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:0".parse()?)?;
+    /// registry.register(&listener, Token(0), Interests::READABLE)?;
+    /// # let _stream = std::net::TcpStream::connect(listener.local_addr()?)?;
+    ///
+    /// let mut connections = Vec::new();
+    /// # for _ in 0..1 {
+    /// poll.poll(&mut events, Some(Duration::from_millis(100)))?;
+    /// for event in &events {
+    ///     if event.token() == Token(0) {
+    ///         let (connection, _) = listener.accept()?;
+    ///         // Registering while `events` is borrowed.
+    ///         let token = Token(connections.len() + 1);
+    ///         registry.register(&connection, token, Interests::READABLE)?;
+    ///         connections.push(connection);
+    ///     }
+    /// }
+    /// # }
+    /// #     Ok(())
+    /// # }
+    /// ```
     pub fn registry(&self) -> &Registry {
         &self.registry
     }
 
+    /// Replace the underlying selector with a new one, for use in the child
+    /// process after `fork`.
+    ///
+    /// A child process created using `fork` inherits the epoll file descriptor
+    /// of its parent, which refers to the same kernel object as the one used
+    /// by the parent. Registering sources in the child would then also change
+    /// the sources monitored by the parent. (A kqueue isn't inherited at all,
+    /// making the inherited `Poll` unusable in the child.) Calling this in the
+    /// child creates a new selector, giving the child a clean `Poll` without
+    /// affecting the parent. Calling this without forking simply replaces the
+    /// selector, closing the old one.
+    ///
+    /// # Notes
+    ///
+    /// None of the sources registered before calling this are registered
+    /// with the new selector, they have to be registered again. The same is
+    /// true for the [`Waker`] and any timeouts. Registries created using
+    /// [`Registry::try_clone`] keep referring to the old selector and must be
+    /// recreated using [`Poll::registry`].
+    ///
+    /// [`Waker`]: crate::Waker
+    ///
+    /// Only available on Unix.
+    #[cfg(unix)]
+    pub fn recreate_after_fork(&mut self) -> io::Result<()> {
+        // This must be checked before creating the new selector, which may
+        // reuse the number of a closed file descriptor.
+        let is_open = self.registry.selector.is_open();
+        let old = std::mem::replace(&mut self.registry, Registry::new()?);
+        if is_open {
+            // Closing an inherited file descriptor only drops the child's
+            // reference, the parent's selector is unaffected.
+            drop(old);
+        } else {
+            // A kqueue isn't inherited, its file descriptor is already closed
+            // and its number may have been reused by the new kqueue, so it
+            // must not be closed again.
+            std::mem::forget(old.selector);
+        }
+        Ok(())
+    }
+
+    /// Deregisters all [`event::Source`]s at once.
+    ///
+    /// This replaces the underlying epoll or kqueue instance with a new one,
+    /// dropping the interest of all sources registered with it, including
+    /// sources that were never deregistered and the sources of any [`Waker`].
+    /// Afterwards [`source_count`] returns zero and, when the `timers` feature
+    /// is enabled, all timeouts set using `set_timeout` are removed.
+    ///
+    /// All prior registrations are invalidated, including those made using a
+    /// [`Registry`] created by [`Registry::try_clone`]: sources can be
+    /// registered again using [`register`], but not reregistered. Existing
+    /// [`Waker`]s no longer wake up [`Poll::poll`] and must be recreated.
+    ///
+    /// Only available on Unix.
+    ///
+    /// [`event::Source`]: crate::event::Source
+    /// [`Waker`]: crate::Waker
+    /// [`source_count`]: Registry::source_count
+    /// [`register`]: Registry::register
+    ///
+    /// # Notes
+    ///
+    /// Registries created using [`Registry::try_clone`] keep referring to the
+    /// old instance, sources registered using them after calling this don't
+    /// return events. These registries must be dropped and cloned again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UdpSocket;
+    /// use mio::{Events, Interests, Poll, Token};
+    /// use std::time::Duration;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(16);
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    /// poll.registry().register(&socket, Token(0), Interests::WRITABLE)?;
+    ///
+    /// // Shut down, dropping all registrations.
+    /// poll.clear()?;
+    /// assert_eq!(poll.registry().source_count(), 0);
+    ///
+    /// poll.poll(&mut events, Some(Duration::from_millis(10)))?;
+    /// assert!(events.is_empty());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn clear(&mut self) -> io::Result<()> {
+        trace!("deregistering all event sources from poller");
+        let registry = &self.registry;
+        registry.selector.clear().map(|()| {
+            registry.sources.store(0, Ordering::Relaxed);
+            *registry.waker.lock().unwrap() = None;
+            #[cfg(feature = "timers")]
+            registry.timers.lock().unwrap().clear();
+        })
+    }
+
+    /// Create a [`Waker`] for this `Poll` instance.
+    ///
+    /// This is a shorthand for `Waker::new(poll.registry(), token)`. The
+    /// returned `Waker` can be cloned and handed out to other threads, allowing
+    /// them to wake this `Poll` without being able to poll it or register
+    /// sources. See [`Waker`] for more information.
+    ///
+    /// [`Waker`]: crate::Waker
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::thread;
+    ///
+    /// use mio::{Events, Poll, Token};
+    ///
+    /// const WAKE_TOKEN: Token = Token(10);
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(2);
+    ///
+    /// let waker = poll.waker_handle(WAKE_TOKEN)?;
+    /// let waker1 = waker.clone();
+    /// let handle = thread::spawn(move || waker1.wake().expect("unable to wake"));
+    ///
+    /// poll.poll(&mut events, None)?;
+    /// assert_eq!(events.iter().next().unwrap().token(), WAKE_TOKEN);
+    /// # handle.join().unwrap();
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn waker_handle(&self, token: Token) -> io::Result<Waker> {
+        Waker::new(&self.registry, token)
+    }
+
     /// Wait for readiness events
     ///
     /// Blocks the current thread and waits for readiness events for any of the
@@ -285,7 +525,10 @@ impl Poll {
     ///
     /// Note that the `timeout` will be rounded up to the system clock
     /// granularity (usually 1ms), and kernel scheduling delays mean that
-    /// the blocking interval may be overrun by a small amount.
+    /// the blocking interval may be overrun by a small amount. A non-zero
+    /// timeout is never rounded down to zero, e.g. a timeout of 100
+    /// microseconds blocks for 1 millisecond when using epoll, which only
+    /// supports millisecond timeouts.
     ///
     /// `poll` returns the number of readiness events that have been pushed into
     /// `events` or `Err` when an error has been encountered with the system
@@ -305,9 +548,26 @@ impl Poll {
     ///
     /// # Notes
     ///
-    /// This returns any errors without attempting to retry, previous versions
-    /// of Mio would automatically retry the poll call if it was interrupted
-    /// (if `EINTR` was returned).
+    /// If the system call is interrupted, e.g. by a signal (`EINTR`), it's
+    /// automatically retried with the remaining time of `timeout`, so a
+    /// signal never extends or shortens the total time spent polling. Use
+    /// [`poll_interruptible`] to return an error of kind `Interrupted`
+    /// instead.
+    ///
+    /// [`poll_interruptible`]: Poll::poll_interruptible
+    ///
+    /// `poll` also returns when a [`Waker`] is woken, in which case `events`
+    /// may only contain the event for the waker's token and no I/O events at
+    /// all. [`Events::contains_only`] can be used to detect this case and
+    /// skip handling I/O. Like any other event, I/O events may be spurious,
+    /// see [spurious events].
+    ///
+    /// [`Waker`]: crate::Waker
+    /// [spurious events]: #spurious-events
+    ///
+    /// If `events` has a capacity of zero, there is nowhere to store any
+    /// events and an error of kind `InvalidInput` is returned, rather than
+    /// returning without events in a busy loop.
     ///
     /// # Examples
     ///
@@ -363,7 +623,354 @@ impl Poll {
     ///
     /// [struct]: #
     pub fn poll(&mut self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
-        self.registry.selector.select(events.sys(), timeout)
+        retry_interrupted(timeout, |timeout| self.poll_interruptible(events, timeout))
+    }
+
+    /// Wait for readiness events, using a timeout in milliseconds.
+    ///
+    /// This is the same as [`poll`], but takes the timeout as a number of
+    /// milliseconds. `None` blocks until an event is received, `Some(0)`
+    /// returns immediately.
+    ///
+    /// [`poll`]: Poll::poll
+    pub fn poll_timeout_ms(
+        &mut self,
+        events: &mut Events,
+        timeout_ms: Option<u32>,
+    ) -> io::Result<()> {
+        let timeout = timeout_ms.map(|ms| Duration::from_millis(u64::from(ms)));
+        self.poll(events, timeout)
+    }
+
+    /// Wait for readiness events, returning the number of events received.
+    ///
+    /// This is the same as [`poll`], but returns the number of events pushed
+    /// into `events`, i.e. `events.len()` after polling. A return value of
+    /// zero means `timeout` elapsed without any events.
+    ///
+    /// [`poll`]: Poll::poll
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    /// use std::time::Duration;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(1024);
+    ///
+    /// let n = poll.poll_count(&mut events, Some(Duration::from_millis(10)))?;
+    /// assert_eq!(n, 0);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn poll_count(
+        &mut self,
+        events: &mut Events,
+        timeout: Option<Duration>,
+    ) -> io::Result<usize> {
+        self.poll(events, timeout).map(|()| events.len())
+    }
+
+    /// Wait for readiness events, without retrying on interrupts.
+    ///
+    /// This is the same as [`poll`], but if the system call is interrupted,
+    /// e.g. by a signal (`EINTR`), it returns an error of kind `Interrupted`
+    /// instead of retrying. This can be used to act on signals, e.g. by
+    /// checking a flag set by a signal handler, before polling again.
+    ///
+    /// [`poll`]: Poll::poll
+    pub fn poll_interruptible(
+        &mut self,
+        events: &mut Events,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        check_capacity(events)?;
+        #[cfg(feature = "timers")]
+        let timeout = self.registry.timers_timeout(timeout);
+        self.registry.selector.select(events.sys(), timeout)?;
+        self.registry.drain_waker(events);
+        #[cfg(feature = "timers")]
+        self.registry.expire_timers(events);
+        #[cfg(feature = "poll-stats")]
+        self.stats.record(events, 0);
+        Ok(())
+    }
+
+    /// Returns the statistics about the calls to poll so far.
+    ///
+    /// All polling methods, e.g. [`poll`] and [`poll_append`], update the
+    /// statistics. Only successful polls are counted.
+    ///
+    /// Only available when the `poll-stats` feature is enabled.
+    ///
+    /// [`poll`]: Poll::poll
+    /// [`poll_append`]: Poll::poll_append
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    /// use std::time::Duration;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(128);
+    ///
+    /// poll.poll(&mut events, Some(Duration::from_millis(10)))?;
+    ///
+    /// let stats = poll.stats();
+    /// assert_eq!(stats.polls, 1);
+    /// if stats.buffer_full_count > stats.polls / 2 {
+    ///     println!("consider increasing the capacity of `Events`");
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "poll-stats")]
+    pub fn stats(&self) -> PollStats {
+        self.stats
+    }
+
+    /// Wait for readiness events until `deadline`.
+    ///
+    /// This works the same as [`poll`], except that it takes an absolute
+    /// deadline rather than a relative timeout. The remaining time is
+    /// determined when this method is called; if `deadline` has already passed
+    /// this polls without blocking, returning only the events that are ready.
+    ///
+    /// [`poll`]: Poll::poll
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(1024);
+    ///
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// // Keep polling until the deadline, e.g. to process events until the
+    /// // next timer fires.
+    /// while Instant::now() < deadline {
+    ///     poll.poll_deadline(&mut events, deadline)?;
+    ///
+    ///     for event in &events {
+    ///         println!("Got an event: {:?}", event);
+    ///     }
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn poll_deadline(&mut self, events: &mut Events, deadline: Instant) -> io::Result<()> {
+        let now = Instant::now();
+        let timeout = if deadline > now {
+            deadline - now
+        } else {
+            Duration::from_millis(0)
+        };
+        self.poll(events, Some(timeout))
+    }
+
+    /// Wait for readiness events, appending them to `events`.
+    ///
+    /// This works the same as [`poll`], except that the events already stored
+    /// in `events` are kept and new events are added after them. This allows
+    /// events from multiple short polls to be collected before processing
+    /// them.
+    ///
+    /// At most `events.capacity()` events are stored in total. If `events` is
+    /// already full this returns immediately without waiting. No events are
+    /// lost when the remaining capacity runs out, they'll be returned by the
+    /// next call to `poll` or `poll_append` instead. Like `poll` this returns
+    /// an error of kind `InvalidInput` if `events` has a capacity of zero, and
+    /// retries the poll if it's interrupted, without extending `timeout`.
+    ///
+    /// [`poll`]: Poll::poll
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll};
+    /// use std::time::Duration;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(1024);
+    ///
+    /// // Collect the events of two polls, before processing them.
+    /// poll.poll_append(&mut events, Some(Duration::from_millis(10)))?;
+    /// poll.poll_append(&mut events, Some(Duration::from_millis(10)))?;
+    ///
+    /// for event in &events {
+    ///     println!("Got an event: {:?}", event);
+    /// }
+    ///
+    /// // Clear the events before appending new ones.
+    /// events.clear();
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn poll_append(
+        &mut self,
+        events: &mut Events,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        retry_interrupted(timeout, |timeout| {
+            self.poll_append_interruptible(events, timeout)
+        })
+    }
+
+    /// Same as `poll_append`, but returns an `Interrupted` error rather than
+    /// retrying.
+    fn poll_append_interruptible(
+        &mut self,
+        events: &mut Events,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        check_capacity(events)?;
+        #[cfg(feature = "poll-stats")]
+        let len = events.len();
+        #[cfg(feature = "timers")]
+        let timeout = self.registry.timers_timeout(timeout);
+        self.registry
+            .selector
+            .select_append(events.sys(), timeout)?;
+        self.registry.drain_waker(events);
+        #[cfg(feature = "timers")]
+        self.registry.expire_timers(events);
+        #[cfg(feature = "poll-stats")]
+        self.stats.record(events, len);
+        Ok(())
+    }
+}
+
+/// Returns `true` if `err`, returned by [`Poll::poll`], is fatal.
+///
+/// A fatal error means the selector itself is no longer usable, e.g. the
+/// epoll or kqueue file descriptor is invalid (`EBADF`), and the `Poll`
+/// instance should be dropped. Other errors, e.g. an interrupted poll
+/// (`EINTR`) or a temporary lack of memory (`ENOMEM`), leave the selector
+/// usable and polling can be retried, possibly after backing off for some
+/// time. Errors that don't originate from the OS, such as the
+/// `InvalidInput` error for an `Events` without capacity, are not fatal
+/// either.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::{is_fatal_poll_error, Events, Poll};
+/// use std::time::Duration;
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(128);
+///
+/// # for _ in 0..1 {
+/// match poll.poll(&mut events, Some(Duration::from_millis(10))) {
+///     Ok(()) => { /* Handle the events. */ }
+///     Err(ref err) if is_fatal_poll_error(err) => return Err(err.to_string().into()),
+///     // Retry on the next iteration of the event loop.
+///     Err(err) => println!("error polling: {}", err),
+/// }
+/// # }
+/// #     Ok(())
+/// # }
+/// ```
+pub fn is_fatal_poll_error(err: &io::Error) -> bool {
+    sys::is_fatal_poll_error(err)
+}
+
+/// Calls `poll` until it doesn't return an `Interrupted` error, passing it the
+/// time remaining until the deadline determined by `timeout`.
+fn retry_interrupted<F>(timeout: Option<Duration>, mut poll: F) -> io::Result<()>
+where
+    F: FnMut(Option<Duration>) -> io::Result<()>,
+{
+    // If the deadline can't be represented we'll never reach it, so we
+    // might as well wait forever.
+    let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+    let mut timeout = deadline.and(timeout);
+    loop {
+        match poll(timeout) {
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {
+                if let Some(deadline) = deadline {
+                    let now = Instant::now();
+                    timeout = Some(if deadline > now {
+                        deadline - now
+                    } else {
+                        Duration::from_millis(0)
+                    });
+                }
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Returns an error if `events` can't hold any events, in which case polling
+/// would always return immediately without events.
+fn check_capacity(events: &Events) -> io::Result<()> {
+    if events.capacity() == 0 {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "can't poll using `Events` with a capacity of zero",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// The trigger mode used in registering an [`event::Source`].
+///
+/// See [`Registry::register_with_mode`].
+///
+/// [`event::Source`]: crate::event::Source
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Trigger {
+    /// Only return an event when the readiness of the source changes, this is
+    /// the default.
+    ///
+    /// This maps to `EPOLLET` when using epoll and `EV_CLEAR` when using
+    /// kqueue.
+    Edge,
+    /// Keep returning events for as long as the source is ready.
+    Level,
+    /// Return a single event once the source is ready, after which the source
+    /// is disabled until it's reregistered using [`reregister`]. Like
+    /// [`Trigger::Level`] the event is returned if the source is already
+    /// ready when it's (re)registered.
+    ///
+    /// This can be used to ensure only a single thread handles an event when
+    /// multiple threads poll the same [`Poll`] instance.
+    ///
+    /// This maps to `EPOLLONESHOT` when using epoll and `EV_DISPATCH` when
+    /// using kqueue. Note that kqueue disables the readable and writable
+    /// interests separately, so a source registered with both interests can
+    /// return an event for each.
+    ///
+    /// [`reregister`]: Registry::reregister
+    Oneshot,
+}
+
+#[cfg(feature = "poll-stats")]
+impl PollStats {
+    /// Record a poll, `old_len` is the number of events in `events` before
+    /// polling.
+    fn record(&mut self, events: &Events, old_len: usize) {
+        self.polls += 1;
+        self.events_returned += (events.len() - old_len) as u64;
+        if events.len() == events.capacity() {
+            self.buffer_full_count += 1;
+        }
     }
 }
 
@@ -379,6 +986,38 @@ impl fmt::Debug for Registry {
     }
 }
 
+impl<'s, S: event::Source + ?Sized> Registration<'s, S> {
+    /// Returns the registered source.
+    pub fn source(&self) -> &'s S {
+        self.source
+    }
+
+    /// Re-register the source, see [`Registry::reregister`].
+    pub fn reregister(&self, token: Token, interests: Interests) -> io::Result<()> {
+        self.registry.reregister(self.source, token, interests)
+    }
+}
+
+impl<'s, S: event::Source + ?Sized> Drop for Registration<'s, S> {
+    fn drop(&mut self) {
+        if let Err(err) = self.registry.deregister(self.source) {
+            error!("error deregistering event source: {}", err);
+        }
+    }
+}
+
+impl<'s, S: event::Source + ?Sized> fmt::Debug for Registration<'s, S> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Registration").finish()
+    }
+}
+
+/// Returns the file descriptor of the underlying epoll or kqueue instance.
+///
+/// This can be used to register `Poll` with another event loop, e.g. to nest
+/// it in another epoll instance, as the file descriptor becomes readable once
+/// events are ready. The file descriptor is owned by `Poll` and **must not**
+/// be closed by the caller.
 #[cfg(unix)]
 impl AsRawFd for Poll {
     fn as_raw_fd(&self) -> RawFd {
@@ -386,7 +1025,57 @@ impl AsRawFd for Poll {
     }
 }
 
+/// Returns the handle of the underlying I/O completion port.
+///
+/// The handle is owned by `Poll` and **must not** be closed by the caller.
+#[cfg(windows)]
+impl AsRawHandle for Poll {
+    fn as_raw_handle(&self) -> RawHandle {
+        self.registry.selector.as_raw_handle()
+    }
+}
+
 impl Registry {
+    /// Create a `Registry` with a new selector.
+    fn new() -> io::Result<Registry> {
+        sys::Selector::new().map(|selector| Registry {
+            selector: Arc::new(selector),
+            sources: Arc::new(AtomicUsize::new(0)),
+            #[cfg(feature = "timers")]
+            timers: Arc::new(Mutex::new(BinaryHeap::new())),
+            waker: Arc::new(Mutex::new(None)),
+            trigger: Trigger::Edge,
+        })
+    }
+
+    /// Returns a `Registry` sharing the selector of this registry, unlike
+    /// `try_clone` this doesn't duplicate the file descriptor (or handle).
+    fn share(&self) -> Registry {
+        Registry {
+            selector: self.selector.clone(),
+            sources: self.sources.clone(),
+            #[cfg(feature = "timers")]
+            timers: self.timers.clone(),
+            waker: self.waker.clone(),
+            trigger: Trigger::Edge,
+        }
+    }
+
+    /// Calls `f` with a registry using the trigger mode `trigger`.
+    fn with_trigger<T, F>(&self, trigger: Trigger, f: F) -> T
+    where
+        F: FnOnce(&Registry) -> T,
+    {
+        if self.trigger == trigger {
+            f(self)
+        } else {
+            f(&Registry {
+                trigger,
+                ..self.share()
+            })
+        }
+    }
+
     /// Register an [`event::Source`] with the `Poll` instance.
     ///
     /// Once registered, the `Poll` instance will monitor the event source for
@@ -430,11 +1119,27 @@ impl Registry {
     /// This function is **thread safe**. It can be called concurrently from
     /// multiple threads.
     ///
+    /// # Registering a source under multiple tokens
+    ///
+    /// An event source can only be registered once with the same `Poll`
+    /// instance, registering it again returns an error on most platforms. To
+    /// receive readiness events for a single socket under multiple tokens,
+    /// e.g. readable events under one token and writable events under
+    /// another, clone the source using its `try_clone` method (e.g.
+    /// [`TcpStream::try_clone`]) and register each clone with its own token
+    /// and interests. The clones refer to the same socket, so both observe
+    /// the same readiness.
+    ///
+    /// Each clone must be deregistered (or dropped) separately. On epoll the
+    /// registration is only removed once all clones are closed or
+    /// deregistered.
+    ///
     /// [`event::Source`]: crate::event::Source
     /// [`poll`]: Poll::poll
     /// [`reregister`]: Registry::reregister
     /// [`deregister`]: Registry::deregister
     /// [`Token`]: Token
+    /// [`TcpStream::try_clone`]: crate::net::TcpStream::try_clone
     ///
     /// # Examples
     ///
@@ -486,12 +1191,7 @@ impl Registry {
     where
         S: event::Source + ?Sized,
     {
-        trace!(
-            "registering event source with poller: token={:?}, interests={:?}",
-            token,
-            interests
-        );
-        source.register(self, token, interests)
+        self.register_with_mode(source, token, interests, Trigger::Edge)
     }
 
     /// Re-register an [`event::Source`] with the `Poll` instance.
@@ -506,7 +1206,10 @@ impl Registry {
     /// requested for the handle.
     ///
     /// The event source must have previously been registered with this instance
-    /// of `Poll` otherwise the call to `reregister` will return with an error.
+    /// of `Poll` otherwise the call to `reregister` will return with an error
+    /// of kind [`NotFound`].
+    ///
+    /// [`NotFound`]: std::io::ErrorKind::NotFound
     ///
     /// See the [`register`] documentation for details about the function
     /// arguments and see the [`struct`] docs for a high level overview of
@@ -549,15 +1252,121 @@ impl Registry {
     /// [`readable`]: crate::event::Event::is_readable
     /// [`writable`]: crate::event::Event::is_writable
     pub fn reregister<S>(&self, source: &S, token: Token, interests: Interests) -> io::Result<()>
+    where
+        S: event::Source + ?Sized,
+    {
+        self.reregister_with_mode(source, token, interests, Trigger::Edge)
+    }
+
+    /// Register an [`event::Source`] with the `Poll` instance, using the
+    /// [`Trigger`] mode `trigger`.
+    ///
+    /// This works the same as [`register`], which always registers the source
+    /// edge-triggered, but allows registering it level-triggered. A
+    /// level-triggered source will be returned by [`poll`] for as long as it's
+    /// ready, even if the corresponding operation wasn't performed until it
+    /// returned [`WouldBlock`].
+    ///
+    /// [`register`]: Registry::register
+    /// [`poll`]: Poll::poll
+    /// [`WouldBlock`]: std::io::ErrorKind::WouldBlock
+    ///
+    /// # Notes
+    ///
+    /// The trigger mode is passed to the [`event::Source`] as part of the
+    /// `Registry` it's registered with, which applies it to the file
+    /// descriptors the source registers using [`SourceFd`]. This includes all
+    /// sources provided by Mio. Sources the source registers using
+    /// [`register`] use the default, edge-triggered, mode.
+    ///
+    /// On Windows the trigger mode is ignored and the source is always
+    /// registered edge-triggered, this includes [`Trigger::Oneshot`].
+    ///
+    /// [`event::Source`]: crate::event::Source
+    /// [`SourceFd`]: crate::unix::SourceFd
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # use std::net;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Poll, Interests, Token, Trigger};
+    /// use mio::net::TcpStream;
+    ///
+    /// let poll = Poll::new()?;
+    ///
+    /// let address = "127.0.0.1:0".parse()?;
+    /// let listener = net::TcpListener::bind(address)?;
+    /// let socket = TcpStream::connect(listener.local_addr()?)?;
+    ///
+    /// // Register the socket with `poll`, keep returning events while it's
+    /// // readable.
+    /// poll.registry().register_with_mode(
+    ///     &socket,
+    ///     Token(0),
+    ///     Interests::READABLE,
+    ///     Trigger::Level)?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn register_with_mode<S>(
+        &self,
+        source: &S,
+        token: Token,
+        interests: Interests,
+        trigger: Trigger,
+    ) -> io::Result<()>
+    where
+        S: event::Source + ?Sized,
+    {
+        trace!(
+            "registering event source with poller: token={:?}, interests={:?}, trigger={:?}",
+            token,
+            interests,
+            trigger
+        );
+        self.with_trigger(trigger, |registry| {
+            source.register(registry, token, interests)
+        })
+        .map(|()| {
+            self.sources.fetch_add(1, Ordering::Relaxed);
+        })
+    }
+
+    /// Re-register an [`event::Source`] with the `Poll` instance, using the
+    /// [`Trigger`] mode `trigger`.
+    ///
+    /// This works the same as [`reregister`], but allows changing the trigger
+    /// mode of the source. See [`register_with_mode`] for more information
+    /// about the trigger mode.
+    ///
+    /// Note that kqueue may not apply a changed trigger mode to a source that
+    /// is already registered, deregistering the source and registering it
+    /// again works on all platforms.
+    ///
+    /// [`event::Source`]: crate::event::Source
+    /// [`reregister`]: Registry::reregister
+    /// [`register_with_mode`]: Registry::register_with_mode
+    pub fn reregister_with_mode<S>(
+        &self,
+        source: &S,
+        token: Token,
+        interests: Interests,
+        trigger: Trigger,
+    ) -> io::Result<()>
     where
         S: event::Source + ?Sized,
     {
         trace!(
-            "reregistering event source with poller: token={:?}, interests={:?}",
+            "reregistering event source with poller: token={:?}, interests={:?}, trigger={:?}",
             token,
-            interests
+            interests,
+            trigger
         );
-        source.reregister(self, token, interests)
+        self.with_trigger(trigger, |registry| {
+            source.reregister(registry, token, interests)
+        })
     }
 
     /// Deregister an [`event::Source`] with the `Poll` instance.
@@ -574,6 +1383,23 @@ impl Registry {
     /// deregistered; however, it must be passed back to the **same** `Poll`
     /// instance.
     ///
+    /// # Deregistering while polling
+    ///
+    /// Using a `Registry` cloned with [`try_clone`] a source can be
+    /// deregistered from another thread while [`Poll::poll`] is blocked. Once
+    /// `deregister` returns no readiness changes of the source are reported
+    /// anymore, including by the `poll` call that was already in progress,
+    /// i.e. writing to the deregistered source doesn't wake up the poll.
+    ///
+    /// However events the OS already handed to the in-progress `poll` call,
+    /// i.e. readiness that occurred *before* `deregister` was called, may
+    /// still be returned by that call. The caller must handle events for
+    /// tokens it has just deregistered, e.g. by ignoring them. On Windows
+    /// deregistering cancels the pending poll operation of the source, the
+    /// resulting completion is dropped by mio and not returned as an event.
+    ///
+    /// [`try_clone`]: Registry::try_clone
+    ///
     /// # Examples
     ///
     /// ```
@@ -611,17 +1437,333 @@ impl Registry {
         S: event::Source + ?Sized,
     {
         trace!("deregistering event source from poller");
-        source.deregister(self)
+        source.deregister(self).map(|()| {
+            // Some selectors don't return an error when deregistering a source
+            // that isn't registered, so don't underflow the counter.
+            let mut count = self.sources.load(Ordering::Relaxed);
+            while count != 0 {
+                match self.sources.compare_exchange_weak(
+                    count,
+                    count - 1,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => break,
+                    Err(current) => count = current,
+                }
+            }
+        })
+    }
+
+    /// Register multiple event sources with the `Poll` instance.
+    ///
+    /// Each `(source, token, interests)` tuple is registered in order, as if
+    /// by calling [`register`]. If registering any of the sources fails the
+    /// sources already registered by this call are deregistered again, in
+    /// order, and the original error is returned. Errors returned while
+    /// deregistering are ignored.
+    ///
+    /// [`register`]: #method.register
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::{TcpListener, UdpSocket};
+    /// use mio::{Interests, Poll, Token};
+    ///
+    /// let poll = Poll::new()?;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:0".parse()?)?;
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// // Either both sources are registered, or neither is.
+    /// poll.registry().register_all(&[
+    ///     (&listener, Token(0), Interests::READABLE),
+    ///     (&socket, Token(1), Interests::READABLE | Interests::WRITABLE),
+    /// ])?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn register_all(
+        &self,
+        sources: &[(&dyn event::Source, Token, Interests)],
+    ) -> io::Result<()> {
+        for (n, &(source, token, interests)) in sources.iter().enumerate() {
+            if let Err(err) = self.register(source, token, interests) {
+                for &(source, _, _) in &sources[..n] {
+                    // Only the original error is returned.
+                    let _ = self.deregister(source);
+                }
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Register an [`event::Source`] with the `Poll` instance, returning a
+    /// [`Registration`] which deregisters the source when dropped.
+    ///
+    /// This is the same as [`register`], but ensures the source is
+    /// deregistered before it's dropped, which is required when the
+    /// underlying file descriptor or socket is shared with another handle,
+    /// e.g. one created by `try_clone`. Use [`register`] to manually
+    /// deregister the source instead.
+    ///
+    /// The `Registration` shares the selector with this registry, so it
+    /// doesn't borrow the `Poll` instance. Unlike [`try_clone`] this doesn't
+    /// duplicate the selector's file descriptor (or handle).
+    ///
+    /// [`register`]: Registry::register
+    /// [`try_clone`]: Registry::try_clone
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UdpSocket;
+    /// use mio::{Interests, Poll, Token};
+    ///
+    /// let poll = Poll::new()?;
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    ///
+    /// {
+    ///     let _registration = poll
+    ///         .registry()
+    ///         .register_scoped(&socket, Token(0), Interests::READABLE)?;
+    ///     // The socket is registered while `_registration` is alive.
+    /// }
+    /// // And deregistered once it's dropped.
+    /// assert_eq!(poll.registry().source_count(), 0);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn register_scoped<'s, S>(
+        &self,
+        source: &'s S,
+        token: Token,
+        interests: Interests,
+    ) -> io::Result<Registration<'s, S>>
+    where
+        S: event::Source + ?Sized,
+    {
+        self.register(source, token, interests)?;
+        Ok(Registration {
+            registry: self.share(),
+            source,
+        })
     }
 
     /// Creates a new independently owned `Registry`.
     ///
     /// Event sources registered with this `Registry` will be registered with
     /// the original `Registry` and `Poll` instance.
+    ///
+    /// The returned `Registry` can be moved to another thread to register,
+    /// reregister and deregister event sources concurrently with polling the
+    /// original `Poll` instance.
+    ///
+    /// The clone shares the underlying selector (the epoll or kqueue file
+    /// descriptor, or the I/O completion port) with the original, which is
+    /// only closed once both are dropped. This means the clone stays valid
+    /// after the `Poll` instance is dropped, but the events of any source
+    /// registered with it can then no longer be retrieved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::TcpListener;
+    /// use mio::{Events, Interests, Poll, Token};
+    /// use std::thread;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let registry = poll.registry().try_clone()?;
+    ///
+    /// // Register a listener from another thread.
+    /// let handle = thread::spawn(move || {
+    ///     let listener = TcpListener::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    ///     registry.register(&listener, Token(0), Interests::READABLE).unwrap();
+    ///     listener
+    /// });
+    /// let listener = handle.join().unwrap();
+    ///
+    /// // Events for the listener are returned by the original `Poll`.
+    /// let _stream = std::net::TcpStream::connect(listener.local_addr()?)?;
+    /// let mut events = Events::with_capacity(128);
+    /// poll.poll(&mut events, None)?;
+    /// #     Ok(())
+    /// # }
+    /// ```
     pub fn try_clone(&self) -> io::Result<Registry> {
-        self.selector
-            .try_clone()
-            .map(|selector| Registry { selector })
+        self.selector.try_clone().map(|selector| Registry {
+            selector: Arc::new(selector),
+            sources: self.sources.clone(),
+            #[cfg(feature = "timers")]
+            timers: self.timers.clone(),
+            waker: self.waker.clone(),
+            trigger: Trigger::Edge,
+        })
+    }
+
+    /// Returns the number of [`event::Source`]s currently registered.
+    ///
+    /// The count is incremented by every successful call to [`register`] and
+    /// decremented by every successful call to [`deregister`], [`reregister`]
+    /// doesn't change it. The count is shared with all registries created
+    /// using [`try_clone`].
+    ///
+    /// This can be used to size [`Events`] accordingly, avoiding extra calls
+    /// to [`Poll::poll`] if many sources are ready at the same time.
+    ///
+    /// [`event::Source`]: crate::event::Source
+    /// [`register`]: Registry::register
+    /// [`deregister`]: Registry::deregister
+    /// [`reregister`]: Registry::reregister
+    /// [`try_clone`]: Registry::try_clone
+    ///
+    /// # Notes
+    ///
+    /// Sources registered or deregistered using the selector directly, e.g.
+    /// [`event::Source::register`] called by hand, or sources that are
+    /// dropped without being deregistered are not accounted for. Since some
+    /// platforms don't return an error when deregistering a source that isn't
+    /// registered the count is only an approximation in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::UdpSocket;
+    /// use mio::{Events, Interests, Poll, Token};
+    ///
+    /// let poll = Poll::new()?;
+    /// let socket = UdpSocket::bind("127.0.0.1:0".parse()?)?;
+    /// poll.registry().register(&socket, Token(0), Interests::READABLE)?;
+    ///
+    /// let events = Events::with_capacity(poll.registry().source_count().max(16));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn source_count(&self) -> usize {
+        self.sources.load(Ordering::Relaxed)
+    }
+
+    /// Set a timeout for `token`, after which [`poll`] returns an event for
+    /// `token` for which [`Event::is_timeout`] returns true.
+    ///
+    /// Polling waits at most until the earliest timeout. Each timeout returns
+    /// a single event, multiple timeouts can be set for the same token. If
+    /// `events` is full the event is returned by the next poll.
+    ///
+    /// Only available when the `timers` feature is enabled.
+    ///
+    /// [`poll`]: Poll::poll
+    /// [`Event::is_timeout`]: crate::event::Event::is_timeout
+    ///
+    /// # Notes
+    ///
+    /// Setting a timeout doesn't wake up a call to [`poll`] that is already
+    /// waiting, so the timeout is only taken into account by the next poll.
+    /// Use a [`Waker`] to wake up the polling thread if required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::{Events, Poll, Token};
+    /// use std::time::Duration;
+    ///
+    /// let mut poll = Poll::new()?;
+    /// let mut events = Events::with_capacity(8);
+    ///
+    /// poll.registry().set_timeout(Token(0), Duration::from_millis(10));
+    ///
+    /// loop {
+    ///     poll.poll(&mut events, None)?;
+    ///     for event in &events {
+    ///         if event.token() == Token(0) && event.is_timeout() {
+    ///             return Ok(());
+    ///         }
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "timers")]
+    pub fn set_timeout(&self, token: Token, after: Duration) {
+        // If the deadline can't be represented it will never be reached.
+        if let Some(deadline) = Instant::now().checked_add(after) {
+            self.timers.lock().unwrap().push(Reverse((deadline, token)));
+        }
+    }
+
+    /// Returns `timeout` capped at the time until the earliest timeout.
+    #[cfg(feature = "timers")]
+    fn timers_timeout(&self, timeout: Option<Duration>) -> Option<Duration> {
+        let timers = self.timers.lock().unwrap();
+        match timers.peek() {
+            Some(Reverse((deadline, _))) => {
+                let now = Instant::now();
+                let mut until = if *deadline > now {
+                    *deadline - now
+                } else {
+                    Duration::from_millis(0)
+                };
+                // Round up to whole milliseconds, otherwise selectors with
+                // millisecond precision (epoll) return before the deadline.
+                let sub_millis = until.subsec_nanos() % 1_000_000;
+                if sub_millis != 0 {
+                    until += Duration::from_nanos(u64::from(1_000_000 - sub_millis));
+                }
+                Some(timeout.map_or(until, |timeout| cmp::min(timeout, until)))
+            }
+            None => timeout,
+        }
+    }
+
+    /// Drains the `Waker` if `events` contains an event for it, so that
+    /// multiple wake ups result in a single event.
+    ///
+    /// A wake up that happens after the selector returned, but before the
+    /// waker is drained, is coalesced into the event in `events`.
+    fn drain_waker(&self, events: &Events) {
+        if events.is_empty() {
+            return;
+        }
+
+        let waker = self.waker.lock().unwrap();
+        if let Some((token, ref waker)) = *waker {
+            if !events.iter().any(|event| event.token() == token) {
+                return;
+            }
+            if let Some(waker) = waker.upgrade() {
+                if let Err(err) = waker.drain() {
+                    error!("error draining waker: {}", err);
+                }
+            }
+        }
+    }
+
+    /// Adds an event for every expired timeout to `events`, as long as it has
+    /// capacity.
+    #[cfg(feature = "timers")]
+    fn expire_timers(&self, events: &mut Events) {
+        let now = Instant::now();
+        let mut timers = self.timers.lock().unwrap();
+        while events.len() < events.capacity() {
+            match timers.peek() {
+                Some(Reverse((deadline, _))) if *deadline <= now => {}
+                _ => break,
+            }
+            if let Some(Reverse((_, token))) = timers.pop() {
+                events.sys().push(sys::event::timeout(token));
+            }
+        }
     }
 }
 
@@ -631,6 +1773,16 @@ pub fn selector(registry: &Registry) -> &sys::Selector {
     &registry.selector
 }
 
+/// Returns the trigger mode to register file descriptors with.
+#[cfg(unix)]
+pub fn trigger(registry: &Registry) -> Trigger {
+    registry.trigger
+}
+
+pub fn set_waker(registry: &Registry, token: Token, waker: &Arc<sys::Waker>) {
+    *registry.waker.lock().unwrap() = Some((token, Arc::downgrade(waker)));
+}
+
 #[cfg(debug_assertions)]
 impl SelectorId {
     pub fn new() -> SelectorId {
@@ -669,3 +1821,10 @@ pub fn as_raw_fd() {
     let poll = Poll::new().unwrap();
     assert!(poll.as_raw_fd() > 0);
 }
+
+#[test]
+#[cfg(windows)]
+pub fn as_raw_handle() {
+    let poll = Poll::new().unwrap();
+    assert!(!poll.as_raw_handle().is_null());
+}
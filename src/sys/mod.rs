@@ -15,15 +15,22 @@
 
 #[cfg(unix)]
 pub use self::unix::{
-    event, Event, Events, Selector, SocketAddr, SourceFd, TcpListener, TcpStream, UdpSocket,
-    UnixDatagram, UnixListener, UnixStream, Waker,
+    event, is_fatal_poll_error, is_transient_accept_error, pipe, Event, Events, Selector,
+    SocketAddr, SourceFd, TcpListener, TcpSocket, TcpStream, UCred, UdpSocket, UnixDatagram,
+    UnixListener, UnixStream, Waker,
 };
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use self::unix::{EventFd, SignalFd, SignalInfo, TimerFd};
+
 #[cfg(unix)]
 mod unix;
 
 #[cfg(windows)]
-pub use self::windows::{event, Event, Events, Selector, TcpListener, TcpStream, UdpSocket, Waker};
+pub use self::windows::{
+    event, is_fatal_poll_error, is_transient_accept_error, Event, Events, Selector, TcpListener,
+    TcpSocket, TcpStream, UdpSocket, Waker,
+};
 
 #[cfg(windows)]
 mod windows;
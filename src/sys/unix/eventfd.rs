@@ -0,0 +1,141 @@
+use crate::sys::unix::SourceFd;
+use crate::{event, Interests, Registry, Token};
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+
+/// Counting semaphore backed by Linux's [`eventfd`].
+///
+/// The `EventFd` is readable while its counter is non-zero. [`post`] adds to
+/// the counter and each call to [`read`] decrements it by one, so each posted
+/// unit is read exactly once. This can be used to signal work to the event
+/// loop from other threads, one unit per item of work.
+///
+/// Unlike [`Waker`], posts don't coalesce. Note however that with the default
+/// edge-triggered registration a single event is returned for multiple posted
+/// units, so `read` must be called until it returns a `WouldBlock` error.
+/// Register the `EventFd` using [`Trigger::Level`] to get an event from every
+/// poll for as long as the counter is non-zero.
+///
+/// [`Trigger::Level`]: crate::Trigger::Level
+/// [`eventfd`]: http://man7.org/linux/man-pages/man2/eventfd.2.html
+/// [`post`]: EventFd::post
+/// [`read`]: EventFd::read
+/// [`Waker`]: crate::Waker
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::unix::EventFd;
+/// use mio::{Events, Interests, Poll, Token};
+/// use std::io;
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let semaphore = EventFd::new()?;
+/// poll.registry().register(&semaphore, Token(0), Interests::READABLE)?;
+///
+/// // Post two units of work, e.g. from another thread.
+/// semaphore.post(2)?;
+///
+/// let mut handled = 0;
+/// while handled < 2 {
+///     poll.poll(&mut events, None)?;
+///     for event in &events {
+///         if event.token() == Token(0) {
+///             // Handle all posted units.
+///             loop {
+///                 match semaphore.read() {
+///                     Ok(_) => handled += 1,
+///                     Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+///                     Err(err) => return Err(err.into()),
+///                 }
+///             }
+///         }
+///     }
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+pub struct EventFd {
+    fd: File,
+}
+
+impl EventFd {
+    /// Create a new `EventFd` with a counter of zero.
+    pub fn new() -> io::Result<EventFd> {
+        let flags = libc::EFD_NONBLOCK | libc::EFD_CLOEXEC | libc::EFD_SEMAPHORE;
+        syscall!(eventfd(0, flags)).map(|fd| EventFd {
+            fd: unsafe { File::from_raw_fd(fd) },
+        })
+    }
+
+    /// Add `n` to the counter, making the `EventFd` readable if `n` is
+    /// non-zero.
+    ///
+    /// If the counter would overflow (its maximum is `u64::max_value() - 1`)
+    /// this returns a `WouldBlock` error.
+    pub fn post(&self, n: u64) -> io::Result<()> {
+        (&self.fd).write(&n.to_ne_bytes()).map(|_| ())
+    }
+
+    /// Decrement the counter by one, returning `1`.
+    ///
+    /// If the counter is zero this returns a `WouldBlock` error.
+    pub fn read(&self) -> io::Result<u64> {
+        let mut buf = [0; 8];
+        (&self.fd).read(&mut buf).map(|_| u64::from_ne_bytes(buf))
+    }
+}
+
+impl event::Source for EventFd {
+    fn register(&self, registry: &Registry, token: Token, interests: Interests) -> io::Result<()> {
+        SourceFd(&self.as_raw_fd()).register(registry, token, interests)
+    }
+
+    fn reregister(
+        &self,
+        registry: &Registry,
+        token: Token,
+        interests: Interests,
+    ) -> io::Result<()> {
+        SourceFd(&self.as_raw_fd()).reregister(registry, token, interests)
+    }
+
+    fn deregister(&self, registry: &Registry) -> io::Result<()> {
+        SourceFd(&self.as_raw_fd()).deregister(registry)
+    }
+}
+
+impl fmt::Debug for EventFd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventFd")
+            .field("fd", &self.as_raw_fd())
+            .finish()
+    }
+}
+
+impl FromRawFd for EventFd {
+    unsafe fn from_raw_fd(fd: RawFd) -> EventFd {
+        EventFd {
+            fd: File::from_raw_fd(fd),
+        }
+    }
+}
+
+impl AsRawFd for EventFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for EventFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.fd.into_raw_fd()
+    }
+}
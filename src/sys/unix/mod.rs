@@ -12,22 +12,39 @@ macro_rules! syscall {
     }};
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod eventfd;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use self::eventfd::EventFd;
+
 mod net;
 
+pub mod pipe;
+
 mod selector;
-pub use self::selector::{event, Event, Events, Selector};
+pub use self::selector::{event, is_fatal_poll_error, Event, Events, Selector};
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod signalfd;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use self::signalfd::{SignalFd, SignalInfo};
 
 mod sourcefd;
 pub use self::sourcefd::SourceFd;
 
 mod tcp;
-pub use self::tcp::{TcpListener, TcpStream};
+pub use self::tcp::{is_transient_accept_error, TcpListener, TcpSocket, TcpStream};
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+mod timerfd;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub use self::timerfd::TimerFd;
 
 mod udp;
 pub use self::udp::UdpSocket;
 
 mod uds;
-pub use self::uds::{SocketAddr, UnixDatagram, UnixListener, UnixStream};
+pub use self::uds::{SocketAddr, UCred, UnixDatagram, UnixListener, UnixStream};
 
 mod waker;
 pub use self::waker::Waker;
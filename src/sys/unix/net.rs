@@ -1,6 +1,8 @@
-use std::io;
-use std::mem::size_of_val;
-use std::net::SocketAddr;
+use std::cmp;
+use std::io::{self, IoSlice};
+use std::mem::{self, size_of, size_of_val, MaybeUninit};
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::os::unix::io::RawFd;
 
 pub fn new_ip_socket(addr: SocketAddr, socket_type: libc::c_int) -> io::Result<libc::c_int> {
     let domain = match addr {
@@ -43,9 +45,71 @@ pub fn new_socket(domain: libc::c_int, socket_type: libc::c_int) -> io::Result<l
             })
     });
 
+    // Darwin doesn't have `MSG_NOSIGNAL`, see `send`.
+    #[cfg(any(target_os = "ios", target_os = "macos"))]
+    let socket = socket.and_then(|socket| {
+        set_nosigpipe(socket).map(|_| socket).map_err(|e| {
+            let _ = syscall!(close(socket));
+            e
+        })
+    });
+
     socket
 }
 
+/// Set the `SO_NOSIGPIPE` option, which causes writing to a socket of which
+/// the peer closed the connection to return an `EPIPE` error rather than
+/// raising `SIGPIPE`.
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+pub fn set_nosigpipe(fd: RawFd) -> io::Result<()> {
+    setsockopt(fd, libc::SOL_SOCKET, libc::SO_NOSIGPIPE, 1 as libc::c_int)
+}
+
+/// Flags used by `send` and `send_vectored`. `MSG_NOSIGNAL` causes writing
+/// to a socket of which the peer closed the connection to return an `EPIPE`
+/// error rather than raising `SIGPIPE`. On Darwin the `SO_NOSIGPIPE` option
+/// is set on the socket instead.
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+const SEND_FLAGS: libc::c_int = libc::MSG_NOSIGNAL;
+
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+const SEND_FLAGS: libc::c_int = 0;
+
+/// Write `buf` to the connected socket `fd`, without raising `SIGPIPE`.
+pub fn send(fd: RawFd, buf: &[u8]) -> io::Result<usize> {
+    syscall!(send(
+        fd,
+        buf.as_ptr() as *const libc::c_void,
+        buf.len(),
+        SEND_FLAGS,
+    ))
+    .map(|n| n as usize)
+}
+
+/// Write `bufs` to the connected socket `fd`, without raising `SIGPIPE`.
+pub fn send_vectored(fd: RawFd, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+    let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+    // `IoSlice` is guaranteed to be ABI compatible with `iovec`.
+    msg.msg_iov = bufs.as_ptr() as *mut libc::iovec;
+    msg.msg_iovlen = bufs.len() as _;
+    syscall!(sendmsg(fd, &msg, SEND_FLAGS)).map(|n| n as usize)
+}
+
+/// Converts a `SocketAddr` into a pointer to a `sockaddr` and its length.
 pub fn socket_addr(addr: &SocketAddr) -> (*const libc::sockaddr, libc::socklen_t) {
     match addr {
         SocketAddr::V4(ref addr) => (
@@ -58,3 +122,188 @@ pub fn socket_addr(addr: &SocketAddr) -> (*const libc::sockaddr, libc::socklen_t
         ),
     }
 }
+
+/// Converts a `sockaddr_storage` into a `SocketAddr`, the reverse of
+/// `socket_addr`.
+///
+/// # Safety
+///
+/// `storage` must be initialised by the OS, e.g. by `recvmsg`.
+pub unsafe fn to_socket_addr(storage: &libc::sockaddr_storage) -> io::Result<SocketAddr> {
+    match storage.ss_family as libc::c_int {
+        libc::AF_INET => {
+            let addr = &*(storage as *const libc::sockaddr_storage as *const libc::sockaddr_in);
+            let ip = Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr));
+            let port = u16::from_be(addr.sin_port);
+            Ok(SocketAddr::V4(SocketAddrV4::new(ip, port)))
+        }
+        libc::AF_INET6 => {
+            let addr = &*(storage as *const libc::sockaddr_storage as *const libc::sockaddr_in6);
+            let ip = Ipv6Addr::from(addr.sin6_addr.s6_addr);
+            let port = u16::from_be(addr.sin6_port);
+            Ok(SocketAddr::V6(SocketAddrV6::new(
+                ip,
+                port,
+                addr.sin6_flowinfo,
+                addr.sin6_scope_id,
+            )))
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid address family",
+        )),
+    }
+}
+
+/// Set the socket option `name` at `level` to `value`.
+pub fn setsockopt<T>(fd: RawFd, level: libc::c_int, name: libc::c_int, value: T) -> io::Result<()> {
+    syscall!(setsockopt(
+        fd,
+        level,
+        name,
+        &value as *const T as *const libc::c_void,
+        size_of::<T>() as libc::socklen_t,
+    ))
+    .map(|_| ())
+}
+
+/// Get the value of the socket option `name` at `level`.
+pub fn getsockopt<T: Copy>(fd: RawFd, level: libc::c_int, name: libc::c_int) -> io::Result<T> {
+    let mut value: MaybeUninit<T> = MaybeUninit::zeroed();
+    let mut len = size_of::<T>() as libc::socklen_t;
+    syscall!(getsockopt(
+        fd,
+        level,
+        name,
+        value.as_mut_ptr() as *mut libc::c_void,
+        &mut len,
+    ))
+    // This is safe because the value is zeroed and the kernel wrote at most
+    // `size_of::<T>()` bytes.
+    .map(|_| unsafe { value.assume_init() })
+}
+
+/// Returns the level and name of the socket option that controls the time to
+/// live for sockets bound to `addr`: `IP_TTL` for IPv4 and
+/// `IPV6_UNICAST_HOPS` for IPv6.
+fn ttl_option(addr: SocketAddr) -> (libc::c_int, libc::c_int) {
+    match addr {
+        SocketAddr::V4(..) => (libc::IPPROTO_IP, libc::IP_TTL),
+        SocketAddr::V6(..) => (libc::IPPROTO_IPV6, libc::IPV6_UNICAST_HOPS),
+    }
+}
+
+/// Set the time to live of the socket `fd`, bound to `addr`.
+pub fn set_socket_ttl(fd: RawFd, addr: SocketAddr, ttl: u32) -> io::Result<()> {
+    if ttl > 255 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "TTL must be in the range 0..=255",
+        ));
+    }
+
+    let (level, name) = ttl_option(addr);
+    setsockopt(fd, level, name, ttl as libc::c_int)
+}
+
+/// Get the time to live of the socket `fd`, bound to `addr`.
+pub fn socket_ttl(fd: RawFd, addr: SocketAddr) -> io::Result<u32> {
+    let (level, name) = ttl_option(addr);
+    getsockopt::<libc::c_int>(fd, level, name).map(|ttl| ttl as u32)
+}
+
+/// Returns the level and name of the socket option that controls the type of
+/// service for sockets bound to `addr`: `IP_TOS` for IPv4 and `IPV6_TCLASS`
+/// for IPv6.
+fn tos_option(addr: SocketAddr) -> (libc::c_int, libc::c_int) {
+    match addr {
+        SocketAddr::V4(..) => (libc::IPPROTO_IP, libc::IP_TOS),
+        SocketAddr::V6(..) => (libc::IPPROTO_IPV6, libc::IPV6_TCLASS),
+    }
+}
+
+/// Set the type of service of the socket `fd`, bound to `addr`.
+pub fn set_socket_tos(fd: RawFd, addr: SocketAddr, tos: u32) -> io::Result<()> {
+    if tos > 255 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "type of service must be in the range 0..=255",
+        ));
+    }
+
+    let (level, name) = tos_option(addr);
+    setsockopt(fd, level, name, tos as libc::c_int)
+}
+
+/// Get the type of service of the socket `fd`, bound to `addr`.
+pub fn socket_tos(fd: RawFd, addr: SocketAddr) -> io::Result<u32> {
+    let (level, name) = tos_option(addr);
+    getsockopt::<libc::c_int>(fd, level, name).map(|tos| tos as u32)
+}
+
+/// Set the `IPV6_V6ONLY` option of the socket `fd`, bound to `addr`, if
+/// `only_v6` is `Some`. Does nothing for IPv4 addresses.
+pub fn set_only_v6(fd: RawFd, addr: SocketAddr, only_v6: Option<bool>) -> io::Result<()> {
+    match (addr, only_v6) {
+        (SocketAddr::V6(..), Some(only_v6)) => setsockopt(
+            fd,
+            libc::IPPROTO_IPV6,
+            libc::IPV6_V6ONLY,
+            only_v6 as libc::c_int,
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Set the `SO_REUSEADDR` option on the socket `fd`.
+pub fn set_reuse_addr(fd: RawFd, on: bool) -> io::Result<()> {
+    setsockopt(fd, libc::SOL_SOCKET, libc::SO_REUSEADDR, on as libc::c_int)
+}
+
+/// Get the `SO_REUSEADDR` option of the socket `fd`.
+pub fn reuse_addr(fd: RawFd) -> io::Result<bool> {
+    getsockopt::<libc::c_int>(fd, libc::SOL_SOCKET, libc::SO_REUSEADDR).map(|on| on != 0)
+}
+
+/// Set the `SO_REUSEPORT` option on the socket `fd`.
+#[cfg(not(target_os = "solaris"))]
+pub fn set_reuse_port(fd: RawFd, on: bool) -> io::Result<()> {
+    setsockopt(fd, libc::SOL_SOCKET, libc::SO_REUSEPORT, on as libc::c_int)
+}
+
+#[cfg(target_os = "solaris")]
+pub fn set_reuse_port(_: RawFd, on: bool) -> io::Result<()> {
+    if on {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "SO_REUSEPORT is not supported on this platform",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Set the size of the buffer of the socket `fd`, `name` must be either
+/// `SO_RCVBUF` or `SO_SNDBUF`.
+pub fn set_socket_buffer_size(fd: RawFd, name: libc::c_int, size: usize) -> io::Result<()> {
+    let size = cmp::min(size, libc::c_int::max_value() as usize) as libc::c_int;
+    setsockopt(fd, libc::SOL_SOCKET, name, size)
+}
+
+/// Get the size of the buffer of the socket `fd`, `name` must be either
+/// `SO_RCVBUF` or `SO_SNDBUF`.
+pub fn socket_buffer_size(fd: RawFd, name: libc::c_int) -> io::Result<usize> {
+    getsockopt::<libc::c_int>(fd, libc::SOL_SOCKET, name).map(|size| size as usize)
+}
+
+/// Set the `SO_MARK` option, which requires the `CAP_NET_ADMIN` capability.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub fn set_socket_mark(fd: RawFd, mark: u32) -> io::Result<()> {
+    setsockopt(fd, libc::SOL_SOCKET, libc::SO_MARK, mark)
+}
+
+/// Get the `SO_MARK` option.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub fn socket_mark(fd: RawFd) -> io::Result<u32> {
+    getsockopt(fd, libc::SOL_SOCKET, libc::SO_MARK)
+}
@@ -1,6 +1,7 @@
-use crate::{Interests, Token};
+use super::not_registered;
+use crate::{Interests, Token, Trigger};
 
-use libc::{EPOLLET, EPOLLIN, EPOLLOUT, EPOLLRDHUP};
+use libc::{EPOLLET, EPOLLIN, EPOLLONESHOT, EPOLLOUT, EPOLLPRI, EPOLLRDHUP};
 use log::error;
 use std::os::unix::io::{AsRawFd, RawFd};
 #[cfg(debug_assertions)]
@@ -45,41 +46,96 @@ impl Selector {
         })
     }
 
+    /// Returns `false` if the file descriptor is closed, e.g. in the child
+    /// process after `fork` for selectors that aren't inherited.
+    pub fn is_open(&self) -> bool {
+        syscall!(fcntl(self.ep, libc::F_GETFD)).is_ok()
+    }
+
+    pub fn clear(&self) -> io::Result<()> {
+        let ep = syscall!(epoll_create1(libc::O_CLOEXEC))?;
+        // Replace the epoll instance referred to by `self.ep` with the new,
+        // empty one, this closes the old instance if it's not shared.
+        let res = syscall!(dup3(ep, self.ep, libc::O_CLOEXEC)).map(|_| ());
+        if let Err(err) = syscall!(close(ep)) {
+            error!("error closing epoll: {}", err);
+        }
+        res
+    }
+
     pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        events.clear();
+        self.select_append(events, timeout)
+    }
+
+    pub fn select_append(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        let len = events.len();
+        let remaining = events.capacity() - len;
+        if remaining == 0 {
+            return Ok(());
+        }
+
         let timeout = timeout
-            .map(|to| cmp::min(to.as_millis(), libc::c_int::max_value() as u128) as libc::c_int)
+            .map(|to| {
+                // Round up to whole milliseconds, otherwise a sub-millisecond
+                // timeout would become zero and not block at all.
+                let mut ms = to.as_millis();
+                if to.subsec_nanos() % 1_000_000 != 0 {
+                    ms += 1;
+                }
+                cmp::min(ms, libc::c_int::max_value() as u128) as libc::c_int
+            })
             .unwrap_or(-1);
 
-        events.clear();
         syscall!(epoll_wait(
             self.ep,
-            events.as_mut_ptr(),
-            events.capacity() as i32,
+            events.as_mut_ptr().wrapping_add(len),
+            remaining as i32,
             timeout,
         ))
         .map(|n_events| {
             // This is safe because `epoll_wait` ensures that `n_events` are
             // assigned.
-            unsafe { events.set_len(n_events as usize) };
+            unsafe { events.set_len(len + n_events as usize) };
         })
     }
 
-    pub fn register(&self, fd: RawFd, token: Token, interests: Interests) -> io::Result<()> {
+    pub fn register(
+        &self,
+        fd: RawFd,
+        token: Token,
+        interests: Interests,
+        trigger: Trigger,
+    ) -> io::Result<()> {
         let mut event = libc::epoll_event {
-            events: interests_to_epoll(interests),
+            events: interests_to_epoll(interests, trigger),
             u64: usize::from(token) as u64,
         };
 
         syscall!(epoll_ctl(self.ep, libc::EPOLL_CTL_ADD, fd, &mut event)).map(|_| ())
     }
 
-    pub fn reregister(&self, fd: RawFd, token: Token, interests: Interests) -> io::Result<()> {
+    pub fn reregister(
+        &self,
+        fd: RawFd,
+        token: Token,
+        interests: Interests,
+        trigger: Trigger,
+    ) -> io::Result<()> {
         let mut event = libc::epoll_event {
-            events: interests_to_epoll(interests),
+            events: interests_to_epoll(interests, trigger),
             u64: usize::from(token) as u64,
         };
 
-        syscall!(epoll_ctl(self.ep, libc::EPOLL_CTL_MOD, fd, &mut event)).map(|_| ())
+        syscall!(epoll_ctl(self.ep, libc::EPOLL_CTL_MOD, fd, &mut event))
+            .map(|_| ())
+            .map_err(|err| {
+                if err.raw_os_error() == Some(libc::ENOENT) {
+                    not_registered()
+                } else {
+                    err
+                }
+            })
     }
 
     pub fn deregister(&self, fd: RawFd) -> io::Result<()> {
@@ -87,8 +143,12 @@ impl Selector {
     }
 }
 
-fn interests_to_epoll(interests: Interests) -> u32 {
-    let mut kind = EPOLLET;
+fn interests_to_epoll(interests: Interests, trigger: Trigger) -> u32 {
+    let mut kind = match trigger {
+        Trigger::Edge => EPOLLET,
+        Trigger::Level => 0,
+        Trigger::Oneshot => EPOLLONESHOT,
+    };
 
     if interests.is_readable() {
         kind = kind | EPOLLIN | EPOLLRDHUP;
@@ -98,6 +158,10 @@ fn interests_to_epoll(interests: Interests) -> u32 {
         kind |= EPOLLOUT;
     }
 
+    if interests.is_priority() {
+        kind |= EPOLLPRI;
+    }
+
     kind as u32
 }
 
@@ -139,6 +203,10 @@ pub mod event {
         (event.events as libc::c_int & libc::EPOLLERR) != 0
     }
 
+    pub fn is_hup(event: &Event) -> bool {
+        (event.events as libc::c_int & libc::EPOLLHUP) != 0
+    }
+
     pub fn is_read_closed(event: &Event) -> bool {
         // Both halves of the socket have closed
         event.events as libc::c_int & libc::EPOLLHUP != 0
@@ -168,6 +236,26 @@ pub mod event {
         // Not supported.
         false
     }
+
+    #[cfg(feature = "os-ext")]
+    pub fn raw_ready(event: &Event) -> u32 {
+        event.events
+    }
+
+    /// Create a timeout event, which doesn't have any readiness set. epoll
+    /// never returns such events.
+    #[cfg(feature = "timers")]
+    pub fn timeout(token: Token) -> Event {
+        Event {
+            events: 0,
+            u64: token.0 as u64,
+        }
+    }
+
+    #[cfg(feature = "timers")]
+    pub fn is_timeout(event: &Event) -> bool {
+        event.events == 0
+    }
 }
 
 #[test]
@@ -1,4 +1,5 @@
-use crate::{Interests, Token};
+use super::not_registered;
+use crate::{Interests, Token, Trigger};
 
 use log::error;
 use std::mem::MaybeUninit;
@@ -27,6 +28,12 @@ type Filter = i16;
 #[cfg(target_os = "netbsd")]
 type Filter = u32;
 
+// Type of the `flags` field in the `kevent` structure.
+#[cfg(not(target_os = "netbsd"))]
+type Flags = u16;
+#[cfg(target_os = "netbsd")]
+type Flags = u32;
+
 // Type of the `data` field in the `kevent` structure.
 #[cfg(any(
     target_os = "dragonfly",
@@ -89,7 +96,38 @@ impl Selector {
         })
     }
 
+    /// Returns `false` if the file descriptor is closed, e.g. in the child
+    /// process after `fork` for selectors that aren't inherited.
+    pub fn is_open(&self) -> bool {
+        syscall!(fcntl(self.kq, libc::F_GETFD)).is_ok()
+    }
+
+    pub fn clear(&self) -> io::Result<()> {
+        let kq = syscall!(kqueue())?;
+        // Replace the kqueue referred to by `self.kq` with the new, empty
+        // one, this closes the old kqueue if it's not shared. `dup2` doesn't
+        // copy the close-on-exec flag, so it's set again.
+        let res = syscall!(dup2(kq, self.kq))
+            .and_then(|_| syscall!(fcntl(self.kq, libc::F_SETFD, libc::FD_CLOEXEC)))
+            .map(|_| ());
+        if let Err(err) = syscall!(close(kq)) {
+            error!("error closing kqueue: {}", err);
+        }
+        res
+    }
+
     pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        events.clear();
+        self.select_append(events, timeout)
+    }
+
+    pub fn select_append(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        let len = events.len();
+        let remaining = events.capacity() - len;
+        if remaining == 0 {
+            return Ok(());
+        }
+
         let timeout = timeout.map(|to| libc::timespec {
             tv_sec: cmp::min(to.as_secs(), libc::time_t::max_value() as u64) as libc::time_t,
             // `Duration::subsec_nanos` is guaranteed to be less than one
@@ -103,24 +141,29 @@ impl Selector {
             .map(|s| s as *const _)
             .unwrap_or(ptr::null_mut());
 
-        events.clear();
         syscall!(kevent(
             self.kq,
             ptr::null(),
             0,
-            events.as_mut_ptr(),
-            events.capacity() as Count,
+            events.as_mut_ptr().wrapping_add(len),
+            remaining as Count,
             timeout,
         ))
         .map(|n_events| {
             // This is safe because `kevent` ensures that `n_events` are
             // assigned.
-            unsafe { events.set_len(n_events as usize) };
+            unsafe { events.set_len(len + n_events as usize) };
         })
     }
 
-    pub fn register(&self, fd: RawFd, token: Token, interests: Interests) -> io::Result<()> {
-        let flags = libc::EV_CLEAR | libc::EV_RECEIPT | libc::EV_ADD;
+    pub fn register(
+        &self,
+        fd: RawFd,
+        token: Token,
+        interests: Interests,
+        trigger: Trigger,
+    ) -> io::Result<()> {
+        let flags = trigger_flags(trigger) | libc::EV_RECEIPT | libc::EV_ADD;
         // At most we need two changes, but maybe we only need 1.
         let mut changes: [MaybeUninit<libc::kevent>; 2] =
             [MaybeUninit::uninit(), MaybeUninit::uninit()];
@@ -158,8 +201,14 @@ impl Selector {
         kevent_register(self.kq, changes, &[libc::EPIPE as Data])
     }
 
-    pub fn reregister(&self, fd: RawFd, token: Token, interests: Interests) -> io::Result<()> {
-        let flags = libc::EV_CLEAR | libc::EV_RECEIPT;
+    pub fn reregister(
+        &self,
+        fd: RawFd,
+        token: Token,
+        interests: Interests,
+        trigger: Trigger,
+    ) -> io::Result<()> {
+        let flags = trigger_flags(trigger) | libc::EV_RECEIPT;
         let write_flags = if interests.is_writable() {
             flags | libc::EV_ADD
         } else {
@@ -171,7 +220,13 @@ impl Selector {
             flags | libc::EV_DELETE
         };
 
-        let mut changes: [libc::kevent; 2] = [
+        let enable_flags = libc::EV_ENABLE | libc::EV_RECEIPT;
+        let mut changes: [libc::kevent; 4] = [
+            // Enabling a filter that doesn't exist fails with ENOENT, which we
+            // use to check if the fd was registered at all, see below. This
+            // also rearms filters disabled by `EV_DISPATCH`.
+            kevent!(fd, libc::EVFILT_WRITE, enable_flags, token.0),
+            kevent!(fd, libc::EVFILT_READ, enable_flags, token.0),
             kevent!(fd, libc::EVFILT_WRITE, write_flags, token.0),
             kevent!(fd, libc::EVFILT_READ, read_flags, token.0),
         ];
@@ -188,7 +243,17 @@ impl Selector {
             self.kq,
             &mut changes,
             &[libc::ENOENT as Data, libc::EPIPE as Data],
-        )
+        )?;
+
+        // If neither filter existed the fd wasn't registered, in which case
+        // the changes above registered it. Undo that and return an error, to
+        // match the behaviour of epoll.
+        if is_not_found(&changes[0]) && is_not_found(&changes[1]) {
+            // Only the original error is returned.
+            let _ = self.deregister(fd);
+            return Err(not_registered());
+        }
+        Ok(())
     }
 
     pub fn deregister(&self, fd: RawFd) -> io::Result<()> {
@@ -247,6 +312,17 @@ impl Selector {
     }
 }
 
+/// Returns the flags for the trigger mode `trigger`, `EV_CLEAR` makes the
+/// filter edge-triggered and `EV_DISPATCH` disables the filter after it
+/// returned an event.
+fn trigger_flags(trigger: Trigger) -> Flags {
+    match trigger {
+        Trigger::Edge => libc::EV_CLEAR,
+        Trigger::Level => 0,
+        Trigger::Oneshot => libc::EV_DISPATCH,
+    }
+}
+
 /// Register `changes` with `kq`ueue.
 fn kevent_register(
     kq: RawFd,
@@ -275,6 +351,14 @@ fn kevent_register(
     .and_then(|()| check_errors(&changes, ignored_errors))
 }
 
+/// Returns `true` if the receipt `event` contains the ENOENT error.
+fn is_not_found(event: &libc::kevent) -> bool {
+    // Copy the data out of the packed structure before use, see
+    // `check_errors`.
+    let data = event.data;
+    (event.flags & libc::EV_ERROR != 0) && data == libc::ENOENT as Data
+}
+
 /// Check all events for possible errors, it returns the first error found.
 fn check_errors(events: &[libc::kevent], ignored_errors: &[Data]) -> io::Result<()> {
     for event in events {
@@ -370,6 +454,12 @@ pub mod event {
             (event.flags & libc::EV_EOF) != 0 && event.fflags != 0
     }
 
+    pub fn is_hup(_: &Event) -> bool {
+        // kqueue doesn't distinguish hang up from a closed read or write half,
+        // both set `EV_EOF`.
+        false
+    }
+
     pub fn is_read_closed(event: &Event) -> bool {
         event.filter == libc::EVFILT_READ && event.flags & libc::EV_EOF != 0
     }
@@ -416,6 +506,24 @@ pub mod event {
             false
         }
     }
+
+    #[cfg(feature = "os-ext")]
+    pub fn raw_ready(event: &Event) -> u32 {
+        event.flags as u32
+    }
+
+    /// Create a timeout event, using the `EVFILT_TIMER` filter which is
+    /// otherwise unused.
+    #[cfg(feature = "timers")]
+    pub fn timeout(token: Token) -> Event {
+        use super::{Filter, UData};
+        kevent!(0, libc::EVFILT_TIMER, 0, token.0)
+    }
+
+    #[cfg(feature = "timers")]
+    pub fn is_timeout(event: &Event) -> bool {
+        event.filter == libc::EVFILT_TIMER
+    }
 }
 
 #[test]
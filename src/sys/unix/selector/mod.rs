@@ -1,3 +1,23 @@
+use std::io;
+
+/// Error returned when reregistering a file descriptor that isn't registered.
+fn not_registered() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        "attempted to reregister an event source that is not registered",
+    )
+}
+
+/// Returns `true` if `err`, returned by polling, means the selector is no
+/// longer usable.
+pub fn is_fatal_poll_error(err: &io::Error) -> bool {
+    match err.raw_os_error() {
+        // The epoll/kqueue file descriptor is invalid or was closed.
+        Some(libc::EBADF) | Some(libc::EINVAL) | Some(libc::EFAULT) => true,
+        _ => false,
+    }
+}
+
 #[cfg(any(target_os = "linux", target_os = "android", target_os = "solaris"))]
 mod epoll;
 
@@ -0,0 +1,190 @@
+use crate::sys::unix::SourceFd;
+use crate::{event, Interests, Registry, Token};
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::mem::{self, MaybeUninit};
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::{fmt, slice};
+
+/// Signal handling backed by Linux's [`signalfd`].
+///
+/// Once one of the signals is pending the `SignalFd` becomes readable, after
+/// which [`read_signal`] returns the information about the received signal.
+/// This allows signals to be handled in the event loop, rather than in a
+/// signal handler which may only call async-signal-safe functions.
+///
+/// [`signalfd`]: http://man7.org/linux/man-pages/man2/signalfd.2.html
+/// [`read_signal`]: SignalFd::read_signal
+///
+/// # Notes
+///
+/// The signals must be blocked, e.g. using `pthread_sigmask`, otherwise they
+/// are handled according to their default disposition (which usually
+/// terminates the process) rather than by the `SignalFd`. Blocking the
+/// signals is the responsibility of the caller and must be done in every
+/// thread, for example by blocking them in the main thread before spawning
+/// any other threads.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::unix::SignalFd;
+/// use mio::{Events, Interests, Poll, Token};
+/// use std::{mem, ptr};
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// // Block the signal so it's delivered to the `SignalFd`.
+/// unsafe {
+///     let mut mask = mem::zeroed();
+///     libc::sigemptyset(&mut mask);
+///     libc::sigaddset(&mut mask, libc::SIGUSR1);
+///     libc::pthread_sigmask(libc::SIG_BLOCK, &mask, ptr::null_mut());
+/// }
+///
+/// let signals = SignalFd::new(&[libc::SIGUSR1])?;
+/// poll.registry().register(&signals, Token(0), Interests::READABLE)?;
+///
+/// unsafe { libc::raise(libc::SIGUSR1) };
+///
+/// loop {
+///     poll.poll(&mut events, None)?;
+///     for event in &events {
+///         if event.token() == Token(0) {
+///             let info = signals.read_signal()?.unwrap();
+///             assert_eq!(info.signal(), libc::SIGUSR1);
+///             return Ok(());
+///         }
+///     }
+/// }
+/// # }
+/// ```
+pub struct SignalFd {
+    fd: File,
+}
+
+/// Information about a signal received by [`SignalFd`].
+#[derive(Copy, Clone)]
+pub struct SignalInfo {
+    info: libc::signalfd_siginfo,
+}
+
+impl SignalFd {
+    /// Create a new `SignalFd` that receives `signals`.
+    ///
+    /// Returns an error if any of the signals is invalid.
+    pub fn new(signals: &[libc::c_int]) -> io::Result<SignalFd> {
+        let mut mask = MaybeUninit::<libc::sigset_t>::uninit();
+        syscall!(sigemptyset(mask.as_mut_ptr()))?;
+        for signal in signals {
+            syscall!(sigaddset(mask.as_mut_ptr(), *signal))?;
+        }
+        // This is safe because `sigemptyset` initialised the set.
+        let mask = unsafe { mask.assume_init() };
+
+        let flags = libc::SFD_NONBLOCK | libc::SFD_CLOEXEC;
+        syscall!(signalfd(-1, &mask, flags)).map(|fd| SignalFd {
+            fd: unsafe { File::from_raw_fd(fd) },
+        })
+    }
+
+    /// Read a received signal.
+    ///
+    /// Returns `None` if none of the signals is pending.
+    pub fn read_signal(&self) -> io::Result<Option<SignalInfo>> {
+        let mut info = MaybeUninit::<libc::signalfd_siginfo>::zeroed();
+        let size = mem::size_of::<libc::signalfd_siginfo>();
+        // This is safe because the zeroed `info` is valid for `size` bytes.
+        let buf = unsafe { slice::from_raw_parts_mut(info.as_mut_ptr() as *mut u8, size) };
+        match (&self.fd).read(buf) {
+            // The kernel always returns complete structures.
+            Ok(n) if n == size => Ok(Some(SignalInfo {
+                info: unsafe { info.assume_init() },
+            })),
+            Ok(_) => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "incomplete read of signal information",
+            )),
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl SignalInfo {
+    /// Returns the number of the received signal, e.g. `libc::SIGTERM`.
+    pub fn signal(&self) -> libc::c_int {
+        self.info.ssi_signo as libc::c_int
+    }
+
+    /// Returns the process id of the sender of the signal.
+    pub fn pid(&self) -> libc::pid_t {
+        self.info.ssi_pid as libc::pid_t
+    }
+
+    /// Returns the real user id of the sender of the signal.
+    pub fn uid(&self) -> libc::uid_t {
+        self.info.ssi_uid as libc::uid_t
+    }
+}
+
+impl fmt::Debug for SignalInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignalInfo")
+            .field("signal", &self.signal())
+            .field("pid", &self.pid())
+            .field("uid", &self.uid())
+            .finish()
+    }
+}
+
+impl event::Source for SignalFd {
+    fn register(&self, registry: &Registry, token: Token, interests: Interests) -> io::Result<()> {
+        SourceFd(&self.as_raw_fd()).register(registry, token, interests)
+    }
+
+    fn reregister(
+        &self,
+        registry: &Registry,
+        token: Token,
+        interests: Interests,
+    ) -> io::Result<()> {
+        SourceFd(&self.as_raw_fd()).reregister(registry, token, interests)
+    }
+
+    fn deregister(&self, registry: &Registry) -> io::Result<()> {
+        SourceFd(&self.as_raw_fd()).deregister(registry)
+    }
+}
+
+impl fmt::Debug for SignalFd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignalFd")
+            .field("fd", &self.as_raw_fd())
+            .finish()
+    }
+}
+
+impl FromRawFd for SignalFd {
+    unsafe fn from_raw_fd(fd: RawFd) -> SignalFd {
+        SignalFd {
+            fd: File::from_raw_fd(fd),
+        }
+    }
+}
+
+impl AsRawFd for SignalFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for SignalFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.fd.into_raw_fd()
+    }
+}
@@ -7,9 +7,10 @@ use std::os::unix::io::RawFd;
 ///
 /// `SourceFd` enables registering any type with an FD with [`Poll`].
 ///
-/// While only implementations for TCP and UDP are provided, Mio supports
-/// registering any FD that can be registered with the underlying OS selector.
-/// `SourceFd` provides the necessary bridge.
+/// While Mio only provides implementations for its own types, it supports
+/// registering any FD that can be registered with the underlying OS selector,
+/// e.g. a `signalfd`, an `inotify` instance or a file descriptor handed out by
+/// a third-party library. `SourceFd` provides the necessary bridge.
 ///
 /// Note that `SourceFd` takes a `&RawFd`. This is because `SourceFd` **does
 /// not** take ownership of the FD. Specifically, it will not manage any
@@ -85,7 +86,7 @@ pub struct SourceFd<'a>(pub &'a RawFd);
 
 impl<'a> event::Source for SourceFd<'a> {
     fn register(&self, registry: &Registry, token: Token, interests: Interests) -> io::Result<()> {
-        poll::selector(registry).register(*self.0, token, interests)
+        poll::selector(registry).register(*self.0, token, interests, poll::trigger(registry))
     }
 
     fn reregister(
@@ -94,7 +95,7 @@ impl<'a> event::Source for SourceFd<'a> {
         token: Token,
         interests: Interests,
     ) -> io::Result<()> {
-        poll::selector(registry).reregister(*self.0, token, interests)
+        poll::selector(registry).reregister(*self.0, token, interests, poll::trigger(registry))
     }
 
     fn deregister(&self, registry: &Registry) -> io::Result<()> {
@@ -1,10 +1,16 @@
-use crate::sys::unix::net::{new_ip_socket, socket_addr};
+use crate::net::TcpListenerOpts;
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+use crate::sys::unix::net::set_nosigpipe;
+use crate::sys::unix::net::{
+    new_ip_socket, set_only_v6, set_reuse_addr, set_reuse_port, socket_addr, to_socket_addr,
+};
 use crate::sys::unix::{SourceFd, TcpStream};
 use crate::{event, Interests, Registry, Token};
 
+use std::cmp;
 use std::fmt;
 use std::io;
-use std::mem::size_of;
+use std::mem;
 use std::net::{self, SocketAddr};
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 
@@ -13,30 +19,35 @@ pub struct TcpListener {
 }
 
 impl TcpListener {
-    pub fn bind(addr: SocketAddr) -> io::Result<TcpListener> {
+    pub fn bind(addr: SocketAddr, opts: &TcpListenerOpts) -> io::Result<TcpListener> {
         new_ip_socket(addr, libc::SOCK_STREAM).and_then(|socket| {
             // Set SO_REUSEADDR (mirrors what libstd does).
-            syscall!(setsockopt(
-                socket,
-                libc::SOL_SOCKET,
-                libc::SO_REUSEADDR,
-                &1 as *const libc::c_int as *const libc::c_void,
-                size_of::<libc::c_int>() as libc::socklen_t,
-            ))
-            .and_then(|_| {
-                let (raw_addr, raw_addr_length) = socket_addr(&addr);
-                syscall!(bind(socket, raw_addr, raw_addr_length))
-            })
-            .and_then(|_| syscall!(listen(socket, 1024)))
-            .map_err(|err| {
-                // Close the socket if we hit an error, ignoring the error
-                // from closing since we can't pass back two errors.
-                let _ = unsafe { libc::close(socket) };
-                err
-            })
-            .map(|_| TcpListener {
-                inner: unsafe { net::TcpListener::from_raw_fd(socket) },
-            })
+            set_reuse_addr(socket, true)
+                .and_then(|_| {
+                    if opts.reuse_port {
+                        set_reuse_port(socket, true)
+                    } else {
+                        Ok(())
+                    }
+                })
+                .and_then(|_| set_only_v6(socket, addr, opts.only_v6))
+                .and_then(|_| {
+                    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+                    syscall!(bind(socket, raw_addr, raw_addr_length))
+                })
+                .and_then(|_| {
+                    let backlog = cmp::min(opts.backlog, libc::c_int::max_value() as u32);
+                    syscall!(listen(socket, backlog as libc::c_int))
+                })
+                .map_err(|err| {
+                    // Close the socket if we hit an error, ignoring the error
+                    // from closing since we can't pass back two errors.
+                    let _ = unsafe { libc::close(socket) };
+                    err
+                })
+                .map(|_| TcpListener {
+                    inner: unsafe { net::TcpListener::from_raw_fd(socket) },
+                })
         })
     }
 
@@ -54,12 +65,74 @@ impl TcpListener {
 
     pub fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
         self.inner.accept().and_then(|(inner, addr)| {
+            // Darwin doesn't have `MSG_NOSIGNAL`, see `net::send`.
+            #[cfg(any(target_os = "ios", target_os = "macos"))]
+            set_nosigpipe(inner.as_raw_fd())?;
             inner
                 .set_nonblocking(true)
                 .map(|()| (TcpStream::new(inner), addr))
         })
     }
 
+    pub fn accept_with_flags(
+        &self,
+        cloexec: bool,
+        nonblocking: bool,
+    ) -> io::Result<(TcpStream, SocketAddr)> {
+        let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+        let mut len = mem::size_of_val(&storage) as libc::socklen_t;
+        let storage_ptr = &mut storage as *mut libc::sockaddr_storage as *mut libc::sockaddr;
+
+        #[cfg(not(any(
+            target_os = "ios",
+            target_os = "macos",
+            target_os = "netbsd",
+            target_os = "solaris"
+        )))]
+        let stream = {
+            let mut flags = 0;
+            if cloexec {
+                flags |= libc::SOCK_CLOEXEC;
+            }
+            if nonblocking {
+                flags |= libc::SOCK_NONBLOCK;
+            }
+            syscall!(accept4(
+                self.inner.as_raw_fd(),
+                storage_ptr,
+                &mut len,
+                flags
+            ))
+            .map(|socket| unsafe { net::TcpStream::from_raw_fd(socket) })?
+        };
+
+        #[cfg(any(
+            target_os = "ios",
+            target_os = "macos",
+            target_os = "netbsd",
+            target_os = "solaris"
+        ))]
+        let stream = {
+            let stream = syscall!(accept(self.inner.as_raw_fd(), storage_ptr, &mut len))
+                .map(|socket| unsafe { net::TcpStream::from_raw_fd(socket) })?;
+            // Some platforms copy `O_NONBLOCK` from the listener, so always
+            // set the mode explicitly.
+            stream.set_nonblocking(nonblocking)?;
+            if cloexec {
+                syscall!(fcntl(stream.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC))?;
+            }
+            stream
+        };
+
+        // Darwin doesn't have `MSG_NOSIGNAL`, see `net::send`.
+        #[cfg(any(target_os = "ios", target_os = "macos"))]
+        set_nosigpipe(stream.as_raw_fd())?;
+
+        // This is safe because `accept` initialised the address.
+        let addr = unsafe { to_socket_addr(&storage)? };
+        Ok((TcpStream::new(stream), addr))
+    }
+
     pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
         self.inner.set_ttl(ttl)
     }
@@ -73,6 +146,29 @@ impl TcpListener {
     }
 }
 
+pub fn is_transient_accept_error(err: &io::Error) -> bool {
+    match err.raw_os_error() {
+        Some(libc::ECONNABORTED)
+        | Some(libc::EINTR)
+        | Some(libc::EMFILE)
+        | Some(libc::ENFILE)
+        | Some(libc::ENOBUFS)
+        | Some(libc::ENOMEM)
+        | Some(libc::EPROTO) => true,
+        // Linux passes already-pending network errors of the new connection
+        // on to `accept`, see accept(2).
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        Some(libc::ENETDOWN)
+        | Some(libc::ENOPROTOOPT)
+        | Some(libc::EHOSTDOWN)
+        | Some(libc::ENONET)
+        | Some(libc::EHOSTUNREACH)
+        | Some(libc::EOPNOTSUPP)
+        | Some(libc::ENETUNREACH) => true,
+        _ => false,
+    }
+}
+
 impl event::Source for TcpListener {
     fn register(&self, registry: &Registry, token: Token, interests: Interests) -> io::Result<()> {
         SourceFd(&self.as_raw_fd()).register(registry, token, interests)
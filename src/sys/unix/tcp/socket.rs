@@ -0,0 +1,87 @@
+use crate::sys::unix::net::{new_socket, set_reuse_addr, set_reuse_port, socket_addr};
+use crate::sys::unix::{TcpListener, TcpStream};
+
+use std::io;
+use std::mem;
+use std::net::{self, SocketAddr};
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::{cmp, fmt};
+
+pub struct TcpSocket {
+    fd: RawFd,
+}
+
+impl TcpSocket {
+    pub fn new_v4() -> io::Result<TcpSocket> {
+        new_socket(libc::AF_INET, libc::SOCK_STREAM).map(|fd| TcpSocket { fd })
+    }
+
+    pub fn new_v6() -> io::Result<TcpSocket> {
+        new_socket(libc::AF_INET6, libc::SOCK_STREAM).map(|fd| TcpSocket { fd })
+    }
+
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        set_reuse_addr(self.fd, reuseaddr)
+    }
+
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        set_reuse_port(self.fd, reuseport)
+    }
+
+    pub fn bind(&self, addr: SocketAddr) -> io::Result<()> {
+        let (raw_addr, raw_addr_length) = socket_addr(&addr);
+        syscall!(bind(self.fd, raw_addr, raw_addr_length)).map(|_| ())
+    }
+
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        let backlog = cmp::min(backlog, libc::c_int::max_value() as u32);
+        syscall!(listen(self.fd, backlog as libc::c_int))?;
+        let listener = unsafe { net::TcpListener::from_raw_fd(self.into_raw_fd()) };
+        Ok(TcpListener::from_std(listener))
+    }
+
+    pub fn connect(self, addr: SocketAddr) -> io::Result<TcpStream> {
+        let (raw_addr, raw_addr_length) = socket_addr(&addr);
+        if let Err(err) = syscall!(connect(self.fd, raw_addr, raw_addr_length)) {
+            // Connect hasn't finished, but that is fine.
+            if err.raw_os_error() != Some(libc::EINPROGRESS) {
+                return Err(err);
+            }
+        }
+        let stream = unsafe { net::TcpStream::from_raw_fd(self.into_raw_fd()) };
+        TcpStream::from_std(stream)
+    }
+}
+
+impl Drop for TcpSocket {
+    fn drop(&mut self) {
+        let _ = syscall!(close(self.fd));
+    }
+}
+
+impl fmt::Debug for TcpSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TcpSocket").field("fd", &self.fd).finish()
+    }
+}
+
+impl AsRawFd for TcpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl FromRawFd for TcpSocket {
+    unsafe fn from_raw_fd(fd: RawFd) -> TcpSocket {
+        TcpSocket { fd }
+    }
+}
+
+impl IntoRawFd for TcpSocket {
+    fn into_raw_fd(self) -> RawFd {
+        let fd = self.fd;
+        // Don't close the socket.
+        mem::forget(self);
+        fd
+    }
+}
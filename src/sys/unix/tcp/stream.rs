@@ -1,11 +1,20 @@
-use crate::sys::unix::net::{new_ip_socket, socket_addr};
+use crate::net::TcpKeepalive;
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+use crate::sys::unix::net::set_nosigpipe;
+use crate::sys::unix::net::{
+    getsockopt, new_ip_socket, send, send_vectored, set_socket_buffer_size, set_socket_ttl,
+    setsockopt, socket_addr, socket_buffer_size, socket_ttl,
+};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::sys::unix::net::{set_socket_mark, socket_mark};
 use crate::sys::unix::SourceFd;
 use crate::{event, Interests, Registry, Token};
 
-use std::fmt;
 use std::io::{self, IoSlice, IoSliceMut, Read, Write};
 use std::net::{self, SocketAddr};
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::time::Duration;
+use std::{cmp, fmt, mem};
 
 pub struct TcpStream {
     inner: net::TcpStream,
@@ -39,8 +48,15 @@ impl TcpStream {
             })
     }
 
-    pub fn from_std(inner: net::TcpStream) -> TcpStream {
-        TcpStream { inner }
+    pub fn from_std(inner: net::TcpStream) -> io::Result<TcpStream> {
+        // Darwin doesn't have `MSG_NOSIGNAL`, see `net::send`.
+        #[cfg(any(target_os = "ios", target_os = "macos"))]
+        set_nosigpipe(inner.as_raw_fd())?;
+        Ok(TcpStream { inner })
+    }
+
+    pub fn into_std(self) -> net::TcpStream {
+        self.inner
     }
 
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
@@ -67,12 +83,131 @@ impl TcpStream {
         self.inner.nodelay()
     }
 
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_quickack(&self, quickack: bool) -> io::Result<()> {
+        let quickack = quickack as libc::c_int;
+        setsockopt(
+            self.inner.as_raw_fd(),
+            libc::IPPROTO_TCP,
+            libc::TCP_QUICKACK,
+            quickack,
+        )
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    pub fn set_quickack(&self, _: bool) -> io::Result<()> {
+        Err(quickack_unsupported())
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn quickack(&self) -> io::Result<bool> {
+        getsockopt::<libc::c_int>(
+            self.inner.as_raw_fd(),
+            libc::IPPROTO_TCP,
+            libc::TCP_QUICKACK,
+        )
+        .map(|quickack| quickack != 0)
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    pub fn quickack(&self) -> io::Result<bool> {
+        Err(quickack_unsupported())
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_mark(&self, mark: u32) -> io::Result<()> {
+        set_socket_mark(self.inner.as_raw_fd(), mark)
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn mark(&self) -> io::Result<u32> {
+        socket_mark(self.inner.as_raw_fd())
+    }
+
     pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
-        self.inner.set_ttl(ttl)
+        set_socket_ttl(self.inner.as_raw_fd(), self.inner.local_addr()?, ttl)
     }
 
     pub fn ttl(&self) -> io::Result<u32> {
-        self.inner.ttl()
+        socket_ttl(self.inner.as_raw_fd(), self.inner.local_addr()?)
+    }
+
+    pub fn set_linger(&self, dur: Option<Duration>) -> io::Result<()> {
+        let linger = libc::linger {
+            l_onoff: dur.is_some() as libc::c_int,
+            l_linger: dur.map(duration_secs).unwrap_or(0),
+        };
+        setsockopt(self.inner.as_raw_fd(), libc::SOL_SOCKET, SO_LINGER, linger)
+    }
+
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        getsockopt::<libc::linger>(self.inner.as_raw_fd(), libc::SOL_SOCKET, SO_LINGER).map(
+            |linger| {
+                if linger.l_onoff != 0 {
+                    Some(Duration::from_secs(linger.l_linger as u64))
+                } else {
+                    None
+                }
+            },
+        )
+    }
+
+    pub fn set_keepalive(&self, keepalive: Option<TcpKeepalive>) -> io::Result<()> {
+        let fd = self.inner.as_raw_fd();
+        let enabled = keepalive.is_some() as libc::c_int;
+        setsockopt(fd, libc::SOL_SOCKET, libc::SO_KEEPALIVE, enabled)?;
+        if let Some(keepalive) = keepalive {
+            if let (Some(name), Some(time)) = (KEEPALIVE_TIME, keepalive.time()) {
+                setsockopt(fd, libc::IPPROTO_TCP, name, duration_secs(time))?;
+            }
+            if let (Some(name), Some(interval)) = (KEEPALIVE_INTERVAL, keepalive.interval()) {
+                setsockopt(fd, libc::IPPROTO_TCP, name, duration_secs(interval))?;
+            }
+            if let (Some(name), Some(retries)) = (KEEPALIVE_RETRIES, keepalive.retries()) {
+                let retries = cmp::min(retries, libc::c_int::max_value() as u32);
+                setsockopt(fd, libc::IPPROTO_TCP, name, retries as libc::c_int)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn keepalive(&self) -> io::Result<Option<TcpKeepalive>> {
+        let fd = self.inner.as_raw_fd();
+        let enabled: libc::c_int = getsockopt(fd, libc::SOL_SOCKET, libc::SO_KEEPALIVE)?;
+        if enabled == 0 {
+            return Ok(None);
+        }
+
+        let mut keepalive = TcpKeepalive::new();
+        if let Some(name) = KEEPALIVE_TIME {
+            let secs: libc::c_int = getsockopt(fd, libc::IPPROTO_TCP, name)?;
+            keepalive = keepalive.with_time(Duration::from_secs(secs as u64));
+        }
+        if let Some(name) = KEEPALIVE_INTERVAL {
+            let secs: libc::c_int = getsockopt(fd, libc::IPPROTO_TCP, name)?;
+            keepalive = keepalive.with_interval(Duration::from_secs(secs as u64));
+        }
+        if let Some(name) = KEEPALIVE_RETRIES {
+            let retries: libc::c_int = getsockopt(fd, libc::IPPROTO_TCP, name)?;
+            keepalive = keepalive.with_retries(retries as u32);
+        }
+        Ok(Some(keepalive))
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_socket_buffer_size(self.inner.as_raw_fd(), libc::SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        socket_buffer_size(self.inner.as_raw_fd(), libc::SO_RCVBUF)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_socket_buffer_size(self.inner.as_raw_fd(), libc::SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        socket_buffer_size(self.inner.as_raw_fd(), libc::SO_SNDBUF)
     }
 
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
@@ -82,6 +217,89 @@ impl TcpStream {
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.peek(buf)
     }
+
+    pub fn peek_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+        // `IoSliceMut` is guaranteed to be ABI compatible with `iovec`.
+        msg.msg_iov = bufs.as_mut_ptr() as *mut libc::iovec;
+        msg.msg_iovlen = cmp::min(bufs.len(), libc::c_int::max_value() as usize) as _;
+        syscall!(recvmsg(self.inner.as_raw_fd(), &mut msg, libc::MSG_PEEK)).map(|n| n as usize)
+    }
+
+    pub fn read_blocking(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.set_nonblocking(false)?;
+        let result = (&self.inner).read(buf);
+        // Always attempt to restore non-blocking mode, even if reading failed.
+        self.inner.set_nonblocking(true).and(result)
+    }
+}
+
+// On Apple platforms `SO_LINGER` is in clock ticks, `SO_LINGER_SEC` is in
+// seconds like on the other platforms.
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+const SO_LINGER: libc::c_int = libc::SO_LINGER_SEC;
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+const SO_LINGER: libc::c_int = libc::SO_LINGER;
+
+#[cfg(not(any(target_os = "android", target_os = "linux")))]
+fn quickack_unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Other,
+        "`TCP_QUICKACK` is not supported on this platform",
+    )
+}
+
+/// Socket options (at the `IPPROTO_TCP` level) used for the parameters of
+/// `TcpKeepalive`, `None` if the parameter is not supported on the platform.
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd"
+))]
+const KEEPALIVE_TIME: Option<libc::c_int> = Some(libc::TCP_KEEPIDLE);
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+const KEEPALIVE_TIME: Option<libc::c_int> = Some(libc::TCP_KEEPALIVE);
+#[cfg(any(target_os = "openbsd", target_os = "solaris"))]
+const KEEPALIVE_TIME: Option<libc::c_int> = None;
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd"
+))]
+const KEEPALIVE_INTERVAL: Option<libc::c_int> = Some(libc::TCP_KEEPINTVL);
+#[cfg(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "openbsd",
+    target_os = "solaris"
+))]
+const KEEPALIVE_INTERVAL: Option<libc::c_int> = None;
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd"
+))]
+const KEEPALIVE_RETRIES: Option<libc::c_int> = Some(libc::TCP_KEEPCNT);
+#[cfg(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "openbsd",
+    target_os = "solaris"
+))]
+const KEEPALIVE_RETRIES: Option<libc::c_int> = None;
+
+/// Returns `dur` in whole seconds, rounding up any sub-second part.
+fn duration_secs(dur: Duration) -> libc::c_int {
+    let secs = dur.as_secs() + if dur.subsec_nanos() > 0 { 1 } else { 0 };
+    cmp::min(secs, libc::c_int::max_value() as u64) as libc::c_int
 }
 
 impl<'a> Read for &'a TcpStream {
@@ -96,11 +314,11 @@ impl<'a> Read for &'a TcpStream {
 
 impl<'a> Write for &'a TcpStream {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        (&self.inner).write(buf)
+        send(self.inner.as_raw_fd(), buf)
     }
 
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
-        (&self.inner).write_vectored(bufs)
+        send_vectored(self.inner.as_raw_fd(), bufs)
     }
 
     fn flush(&mut self) -> io::Result<()> {
@@ -0,0 +1,144 @@
+use crate::sys::unix::SourceFd;
+use crate::{event, Interests, Registry, Token};
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::time::Duration;
+use std::{cmp, fmt, ptr};
+
+/// Timer backed by Linux's [`timerfd`].
+///
+/// The timer uses the monotonic clock (`CLOCK_MONOTONIC`). Once the timer
+/// expires the `TimerFd` becomes readable, after which [`read`] returns the
+/// number of expirations since the last read.
+///
+/// [`timerfd`]: http://man7.org/linux/man-pages/man2/timerfd_create.2.html
+/// [`read`]: TimerFd::read
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use mio::unix::TimerFd;
+/// use mio::{Events, Interests, Poll, Token};
+/// use std::time::Duration;
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let timer = TimerFd::new()?;
+/// poll.registry().register(&timer, Token(0), Interests::READABLE)?;
+///
+/// // Expire once after 10 milliseconds.
+/// timer.set(Duration::from_millis(10), None)?;
+///
+/// loop {
+///     poll.poll(&mut events, None)?;
+///     for event in &events {
+///         if event.token() == Token(0) {
+///             assert_eq!(timer.read()?, 1);
+///             return Ok(());
+///         }
+///     }
+/// }
+/// # }
+/// ```
+pub struct TimerFd {
+    fd: File,
+}
+
+impl TimerFd {
+    /// Create a new disarmed timer.
+    pub fn new() -> io::Result<TimerFd> {
+        let flags = libc::TFD_NONBLOCK | libc::TFD_CLOEXEC;
+        syscall!(timerfd_create(libc::CLOCK_MONOTONIC, flags)).map(|fd| TimerFd {
+            fd: unsafe { File::from_raw_fd(fd) },
+        })
+    }
+
+    /// Arm the timer to first expire after `initial`, and after that every
+    /// `interval`, if any.
+    ///
+    /// Setting the timer replaces the previous setting. If `initial` is zero
+    /// the timer is disarmed, ignoring `interval`.
+    pub fn set(&self, initial: Duration, interval: Option<Duration>) -> io::Result<()> {
+        let new_value = libc::itimerspec {
+            it_interval: timespec(interval.unwrap_or_else(|| Duration::from_secs(0))),
+            it_value: timespec(initial),
+        };
+        syscall!(timerfd_settime(
+            self.fd.as_raw_fd(),
+            0,
+            &new_value,
+            ptr::null_mut()
+        ))
+        .map(|_| ())
+    }
+
+    /// Read the number of times the timer expired since it was last read, or
+    /// since it was set.
+    ///
+    /// If the timer hasn't expired yet this returns a `WouldBlock` error.
+    pub fn read(&self) -> io::Result<u64> {
+        let mut buf = [0; 8];
+        (&self.fd).read(&mut buf).map(|_| u64::from_ne_bytes(buf))
+    }
+}
+
+/// Converts `dur` into a `timespec`, saturating at the maximum number of
+/// seconds.
+fn timespec(dur: Duration) -> libc::timespec {
+    libc::timespec {
+        tv_sec: cmp::min(dur.as_secs(), libc::time_t::max_value() as u64) as libc::time_t,
+        tv_nsec: dur.subsec_nanos() as libc::c_long,
+    }
+}
+
+impl event::Source for TimerFd {
+    fn register(&self, registry: &Registry, token: Token, interests: Interests) -> io::Result<()> {
+        SourceFd(&self.as_raw_fd()).register(registry, token, interests)
+    }
+
+    fn reregister(
+        &self,
+        registry: &Registry,
+        token: Token,
+        interests: Interests,
+    ) -> io::Result<()> {
+        SourceFd(&self.as_raw_fd()).reregister(registry, token, interests)
+    }
+
+    fn deregister(&self, registry: &Registry) -> io::Result<()> {
+        SourceFd(&self.as_raw_fd()).deregister(registry)
+    }
+}
+
+impl fmt::Debug for TimerFd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimerFd")
+            .field("fd", &self.as_raw_fd())
+            .finish()
+    }
+}
+
+impl FromRawFd for TimerFd {
+    unsafe fn from_raw_fd(fd: RawFd) -> TimerFd {
+        TimerFd {
+            fd: File::from_raw_fd(fd),
+        }
+    }
+}
+
+impl AsRawFd for TimerFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for TimerFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.fd.into_raw_fd()
+    }
+}
@@ -1,17 +1,31 @@
-use crate::sys::unix::net::{new_ip_socket, socket_addr};
+use crate::net::UdpSocketOpts;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::net::{PktInfo, RecvFlags, RecvMsg, RecvResult, SendMsg};
+use crate::sys::unix::net::{
+    getsockopt, new_ip_socket, reuse_addr, set_only_v6, set_reuse_addr, set_reuse_port,
+    set_socket_buffer_size, set_socket_tos, set_socket_ttl, setsockopt, socket_addr,
+    socket_buffer_size, socket_tos, socket_ttl, to_socket_addr,
+};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use crate::sys::unix::net::{set_socket_mark, socket_mark};
 use crate::unix::SourceFd;
 use crate::{event, Interests, Registry, Token};
 
+use std::io::{IoSlice, IoSliceMut};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::net::IpAddr;
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
-use std::{fmt, io, net};
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use std::ptr;
+use std::{fmt, io, mem, net};
 
 pub struct UdpSocket {
     io: net::UdpSocket,
 }
 
 impl UdpSocket {
-    pub fn bind(addr: SocketAddr) -> io::Result<UdpSocket> {
+    pub fn bind(addr: SocketAddr, opts: &UdpSocketOpts) -> io::Result<UdpSocket> {
         // Gives a warning for non Apple platforms.
         #[allow(clippy::let_and_return)]
         let socket = new_ip_socket(addr, libc::SOCK_DGRAM);
@@ -30,8 +44,24 @@ impl UdpSocket {
         });
 
         socket.and_then(|socket| {
-            let (raw_addr, raw_addr_length) = socket_addr(&addr);
-            syscall!(bind(socket, raw_addr, raw_addr_length))
+            let result = if opts.reuse_addr {
+                set_reuse_addr(socket, true)
+            } else {
+                Ok(())
+            };
+            result
+                .and_then(|_| {
+                    if opts.reuse_port {
+                        set_reuse_port(socket, true)
+                    } else {
+                        Ok(())
+                    }
+                })
+                .and_then(|_| set_only_v6(socket, addr, opts.only_v6))
+                .and_then(|_| {
+                    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+                    syscall!(bind(socket, raw_addr, raw_addr_length))
+                })
                 .map_err(|err| {
                     // Close the socket if we hit an error, ignoring the error
                     // from closing since we can't pass back two errors.
@@ -68,6 +98,185 @@ impl UdpSocket {
         self.io.peek_from(buf)
     }
 
+    pub fn send_to_vectored(&self, bufs: &[IoSlice<'_>], target: SocketAddr) -> io::Result<usize> {
+        let (raw_addr, raw_addr_length) = socket_addr(&target);
+        let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+        msg.msg_name = raw_addr as *mut libc::c_void;
+        msg.msg_namelen = raw_addr_length;
+        // `IoSlice` is guaranteed to be ABI compatible with `iovec`.
+        msg.msg_iov = bufs.as_ptr() as *mut libc::iovec;
+        msg.msg_iovlen = bufs.len() as _;
+        syscall!(sendmsg(self.io.as_raw_fd(), &msg, 0)).map(|n| n as usize)
+    }
+
+    pub fn recv_from_vectored(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, SocketAddr)> {
+        let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+        let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+        msg.msg_name = &mut storage as *mut libc::sockaddr_storage as *mut libc::c_void;
+        msg.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+        // `IoSliceMut` is guaranteed to be ABI compatible with `iovec`.
+        msg.msg_iov = bufs.as_mut_ptr() as *mut libc::iovec;
+        msg.msg_iovlen = bufs.len() as _;
+        let n = syscall!(recvmsg(self.io.as_raw_fd(), &mut msg, 0))?;
+        // This is safe because `recvmsg` initialised the address.
+        let addr = unsafe { to_socket_addr(&storage)? };
+        Ok((n as usize, addr))
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_recv_pktinfo(&self, on: bool) -> io::Result<()> {
+        let fd = self.io.as_raw_fd();
+        let on = on as libc::c_int;
+        if self.io.local_addr()?.is_ipv6() {
+            setsockopt(fd, libc::IPPROTO_IPV6, libc::IPV6_RECVPKTINFO, on)?;
+        }
+        setsockopt(fd, libc::IPPROTO_IP, libc::IP_PKTINFO, on)
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_from_with_info(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr, PktInfo)> {
+        let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+        let mut iov = libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+            iov_len: buf.len(),
+        };
+        // Large enough for a single `in_pktinfo` or `in6_pktinfo` control
+        // message, using `u64` to align it for `cmsghdr`.
+        let mut control = [0u64; 8];
+        let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+        msg.msg_name = &mut storage as *mut libc::sockaddr_storage as *mut libc::c_void;
+        msg.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_controllen = mem::size_of_val(&control) as _;
+        let n = syscall!(recvmsg(self.io.as_raw_fd(), &mut msg, 0))?;
+        // This is safe because `recvmsg` initialised the address and the
+        // control messages.
+        let addr = unsafe { to_socket_addr(&storage)? };
+        let info = unsafe { pktinfo(&msg) }.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "no packet information received, see `UdpSocket::set_recv_pktinfo`",
+            )
+        })?;
+        Ok((n as usize, addr, info))
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_from_with_flags(
+        &self,
+        buf: &mut [u8],
+        flags: RecvFlags,
+    ) -> io::Result<(usize, SocketAddr, RecvResult)> {
+        let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+        let mut iov = libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+            iov_len: buf.len(),
+        };
+        let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+        msg.msg_name = &mut storage as *mut libc::sockaddr_storage as *mut libc::c_void;
+        msg.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        let mut raw_flags = 0;
+        if flags.is_peek() {
+            raw_flags |= libc::MSG_PEEK;
+        }
+        if flags.is_trunc() {
+            raw_flags |= libc::MSG_TRUNC;
+        }
+        // With `MSG_TRUNC` this returns the full length of the datagram, which
+        // can be larger than `buf`.
+        let n = syscall!(recvmsg(self.io.as_raw_fd(), &mut msg, raw_flags))? as usize;
+        // This is safe because `recvmsg` initialised the address.
+        let addr = unsafe { to_socket_addr(&storage)? };
+        let truncated = msg.msg_flags & libc::MSG_TRUNC != 0;
+        let result = RecvResult {
+            truncated,
+            datagram_len: if truncated && !flags.is_trunc() {
+                None
+            } else {
+                Some(n)
+            },
+        };
+        Ok((n.min(buf.len()), addr, result))
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn recv_mmsg(&self, msgs: &mut [RecvMsg<'_>]) -> io::Result<usize> {
+        let mut storages: Vec<libc::sockaddr_storage> = vec![unsafe { mem::zeroed() }; msgs.len()];
+        let mut iovecs: Vec<libc::iovec> = msgs
+            .iter_mut()
+            .map(|msg| libc::iovec {
+                iov_base: msg.buf.as_mut_ptr() as *mut libc::c_void,
+                iov_len: msg.buf.len(),
+            })
+            .collect();
+        let mut headers: Vec<libc::mmsghdr> = storages
+            .iter_mut()
+            .zip(iovecs.iter_mut())
+            .map(|(storage, iovec)| {
+                let mut header: libc::mmsghdr = unsafe { mem::zeroed() };
+                header.msg_hdr.msg_name = storage as *mut libc::sockaddr_storage as *mut _;
+                header.msg_hdr.msg_namelen =
+                    mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+                header.msg_hdr.msg_iov = iovec;
+                header.msg_hdr.msg_iovlen = 1;
+                header
+            })
+            .collect();
+
+        let n = syscall!(recvmmsg(
+            self.io.as_raw_fd(),
+            headers.as_mut_ptr(),
+            headers.len() as libc::c_uint,
+            0,
+            ptr::null_mut(),
+        ))? as usize;
+        for ((msg, header), storage) in msgs.iter_mut().zip(&headers).zip(&storages).take(n) {
+            msg.len = header.msg_len as usize;
+            // This is safe because `recvmmsg` initialised the address.
+            msg.addr = Some(unsafe { to_socket_addr(storage)? });
+        }
+        Ok(n)
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn send_mmsg(&self, msgs: &[SendMsg<'_>]) -> io::Result<usize> {
+        let mut iovecs: Vec<libc::iovec> = msgs
+            .iter()
+            .map(|msg| libc::iovec {
+                iov_base: msg.buf.as_ptr() as *mut libc::c_void,
+                iov_len: msg.buf.len(),
+            })
+            .collect();
+        let mut headers: Vec<libc::mmsghdr> = msgs
+            .iter()
+            .zip(iovecs.iter_mut())
+            .map(|(msg, iovec)| {
+                let (raw_addr, raw_addr_length) = socket_addr(&msg.addr);
+                let mut header: libc::mmsghdr = unsafe { mem::zeroed() };
+                header.msg_hdr.msg_name = raw_addr as *mut libc::c_void;
+                header.msg_hdr.msg_namelen = raw_addr_length;
+                header.msg_hdr.msg_iov = iovec;
+                header.msg_hdr.msg_iovlen = 1;
+                header
+            })
+            .collect();
+
+        syscall!(sendmmsg(
+            self.io.as_raw_fd(),
+            headers.as_mut_ptr(),
+            headers.len() as libc::c_uint,
+            0,
+        ))
+        .map(|n| n as usize)
+    }
+
     pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
         self.io.send(buf)
     }
@@ -116,12 +325,112 @@ impl UdpSocket {
         self.io.set_multicast_loop_v6(on)
     }
 
+    pub fn multicast_if_v4(&self) -> io::Result<Ipv4Addr> {
+        getsockopt::<libc::in_addr>(self.io.as_raw_fd(), libc::IPPROTO_IP, libc::IP_MULTICAST_IF)
+            .map(|addr| Ipv4Addr::from(addr.s_addr.to_ne_bytes()))
+    }
+
+    pub fn set_multicast_if_v4(&self, interface: Ipv4Addr) -> io::Result<()> {
+        let addr = libc::in_addr {
+            s_addr: u32::from_ne_bytes(interface.octets()),
+        };
+        setsockopt(
+            self.io.as_raw_fd(),
+            libc::IPPROTO_IP,
+            libc::IP_MULTICAST_IF,
+            addr,
+        )
+    }
+
+    pub fn multicast_if_v6(&self) -> io::Result<u32> {
+        getsockopt::<libc::c_uint>(
+            self.io.as_raw_fd(),
+            libc::IPPROTO_IPV6,
+            libc::IPV6_MULTICAST_IF,
+        )
+        .map(|index| index as u32)
+    }
+
+    pub fn set_multicast_if_v6(&self, index: u32) -> io::Result<()> {
+        setsockopt(
+            self.io.as_raw_fd(),
+            libc::IPPROTO_IPV6,
+            libc::IPV6_MULTICAST_IF,
+            index as libc::c_uint,
+        )
+    }
+
     pub fn ttl(&self) -> io::Result<u32> {
-        self.io.ttl()
+        socket_ttl(self.io.as_raw_fd(), self.io.local_addr()?)
     }
 
     pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
-        self.io.set_ttl(ttl)
+        set_socket_ttl(self.io.as_raw_fd(), self.io.local_addr()?, ttl)
+    }
+
+    pub fn tos(&self) -> io::Result<u32> {
+        socket_tos(self.io.as_raw_fd(), self.io.local_addr()?)
+    }
+
+    pub fn set_tos(&self, tos: u32) -> io::Result<()> {
+        set_socket_tos(self.io.as_raw_fd(), self.io.local_addr()?, tos)
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn set_mark(&self, mark: u32) -> io::Result<()> {
+        set_socket_mark(self.io.as_raw_fd(), mark)
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn mark(&self) -> io::Result<u32> {
+        socket_mark(self.io.as_raw_fd())
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn bind_device(&self, interface: Option<&[u8]>) -> io::Result<()> {
+        // An empty value removes the binding.
+        let interface = interface.unwrap_or(&[]);
+        syscall!(setsockopt(
+            self.io.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_BINDTODEVICE,
+            interface.as_ptr() as *const libc::c_void,
+            interface.len() as libc::socklen_t,
+        ))
+        .map(|_| ())
+    }
+
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn device(&self) -> io::Result<Option<Vec<u8>>> {
+        let mut buf = [0u8; libc::IFNAMSIZ];
+        let mut len = buf.len() as libc::socklen_t;
+        syscall!(getsockopt(
+            self.io.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_BINDTODEVICE,
+            buf.as_mut_ptr() as *mut libc::c_void,
+            &mut len,
+        ))?;
+        // The name is nul terminated, the length is zero if the socket isn't
+        // bound to a device.
+        let name = &buf[..len as usize];
+        let name = match name.iter().position(|&b| b == 0) {
+            Some(end) => &name[..end],
+            None => name,
+        };
+        Ok(if name.is_empty() {
+            None
+        } else {
+            Some(name.to_vec())
+        })
+    }
+
+    pub fn reuse_address(&self) -> io::Result<bool> {
+        reuse_addr(self.io.as_raw_fd())
+    }
+
+    pub fn set_reuse_address(&self, on: bool) -> io::Result<()> {
+        set_reuse_addr(self.io.as_raw_fd(), on)
     }
 
     pub fn join_multicast_v4(&self, multiaddr: Ipv4Addr, interface: Ipv4Addr) -> io::Result<()> {
@@ -140,11 +449,59 @@ impl UdpSocket {
         self.io.leave_multicast_v6(multiaddr, interface)
     }
 
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_socket_buffer_size(self.io.as_raw_fd(), libc::SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        socket_buffer_size(self.io.as_raw_fd(), libc::SO_RCVBUF)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_socket_buffer_size(self.io.as_raw_fd(), libc::SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        socket_buffer_size(self.io.as_raw_fd(), libc::SO_SNDBUF)
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.io.take_error()
     }
 }
 
+/// Returns the packet information from the control messages in `msg`.
+///
+/// # Safety
+///
+/// The control messages in `msg` must be initialised by `recvmsg`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+unsafe fn pktinfo(msg: &libc::msghdr) -> Option<PktInfo> {
+    let mut cmsg = libc::CMSG_FIRSTHDR(msg);
+    while !cmsg.is_null() {
+        let data = libc::CMSG_DATA(cmsg);
+        match ((*cmsg).cmsg_level, (*cmsg).cmsg_type) {
+            (libc::IPPROTO_IP, libc::IP_PKTINFO) => {
+                let info = ptr::read_unaligned(data as *const libc::in_pktinfo);
+                return Some(PktInfo {
+                    destination: IpAddr::V4(Ipv4Addr::from(u32::from_be(info.ipi_addr.s_addr))),
+                    interface_index: info.ipi_ifindex as u32,
+                });
+            }
+            (libc::IPPROTO_IPV6, libc::IPV6_PKTINFO) => {
+                let info = ptr::read_unaligned(data as *const libc::in6_pktinfo);
+                return Some(PktInfo {
+                    destination: IpAddr::V6(Ipv6Addr::from(info.ipi6_addr.s6_addr)),
+                    interface_index: info.ipi6_ifindex as u32,
+                });
+            }
+            _ => {}
+        }
+        cmsg = libc::CMSG_NXTHDR(msg, cmsg);
+    }
+    None
+}
+
 impl event::Source for UdpSocket {
     fn register(&self, registry: &Registry, token: Token, interests: Interests) -> io::Result<()> {
         SourceFd(&self.as_raw_fd()).register(registry, token, interests)
@@ -1,6 +1,8 @@
 use super::{path_offset, socket_addr};
 use crate::event::Source;
 use crate::sys::unix::net::new_socket;
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+use crate::sys::unix::net::set_nosigpipe;
 use crate::sys::unix::UnixStream;
 use crate::unix::SourceFd;
 use crate::{Interests, Registry, Token};
@@ -25,6 +27,7 @@ pub struct UnixListener {
 ///
 /// [`net::SocketAddr`]: std::os::unix::net::SocketAddr
 /// [`accept`]: #method.accept
+#[derive(Clone)]
 pub struct SocketAddr {
     sockaddr: libc::sockaddr_un,
     socklen: libc::socklen_t,
@@ -93,13 +96,19 @@ impl UnixListener {
                 .and_then(|_| syscall!(fcntl(socket, libc::F_SETFD, libc::FD_CLOEXEC)).map(|_| s))
         });
 
+        #[cfg(any(target_os = "ios", target_os = "macos"))]
+        let socket = socket.and_then(|s| set_nosigpipe(s.as_raw_fd()).map(|_| s));
+
         socket.and_then(|s| Ok((s, SocketAddr::from_parts(sockaddr, socklen))))
     }
 
     pub(crate) fn bind(path: &Path) -> io::Result<UnixListener> {
+        SocketAddr::from_pathname(path).and_then(|address| UnixListener::bind_addr(&address))
+    }
+
+    pub(crate) fn bind_addr(address: &SocketAddr) -> io::Result<UnixListener> {
         let socket = new_socket(libc::AF_UNIX, libc::SOCK_STREAM)?;
-        let (sockaddr, socklen) = socket_addr(path)?;
-        let sockaddr = &sockaddr as *const libc::sockaddr_un as *const libc::sockaddr;
+        let (sockaddr, socklen) = address.as_raw();
 
         syscall!(bind(socket, sockaddr, socklen))
             .and_then(|_| syscall!(listen(socket, 1024)))
@@ -191,6 +200,53 @@ impl SocketAddr {
         SocketAddr { sockaddr, socklen }
     }
 
+    /// Returns a pointer to the raw address and its length, for use in system
+    /// calls.
+    pub(crate) fn as_raw(&self) -> (*const libc::sockaddr, libc::socklen_t) {
+        (
+            &self.sockaddr as *const libc::sockaddr_un as *const libc::sockaddr,
+            self.socklen,
+        )
+    }
+
+    /// Creates a `pathname` address from `path`.
+    ///
+    /// Returns an error if `path` is too long to fit in the address.
+    pub fn from_pathname<P: AsRef<Path>>(path: P) -> io::Result<SocketAddr> {
+        socket_addr(path.as_ref()).map(|(sockaddr, socklen)| SocketAddr { sockaddr, socklen })
+    }
+
+    /// Creates an address in the abstract namespace from `name`.
+    ///
+    /// The abstract namespace is a Linux specific extension which allows
+    /// sockets to be bound without creating an entry in the file system.
+    /// `name` should **not** include the leading null byte.
+    ///
+    /// Returns an error if `name` is too long to fit in the address.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use mio::net::{UnixListener, UnixStream};
+    /// use mio::unix::SocketAddr;
+    ///
+    /// let address = SocketAddr::from_abstract_name(b"mio-example")?;
+    /// let listener = UnixListener::bind_addr(&address)?;
+    /// let stream = UnixStream::connect_addr(&address)?;
+    /// # drop((listener, stream));
+    /// #     Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn from_abstract_name(name: &[u8]) -> io::Result<SocketAddr> {
+        let mut path = Vec::with_capacity(name.len() + 1);
+        path.push(0);
+        path.extend_from_slice(name);
+        SocketAddr::from_pathname(OsStr::from_bytes(&path))
+    }
+
     /// Returns `true` if the address is unnamed.
     ///
     /// Documentation reflected in [`SocketAddr`]
@@ -217,6 +273,33 @@ impl SocketAddr {
         }
     }
 
+    /// Returns `true` if the address is in the abstract namespace.
+    ///
+    /// Always returns `false` on platforms other than Android and Linux. See
+    /// [`SocketAddr::from_abstract_name`] for more information about abstract
+    /// addresses.
+    pub fn is_abstract(&self) -> bool {
+        if let AddressKind::Abstract(_) = self.address() {
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the contents of this address if it is in the abstract
+    /// namespace, without the leading null byte.
+    ///
+    /// See [`SocketAddr::from_abstract_name`] for more information about
+    /// abstract addresses.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    pub fn as_abstract_name(&self) -> Option<&[u8]> {
+        if let AddressKind::Abstract(name) = self.address() {
+            Some(name)
+        } else {
+            None
+        }
+    }
+
     fn address(&self) -> AddressKind<'_> {
         let offset = path_offset(&self.sockaddr);
         let len = self.socklen as usize - offset;
@@ -12,6 +12,9 @@ pub use self::listener::{SocketAddr, UnixListener};
 mod stream;
 pub use self::stream::UnixStream;
 
+mod ucred;
+pub use self::ucred::UCred;
+
 pub fn socket_addr(path: &Path) -> io::Result<(libc::sockaddr_un, libc::socklen_t)> {
     let sockaddr = mem::MaybeUninit::<libc::sockaddr_un>::zeroed();
 
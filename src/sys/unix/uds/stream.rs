@@ -1,6 +1,9 @@
-use super::{socket_addr, SocketAddr};
+use super::ucred::{self, UCred};
+use super::SocketAddr;
 use crate::event::Source;
-use crate::sys::unix::net::new_socket;
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+use crate::sys::unix::net::set_nosigpipe;
+use crate::sys::unix::net::{new_socket, send, send_vectored};
 use crate::sys::unix::SourceFd;
 use crate::{Interests, Registry, Token};
 
@@ -21,9 +24,12 @@ impl UnixStream {
     }
 
     pub(crate) fn connect(path: &Path) -> io::Result<UnixStream> {
+        SocketAddr::from_pathname(path).and_then(|address| UnixStream::connect_addr(&address))
+    }
+
+    pub(crate) fn connect_addr(address: &SocketAddr) -> io::Result<UnixStream> {
         let socket = new_socket(libc::AF_UNIX, libc::SOCK_STREAM)?;
-        let (sockaddr, socklen) = socket_addr(path)?;
-        let sockaddr = &sockaddr as *const libc::sockaddr_un as *const libc::sockaddr;
+        let (sockaddr, socklen) = address.as_raw();
 
         match syscall!(connect(socket, sockaddr, socklen)) {
             Ok(_) => {}
@@ -45,8 +51,16 @@ impl UnixStream {
     }
 
     pub(crate) fn pair() -> io::Result<(UnixStream, UnixStream)> {
+        UnixStream::pair_with_type(libc::SOCK_STREAM)
+    }
+
+    pub(crate) fn seqpacket_pair() -> io::Result<(UnixStream, UnixStream)> {
+        UnixStream::pair_with_type(libc::SOCK_SEQPACKET)
+    }
+
+    fn pair_with_type(socket_type: libc::c_int) -> io::Result<(UnixStream, UnixStream)> {
         let mut fds = [-1; 2];
-        let flags = libc::SOCK_STREAM;
+        let flags = socket_type;
         #[cfg(not(any(target_os = "ios", target_os = "macos", target_os = "solaris")))]
         let flags = flags | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC;
 
@@ -71,6 +85,11 @@ impl UnixStream {
             syscall!(fcntl(fds[1], libc::F_SETFL, libc::O_NONBLOCK))?;
             syscall!(fcntl(fds[1], libc::F_SETFD, libc::FD_CLOEXEC))?;
         }
+        #[cfg(any(target_os = "ios", target_os = "macos"))]
+        {
+            set_nosigpipe(fds[0])?;
+            set_nosigpipe(fds[1])?;
+        }
         Ok(pair)
     }
 
@@ -91,6 +110,10 @@ impl UnixStream {
         })
     }
 
+    pub(crate) fn peer_cred(&self) -> io::Result<UCred> {
+        ucred::peer_cred(self.inner.as_raw_fd())
+    }
+
     pub(crate) fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
@@ -98,6 +121,16 @@ impl UnixStream {
     pub(crate) fn shutdown(&self, how: Shutdown) -> io::Result<()> {
         self.inner.shutdown(how)
     }
+
+    pub(crate) fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
+        syscall!(recv(
+            self.inner.as_raw_fd(),
+            buf.as_mut_ptr() as *mut libc::c_void,
+            buf.len(),
+            libc::MSG_PEEK,
+        ))
+        .map(|n| n as usize)
+    }
 }
 
 impl Source for UnixStream {
@@ -131,11 +164,11 @@ impl<'a> Read for &'a UnixStream {
 
 impl<'a> Write for &'a UnixStream {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        (&self.inner).write(buf)
+        send(self.inner.as_raw_fd(), buf)
     }
 
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
-        (&self.inner).write_vectored(bufs)
+        send_vectored(self.inner.as_raw_fd(), bufs)
     }
 
     fn flush(&mut self) -> io::Result<()> {
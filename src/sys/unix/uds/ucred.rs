@@ -0,0 +1,55 @@
+use std::io;
+use std::os::unix::io::RawFd;
+
+/// Credentials of the process on the other side of a Unix socket, see
+/// [`UnixStream::peer_cred`].
+///
+/// [`UnixStream::peer_cred`]: crate::net::UnixStream::peer_cred
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct UCred {
+    /// The effective user id of the peer.
+    pub uid: libc::uid_t,
+    /// The effective group id of the peer.
+    pub gid: libc::gid_t,
+    /// The process id of the peer, only available on Android and Linux.
+    pub pid: Option<libc::pid_t>,
+}
+
+/// Get the credentials of the peer of the socket `fd` using `SO_PEERCRED`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub(crate) fn peer_cred(fd: RawFd) -> io::Result<UCred> {
+    use crate::sys::unix::net::getsockopt;
+
+    getsockopt::<libc::ucred>(fd, libc::SOL_SOCKET, libc::SO_PEERCRED).map(|cred| UCred {
+        uid: cred.uid,
+        gid: cred.gid,
+        pid: Some(cred.pid),
+    })
+}
+
+/// Get the credentials of the peer of the socket `fd` using `getpeereid`.
+#[cfg(any(
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+pub(crate) fn peer_cred(fd: RawFd) -> io::Result<UCred> {
+    let mut uid = 0;
+    let mut gid = 0;
+    syscall!(getpeereid(fd, &mut uid, &mut gid)).map(|_| UCred {
+        uid,
+        gid,
+        pid: None,
+    })
+}
+
+#[cfg(target_os = "solaris")]
+pub(crate) fn peer_cred(_: RawFd) -> io::Result<UCred> {
+    Err(io::Error::new(
+        io::ErrorKind::Other,
+        "peer credentials are not supported on this platform",
+    ))
+}
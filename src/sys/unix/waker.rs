@@ -1,7 +1,9 @@
+use std::io;
+
 #[cfg(any(target_os = "linux", target_os = "android"))]
 mod eventfd {
     use crate::sys::Selector;
-    use crate::{Interests, Token};
+    use crate::{Interests, Token, Trigger};
 
     use std::fs::File;
     use std::io::{self, Read, Write};
@@ -25,7 +27,7 @@ mod eventfd {
                 // it's closed when dropped, e.g. when register below fails.
                 let file = unsafe { File::from_raw_fd(fd) };
                 selector
-                    .register(fd, token, Interests::READABLE)
+                    .register(fd, token, Interests::READABLE, Trigger::Edge)
                     .map(|()| Waker { fd: file })
             })
         }
@@ -37,15 +39,16 @@ mod eventfd {
                 Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
                     // Writing only blocks if the counter is going to overflow.
                     // So we'll reset the counter to 0 and wake it again.
-                    self.reset()?;
+                    self.drain()?;
                     self.wake()
                 }
                 Err(err) => Err(err),
             }
         }
 
-        /// Reset the eventfd object, only need to call this if `wake` fails.
-        fn reset(&self) -> io::Result<()> {
+        /// Reset the eventfd object, called after polling returned an event
+        /// for the waker and if `wake` fails.
+        pub fn drain(&self) -> io::Result<()> {
             let mut buf: [u8; 8] = 0u64.to_ne_bytes();
             match (&self.fd).read(&mut buf) {
                 Ok(_) => Ok(()),
@@ -92,6 +95,12 @@ mod kqueue {
         pub fn wake(&self) -> io::Result<()> {
             self.selector.wake(self.token)
         }
+
+        pub fn drain(&self) -> io::Result<()> {
+            // The event is registered with `EV_CLEAR`, so the kernel already
+            // resets it once it's returned.
+            Ok(())
+        }
     }
 }
 
@@ -106,7 +115,7 @@ pub use self::kqueue::Waker;
 ))]
 mod pipe {
     use crate::sys::unix::Selector;
-    use crate::{Interests, Token};
+    use crate::{Interests, Token, Trigger};
 
     use std::fs::File;
     use std::io::{self, Read, Write};
@@ -131,7 +140,7 @@ mod pipe {
             let sender = unsafe { File::from_raw_fd(fds[1]) };
             let receiver = unsafe { File::from_raw_fd(fds[0]) };
             selector
-                .register(fds[0], token, Interests::READABLE)
+                .register(fds[0], token, Interests::READABLE, Trigger::Edge)
                 .map(|()| Waker { sender, receiver })
         }
 
@@ -141,7 +150,7 @@ mod pipe {
                 Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
                     // The reading end is full so we'll empty the buffer and try
                     // again.
-                    self.empty();
+                    self.drain()?;
                     self.wake()
                 }
                 Err(ref err) if err.kind() == io::ErrorKind::Interrupted => self.wake(),
@@ -149,14 +158,17 @@ mod pipe {
             }
         }
 
-        /// Empty the pipe's buffer, only need to call this if `wake` fails.
-        /// This ignores any errors.
-        fn empty(&self) {
+        /// Empty the pipe's buffer, called after polling returned an event
+        /// for the waker and if `wake` fails.
+        pub fn drain(&self) -> io::Result<()> {
             let mut buf = [0; 4096];
             loop {
                 match (&self.receiver).read(&mut buf) {
                     Ok(n) if n > 0 => continue,
-                    _ => return,
+                    Ok(_) => return Ok(()),
+                    Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                    Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(err) => return Err(err),
                 }
             }
         }
@@ -170,3 +182,37 @@ mod pipe {
     target_os = "solaris"
 ))]
 pub use self::pipe::Waker;
+
+impl Waker {
+    /// Same as `wake`, but preserves `errno`, see
+    /// `crate::Waker::wake_from_signal_handler`.
+    ///
+    /// All implementations of `wake` only use async-signal-safe system calls
+    /// (`write`, `read` and `kevent`) and don't allocate or take any locks.
+    pub fn wake_from_signal_handler(&self) -> io::Result<()> {
+        let errno = unsafe { *errno_location() };
+        let result = self.wake();
+        unsafe { *errno_location() = errno };
+        result
+    }
+}
+
+/// Returns a pointer to the calling thread's `errno`.
+unsafe fn errno_location() -> *mut libc::c_int {
+    #[cfg(any(target_os = "linux", target_os = "dragonfly"))]
+    {
+        libc::__errno_location()
+    }
+    #[cfg(any(target_os = "android", target_os = "netbsd", target_os = "openbsd"))]
+    {
+        libc::__errno()
+    }
+    #[cfg(any(target_os = "freebsd", target_os = "ios", target_os = "macos"))]
+    {
+        libc::__error()
+    }
+    #[cfg(target_os = "solaris")]
+    {
+        libc::___errno()
+    }
+}
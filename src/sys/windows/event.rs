@@ -3,7 +3,7 @@ use miow::iocp::CompletionStatus;
 use super::afd;
 use crate::Token;
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct Event {
     pub flags: u32,
     pub data: u64,
@@ -27,6 +27,10 @@ pub fn is_error(event: &Event) -> bool {
     event.flags & afd::POLL_CONNECT_FAIL != 0
 }
 
+pub fn is_hup(event: &Event) -> bool {
+    event.flags & afd::POLL_ABORT != 0
+}
+
 pub fn is_read_closed(event: &Event) -> bool {
     event.flags & afd::POLL_DISCONNECT != 0
 }
@@ -49,6 +53,28 @@ pub fn is_lio(_: &Event) -> bool {
     false
 }
 
+/// Flag used to mark timeout events, not used by AFD.
+#[cfg(feature = "timers")]
+const TIMEOUT: u32 = 1 << 31;
+
+#[cfg(feature = "os-ext")]
+pub fn raw_ready(event: &Event) -> u32 {
+    event.flags
+}
+
+#[cfg(feature = "timers")]
+pub fn timeout(token: Token) -> Event {
+    Event {
+        flags: TIMEOUT,
+        data: token.0 as u64,
+    }
+}
+
+#[cfg(feature = "timers")]
+pub fn is_timeout(event: &Event) -> bool {
+    event.flags & TIMEOUT != 0
+}
+
 pub struct Events {
     /// Raw I/O event completions are filled in here by the call to `get_many`
     /// on the completion port above. These are then processed to run callbacks
@@ -72,6 +98,10 @@ impl Events {
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
     pub fn is_empty(&self) -> bool {
         self.events.is_empty()
     }
@@ -84,6 +114,11 @@ impl Events {
         self.events.get(idx)
     }
 
+    #[cfg(feature = "timers")]
+    pub fn push(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
     pub fn clear(&mut self) {
         self.events.clear();
         for status in self.statuses.iter_mut() {
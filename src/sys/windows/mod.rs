@@ -1,11 +1,20 @@
+use std::cmp;
 use std::io;
-use std::mem::size_of_val;
+use std::mem::{size_of, size_of_val, MaybeUninit};
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::os::windows::io::RawSocket;
 use std::sync::{Arc, Mutex, Once};
-use winapi::ctypes::c_int;
-use winapi::shared::ws2def::SOCKADDR;
+use winapi::ctypes::{c_char, c_int};
+use winapi::shared::ws2def::{
+    AF_INET, AF_INET6, IPPROTO_IP, IPPROTO_IPV6, SOCKADDR, SOCKADDR_IN, SOCKADDR_STORAGE,
+    SOL_SOCKET,
+};
+use winapi::shared::ws2ipdef::{
+    IPV6_TCLASS, IPV6_UNICAST_HOPS, IPV6_V6ONLY, IP_TOS, IP_TTL, SOCKADDR_IN6_LH,
+};
 use winapi::um::winsock2::{
-    ioctlsocket, socket, FIONBIO, INVALID_SOCKET, PF_INET, PF_INET6, SOCKET,
+    getsockopt, ioctlsocket, setsockopt, socket, FIONBIO, INVALID_SOCKET, PF_INET, PF_INET6,
+    SOCKET, SOCKET_ERROR,
 };
 
 /// Helper macro to execute a system call that returns an `io::Result`.
@@ -45,8 +54,8 @@ mod udp;
 mod waker;
 
 pub use event::{Event, Events};
-pub use selector::{Selector, SelectorInner, SockState};
-pub use tcp::{TcpListener, TcpStream};
+pub use selector::{is_fatal_poll_error, Selector, SelectorInner, SockState};
+pub use tcp::{is_transient_accept_error, TcpListener, TcpSocket, TcpStream};
 pub use udp::UdpSocket;
 pub use waker::Waker;
 
@@ -112,6 +121,7 @@ fn new_socket(addr: SocketAddr, socket_type: c_int) -> io::Result<SOCKET> {
     })
 }
 
+/// Converts a `SocketAddr` into a pointer to a `SOCKADDR` and its length.
 fn socket_addr(addr: &SocketAddr) -> (*const SOCKADDR, c_int) {
     match addr {
         SocketAddr::V4(ref addr) => (
@@ -125,6 +135,38 @@ fn socket_addr(addr: &SocketAddr) -> (*const SOCKADDR, c_int) {
     }
 }
 
+/// Converts a `SOCKADDR_STORAGE` into a `SocketAddr`, the reverse of
+/// `socket_addr`.
+///
+/// # Safety
+///
+/// `storage` must be initialised by the OS, e.g. by `WSARecvFrom`.
+unsafe fn to_socket_addr(storage: &SOCKADDR_STORAGE) -> io::Result<SocketAddr> {
+    match storage.ss_family as c_int {
+        AF_INET => {
+            let addr = &*(storage as *const SOCKADDR_STORAGE as *const SOCKADDR_IN);
+            let ip = Ipv4Addr::from(u32::from_be(*addr.sin_addr.S_un.S_addr()));
+            let port = u16::from_be(addr.sin_port);
+            Ok(SocketAddr::V4(SocketAddrV4::new(ip, port)))
+        }
+        AF_INET6 => {
+            let addr = &*(storage as *const SOCKADDR_STORAGE as *const SOCKADDR_IN6_LH);
+            let ip = Ipv6Addr::from(*addr.sin6_addr.u.Byte());
+            let port = u16::from_be(addr.sin6_port);
+            Ok(SocketAddr::V6(SocketAddrV6::new(
+                ip,
+                port,
+                addr.sin6_flowinfo,
+                *addr.u.sin6_scope_id(),
+            )))
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid address family",
+        )),
+    }
+}
+
 fn inaddr_any(other: SocketAddr) -> SocketAddr {
     match other {
         SocketAddr::V4(..) => {
@@ -139,3 +181,121 @@ fn inaddr_any(other: SocketAddr) -> SocketAddr {
         }
     }
 }
+
+/// Set the socket option `name` at `level` to `value`.
+fn set_socket_option<T>(socket: RawSocket, level: c_int, name: c_int, value: T) -> io::Result<()> {
+    syscall!(
+        setsockopt(
+            socket as SOCKET,
+            level,
+            name,
+            &value as *const T as *const c_char,
+            size_of::<T>() as c_int,
+        ),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )
+    .map(|_| ())
+}
+
+/// Get the value of the socket option `name` at `level`.
+fn socket_option<T: Copy>(socket: RawSocket, level: c_int, name: c_int) -> io::Result<T> {
+    let mut value: MaybeUninit<T> = MaybeUninit::zeroed();
+    let mut len = size_of::<T>() as c_int;
+    syscall!(
+        getsockopt(
+            socket as SOCKET,
+            level,
+            name,
+            value.as_mut_ptr() as *mut c_char,
+            &mut len,
+        ),
+        PartialEq::eq,
+        SOCKET_ERROR
+    )
+    // This is safe because the value is zeroed and Windows wrote at most
+    // `size_of::<T>()` bytes.
+    .map(|_| unsafe { value.assume_init() })
+}
+
+/// Returns the level and name of the socket option that controls the time to
+/// live for sockets bound to `addr`: `IP_TTL` for IPv4 and
+/// `IPV6_UNICAST_HOPS` for IPv6.
+fn ttl_option(addr: SocketAddr) -> (c_int, c_int) {
+    match addr {
+        SocketAddr::V4(..) => (IPPROTO_IP as c_int, IP_TTL as c_int),
+        SocketAddr::V6(..) => (IPPROTO_IPV6 as c_int, IPV6_UNICAST_HOPS as c_int),
+    }
+}
+
+/// Set the time to live of `socket`, bound to `addr`.
+fn set_socket_ttl(socket: RawSocket, addr: SocketAddr, ttl: u32) -> io::Result<()> {
+    if ttl > 255 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "TTL must be in the range 0..=255",
+        ));
+    }
+
+    let (level, name) = ttl_option(addr);
+    set_socket_option(socket, level, name, ttl as c_int)
+}
+
+/// Get the time to live of `socket`, bound to `addr`.
+fn socket_ttl(socket: RawSocket, addr: SocketAddr) -> io::Result<u32> {
+    let (level, name) = ttl_option(addr);
+    socket_option::<c_int>(socket, level, name).map(|ttl| ttl as u32)
+}
+
+/// Returns the level and name of the socket option that controls the type of
+/// service for sockets bound to `addr`: `IP_TOS` for IPv4 and `IPV6_TCLASS`
+/// for IPv6.
+fn tos_option(addr: SocketAddr) -> (c_int, c_int) {
+    match addr {
+        SocketAddr::V4(..) => (IPPROTO_IP as c_int, IP_TOS as c_int),
+        SocketAddr::V6(..) => (IPPROTO_IPV6 as c_int, IPV6_TCLASS as c_int),
+    }
+}
+
+/// Set the type of service of `socket`, bound to `addr`.
+fn set_socket_tos(socket: RawSocket, addr: SocketAddr, tos: u32) -> io::Result<()> {
+    if tos > 255 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "type of service must be in the range 0..=255",
+        ));
+    }
+
+    let (level, name) = tos_option(addr);
+    set_socket_option(socket, level, name, tos as c_int)
+}
+
+/// Get the type of service of `socket`, bound to `addr`.
+fn socket_tos(socket: RawSocket, addr: SocketAddr) -> io::Result<u32> {
+    let (level, name) = tos_option(addr);
+    socket_option::<c_int>(socket, level, name).map(|tos| tos as u32)
+}
+
+/// Set the `IPV6_V6ONLY` option of `socket`, bound to `addr`, if `only_v6` is
+/// `Some`. Does nothing for IPv4 addresses.
+fn set_only_v6(socket: RawSocket, addr: SocketAddr, only_v6: Option<bool>) -> io::Result<()> {
+    match (addr, only_v6) {
+        (SocketAddr::V6(..), Some(only_v6)) => {
+            set_socket_option(socket, IPPROTO_IPV6 as c_int, IPV6_V6ONLY, only_v6 as c_int)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Set the size of the buffer of `socket`, `name` must be either `SO_RCVBUF`
+/// or `SO_SNDBUF`.
+fn set_socket_buffer_size(socket: RawSocket, name: c_int, size: usize) -> io::Result<()> {
+    let size = cmp::min(size, c_int::max_value() as usize) as c_int;
+    set_socket_option(socket, SOL_SOCKET, name, size)
+}
+
+/// Get the size of the buffer of `socket`, `name` must be either `SO_RCVBUF`
+/// or `SO_SNDBUF`.
+fn socket_buffer_size(socket: RawSocket, name: c_int) -> io::Result<usize> {
+    socket_option::<c_int>(socket, SOL_SOCKET, name).map(|size| size as usize)
+}
@@ -9,7 +9,7 @@ use miow::iocp::{CompletionPort, CompletionStatus};
 use miow::Overlapped;
 use std::collections::VecDeque;
 use std::mem::size_of;
-use std::os::windows::io::{AsRawSocket, RawSocket};
+use std::os::windows::io::{AsRawHandle, AsRawSocket, RawHandle, RawSocket};
 use std::pin::Pin;
 use std::ptr::null_mut;
 #[cfg(debug_assertions)]
@@ -21,10 +21,22 @@ use std::{io, ptr};
 use winapi::shared::ntdef::NT_SUCCESS;
 use winapi::shared::ntdef::{HANDLE, PVOID};
 use winapi::shared::ntstatus::STATUS_CANCELLED;
-use winapi::shared::winerror::{ERROR_INVALID_HANDLE, ERROR_IO_PENDING, WAIT_TIMEOUT};
+use winapi::shared::winerror::{
+    ERROR_ABANDONED_WAIT_0, ERROR_INVALID_HANDLE, ERROR_IO_PENDING, WAIT_TIMEOUT,
+};
 use winapi::um::mswsock::SIO_BASE_HANDLE;
 use winapi::um::winsock2::{WSAIoctl, INVALID_SOCKET, SOCKET_ERROR};
 
+/// Returns `true` if `err`, returned by polling, means the completion port is
+/// no longer usable.
+pub fn is_fatal_poll_error(err: &io::Error) -> bool {
+    match err.raw_os_error().map(|code| code as u32) {
+        // The completion port handle is invalid or was closed while waiting.
+        Some(ERROR_INVALID_HANDLE) | Some(ERROR_ABANDONED_WAIT_0) => true,
+        _ => false,
+    }
+}
+
 const POLL_GROUP__MAX_GROUP_SIZE: usize = 32;
 
 /// Overlapped value to indicate a `Waker` event.
@@ -367,6 +379,15 @@ impl Selector {
         self.inner.select(events, timeout)
     }
 
+    /// Same as `select`, but keeps the events already in `events`.
+    pub fn select_append(
+        &mut self,
+        events: &mut Events,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        self.inner.select_append(events, timeout)
+    }
+
     pub fn register<S: SocketState + AsRawSocket>(
         &self,
         socket: &S,
@@ -403,6 +424,12 @@ impl Selector {
     }
 }
 
+impl AsRawHandle for Selector {
+    fn as_raw_handle(&self) -> RawHandle {
+        self.inner.cp.as_raw_handle()
+    }
+}
+
 #[derive(Debug)]
 pub struct SelectorInner {
     cp: Arc<CompletionPort>,
@@ -434,19 +461,32 @@ impl SelectorInner {
     /// May only be calling via `Selector::select`.
     pub fn select(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
         events.clear();
+        self.select_append(events, timeout)
+    }
+
+    /// # Safety
+    ///
+    /// May only be calling via `Selector::select_append`.
+    pub fn select_append(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        // Only dequeue as many completions as there is room left for events.
+        let available = events.statuses.len().saturating_sub(events.events.len());
+        if available == 0 {
+            return Ok(());
+        }
 
         let mut n = 0;
         let start = Instant::now();
 
         loop {
             if timeout.is_none() {
-                let len = self.select2(&mut events.statuses, &mut events.events, None)?;
+                let len =
+                    self.select2(&mut events.statuses[..available], &mut events.events, None)?;
                 if len == 0 {
                     continue;
                 }
                 return Ok(());
             } else {
-                if n >= events.statuses.len() {
+                if n >= available {
                     return Ok(());
                 }
                 let timeout = timeout.unwrap();
@@ -457,7 +497,7 @@ impl SelectorInner {
                         return Ok(());
                     }
                     let len = self.select2(
-                        &mut events.statuses[n..],
+                        &mut events.statuses[n..available],
                         &mut events.events,
                         Some(deadline - now),
                     )?;
@@ -466,7 +506,11 @@ impl SelectorInner {
                     }
                     n += len;
                 } else {
-                    self.select2(&mut events.statuses[n..], &mut events.events, Some(timeout))?;
+                    self.select2(
+                        &mut events.statuses[n..available],
+                        &mut events.events,
+                        Some(timeout),
+                    )?;
                     return Ok(());
                 }
             }
@@ -534,7 +578,12 @@ impl SelectorInner {
 
         let sock = match socket.get_sock_state() {
             Some(sock) => sock,
-            None => return Err(io::Error::from(io::ErrorKind::NotFound)),
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "attempted to reregister an event source that is not registered",
+                ))
+            }
         };
         let event = Event {
             flags,
@@ -615,14 +664,22 @@ impl SelectorInner {
         iocp_events: &[CompletionStatus],
     ) -> usize {
         let mut n = 0;
+        let start = events.len();
         let mut update_queue = self.update_queue.lock().unwrap();
         for iocp_event in iocp_events.iter() {
             if iocp_event.overlapped().is_null() {
-                // `Waker` event, we'll add a readable event to match the other platforms.
-                events.push(Event {
+                // `Waker` event, we'll add a readable event to match the other
+                // platforms. Multiple wake ups result in a single event.
+                let event = Event {
                     flags: afd::POLL_RECEIVE,
                     data: iocp_event.token() as u64,
-                });
+                };
+                if !events[start..]
+                    .iter()
+                    .any(|e| e.flags == event.flags && e.data == event.data)
+                {
+                    events.push(event);
+                }
                 n += 1;
                 continue;
             }
@@ -1,15 +1,34 @@
 use super::selector::SockState;
-use super::{inaddr_any, new_socket, socket_addr, InternalState};
+use super::{
+    inaddr_any, new_socket, set_only_v6, set_socket_buffer_size, set_socket_option, set_socket_ttl,
+    socket_addr, socket_buffer_size, socket_option, socket_ttl, InternalState,
+};
+use crate::net::{TcpKeepalive, TcpListenerOpts};
 use crate::sys::windows::init;
 use crate::{event, poll, Interests, Registry, Token};
 
+use std::cmp;
 use std::fmt;
 use std::io::{self, IoSlice, IoSliceMut, Read, Write};
-use std::net::{self, SocketAddr};
+use std::mem::{self, size_of};
+use std::net::{self, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::os::windows::io::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
 use std::os::windows::raw::SOCKET as StdSocket; // winapi uses usize, stdlib uses u32/u64.
+use std::ptr;
 use std::sync::{Arc, Mutex};
-use winapi::um::winsock2::{bind, closesocket, connect, listen, SOCKET_ERROR, SOCK_STREAM};
+use std::time::Duration;
+use winapi::ctypes::c_int;
+use winapi::shared::minwindef::{DWORD, ULONG};
+use winapi::shared::mstcpip::{tcp_keepalive, SIO_KEEPALIVE_VALS};
+use winapi::shared::winerror::{WSAECONNRESET, WSAEINTR, WSAEMFILE, WSAENOBUFS};
+use winapi::shared::ws2def::{
+    SOCKADDR, SOCKADDR_STORAGE, SOL_SOCKET, SO_KEEPALIVE, SO_LINGER, SO_RCVBUF, SO_REUSEADDR,
+    SO_SNDBUF, WSABUF,
+};
+use winapi::um::winsock2::{
+    bind, closesocket, connect, getsockname, linger, listen, WSAIoctl, WSARecv, MSG_PEEK, SOCKET,
+    SOCKET_ERROR, SOCK_STREAM,
+};
 
 pub struct TcpStream {
     internal: Box<Mutex<Option<InternalState>>>,
@@ -61,11 +80,15 @@ impl TcpStream {
             })
     }
 
-    pub fn from_std(inner: net::TcpStream) -> TcpStream {
-        TcpStream {
+    pub fn from_std(inner: net::TcpStream) -> io::Result<TcpStream> {
+        Ok(TcpStream {
             internal: Box::new(Mutex::new(None)),
             inner,
-        }
+        })
+    }
+
+    pub fn into_std(self) -> net::TcpStream {
+        self.inner
     }
 
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
@@ -95,12 +118,104 @@ impl TcpStream {
         self.inner.nodelay()
     }
 
+    pub fn set_quickack(&self, _: bool) -> io::Result<()> {
+        Err(quickack_unsupported())
+    }
+
+    pub fn quickack(&self) -> io::Result<bool> {
+        Err(quickack_unsupported())
+    }
+
     pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
-        self.inner.set_ttl(ttl)
+        set_socket_ttl(self.inner.as_raw_socket(), self.inner.local_addr()?, ttl)
     }
 
     pub fn ttl(&self) -> io::Result<u32> {
-        self.inner.ttl()
+        socket_ttl(self.inner.as_raw_socket(), self.inner.local_addr()?)
+    }
+
+    pub fn set_linger(&self, dur: Option<Duration>) -> io::Result<()> {
+        let value = linger {
+            l_onoff: dur.is_some() as u16,
+            l_linger: dur.map(linger_secs).unwrap_or(0),
+        };
+        set_socket_option(self.inner.as_raw_socket(), SOL_SOCKET, SO_LINGER, value)
+    }
+
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        socket_option::<linger>(self.inner.as_raw_socket(), SOL_SOCKET, SO_LINGER).map(|value| {
+            if value.l_onoff != 0 {
+                Some(Duration::from_secs(u64::from(value.l_linger)))
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn set_keepalive(&self, keepalive: Option<TcpKeepalive>) -> io::Result<()> {
+        let socket = self.inner.as_raw_socket();
+        let enabled = keepalive.is_some() as c_int;
+        set_socket_option(socket, SOL_SOCKET, SO_KEEPALIVE, enabled)?;
+        match keepalive {
+            Some(keepalive) if keepalive.time().is_some() || keepalive.interval().is_some() => {
+                // Windows doesn't allow the time and interval to be set
+                // separately, so use the defaults for the missing one.
+                let values = tcp_keepalive {
+                    onoff: 1,
+                    keepalivetime: keepalive.time().map_or(KEEPALIVE_TIME_MS, duration_ms),
+                    keepaliveinterval: keepalive
+                        .interval()
+                        .map_or(KEEPALIVE_INTERVAL_MS, duration_ms),
+                };
+                let mut returned = 0;
+                syscall!(
+                    WSAIoctl(
+                        socket as SOCKET,
+                        SIO_KEEPALIVE_VALS,
+                        &values as *const tcp_keepalive as *mut _,
+                        size_of::<tcp_keepalive>() as DWORD,
+                        ptr::null_mut(),
+                        0,
+                        &mut returned,
+                        ptr::null_mut(),
+                        None,
+                    ),
+                    PartialEq::eq,
+                    SOCKET_ERROR
+                )
+                .map(|_| ())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn keepalive(&self) -> io::Result<Option<TcpKeepalive>> {
+        // The parameters set using `SIO_KEEPALIVE_VALS` can't be retrieved.
+        socket_option::<c_int>(self.inner.as_raw_socket(), SOL_SOCKET, SO_KEEPALIVE).map(
+            |enabled| {
+                if enabled != 0 {
+                    Some(TcpKeepalive::new())
+                } else {
+                    None
+                }
+            },
+        )
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_socket_buffer_size(self.inner.as_raw_socket(), SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        socket_buffer_size(self.inner.as_raw_socket(), SO_RCVBUF)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_socket_buffer_size(self.inner.as_raw_socket(), SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        socket_buffer_size(self.inner.as_raw_socket(), SO_SNDBUF)
     }
 
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
@@ -111,6 +226,33 @@ impl TcpStream {
         self.inner.peek(buf)
     }
 
+    pub fn peek_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        let mut received = 0;
+        let mut flags = MSG_PEEK as DWORD;
+        syscall!(
+            WSARecv(
+                self.inner.as_raw_socket() as SOCKET,
+                // `IoSliceMut` is guaranteed to be ABI compatible with `WSABUF`.
+                bufs.as_mut_ptr() as *mut WSABUF,
+                cmp::min(bufs.len(), DWORD::max_value() as usize) as DWORD,
+                &mut received,
+                &mut flags,
+                ptr::null_mut(),
+                None,
+            ),
+            PartialEq::eq,
+            SOCKET_ERROR
+        )
+        .map(|_| received as usize)
+    }
+
+    pub fn read_blocking(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.set_nonblocking(false)?;
+        let result = (&self.inner).read(buf);
+        // Always attempt to restore non-blocking mode, even if reading failed.
+        self.inner.set_nonblocking(true).and(result)
+    }
+
     // Used by `try_io` to register after an I/O operation blocked.
     fn io_blocked_reregister(&self) -> io::Result<()> {
         let internal = self.internal.lock().unwrap();
@@ -126,6 +268,35 @@ impl TcpStream {
     }
 }
 
+/// Converts `dur` into the number of seconds used in `SO_LINGER`, rounding up
+/// sub-second durations.
+fn linger_secs(dur: Duration) -> u16 {
+    let secs = dur.as_secs() + if dur.subsec_nanos() > 0 { 1 } else { 0 };
+    cmp::min(secs, u64::from(u16::max_value())) as u16
+}
+
+fn quickack_unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Other,
+        "`TCP_QUICKACK` is not supported on this platform",
+    )
+}
+
+/// Windows' default keepalive time (2 hours) and interval (1 second), in
+/// milliseconds.
+const KEEPALIVE_TIME_MS: ULONG = 2 * 60 * 60 * 1000;
+const KEEPALIVE_INTERVAL_MS: ULONG = 1000;
+
+/// Converts `dur` into milliseconds, rounding up sub-millisecond durations.
+fn duration_ms(dur: Duration) -> ULONG {
+    let nanos = u64::from(dur.subsec_nanos());
+    let ms = dur
+        .as_secs()
+        .saturating_mul(1000)
+        .saturating_add((nanos + 999_999) / 1_000_000);
+    cmp::min(ms, u64::from(ULONG::max_value())) as ULONG
+}
+
 impl super::SocketState for TcpStream {
     fn get_sock_state(&self) -> Option<Arc<Mutex<SockState>>> {
         let internal = self.internal.lock().unwrap();
@@ -322,26 +493,43 @@ impl AsRawSocket for TcpStream {
 }
 
 impl TcpListener {
-    pub fn bind(addr: SocketAddr) -> io::Result<TcpListener> {
+    pub fn bind(addr: SocketAddr, opts: &TcpListenerOpts) -> io::Result<TcpListener> {
+        if opts.reuse_port {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "SO_REUSEPORT is not supported on Windows",
+            ));
+        }
+
         init();
         new_socket(addr, SOCK_STREAM).and_then(|socket| {
-            let (raw_addr, raw_addr_length) = socket_addr(&addr);
-            syscall!(
-                bind(socket, raw_addr, raw_addr_length,),
-                PartialEq::eq,
-                SOCKET_ERROR
-            )
-            .and_then(|_| syscall!(listen(socket, 1024), PartialEq::eq, SOCKET_ERROR))
-            .map_err(|err| {
-                // Close the socket if we hit an error, ignoring the error
-                // from closing since we can't pass back two errors.
-                let _ = unsafe { closesocket(socket) };
-                err
-            })
-            .map(|_| TcpListener {
-                internal: Box::new(Mutex::new(None)),
-                inner: unsafe { net::TcpListener::from_raw_socket(socket as StdSocket) },
-            })
+            set_only_v6(socket as RawSocket, addr, opts.only_v6)
+                .and_then(|_| {
+                    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+                    syscall!(
+                        bind(socket, raw_addr, raw_addr_length,),
+                        PartialEq::eq,
+                        SOCKET_ERROR
+                    )
+                })
+                .and_then(|_| {
+                    let backlog = cmp::min(opts.backlog, c_int::max_value() as u32);
+                    syscall!(
+                        listen(socket, backlog as c_int),
+                        PartialEq::eq,
+                        SOCKET_ERROR
+                    )
+                })
+                .map_err(|err| {
+                    // Close the socket if we hit an error, ignoring the error
+                    // from closing since we can't pass back two errors.
+                    let _ = unsafe { closesocket(socket) };
+                    err
+                })
+                .map(|_| TcpListener {
+                    internal: Box::new(Mutex::new(None)),
+                    inner: unsafe { net::TcpListener::from_raw_socket(socket as StdSocket) },
+                })
         })
     }
 
@@ -491,6 +679,13 @@ impl event::Source for TcpListener {
     }
 }
 
+pub fn is_transient_accept_error(err: &io::Error) -> bool {
+    match err.raw_os_error().map(|code| code as u32) {
+        Some(WSAECONNRESET) | Some(WSAEINTR) | Some(WSAEMFILE) | Some(WSAENOBUFS) => true,
+        _ => false,
+    }
+}
+
 impl fmt::Debug for TcpListener {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Debug::fmt(&self.inner, f)
@@ -517,3 +712,137 @@ impl AsRawSocket for TcpListener {
         self.inner.as_raw_socket()
     }
 }
+
+pub struct TcpSocket {
+    socket: SOCKET,
+}
+
+impl TcpSocket {
+    pub fn new_v4() -> io::Result<TcpSocket> {
+        init();
+        let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
+        new_socket(addr, SOCK_STREAM).map(|socket| TcpSocket { socket })
+    }
+
+    pub fn new_v6() -> io::Result<TcpSocket> {
+        init();
+        let addr = SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), 0);
+        new_socket(addr, SOCK_STREAM).map(|socket| TcpSocket { socket })
+    }
+
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        let reuseaddr = reuseaddr as c_int;
+        set_socket_option(
+            self.socket as RawSocket,
+            SOL_SOCKET,
+            SO_REUSEADDR,
+            reuseaddr,
+        )
+    }
+
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        if reuseport {
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                "SO_REUSEPORT is not supported on Windows",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn bind(&self, addr: SocketAddr) -> io::Result<()> {
+        let (raw_addr, raw_addr_length) = socket_addr(&addr);
+        syscall!(
+            bind(self.socket, raw_addr, raw_addr_length),
+            PartialEq::eq,
+            SOCKET_ERROR
+        )
+        .map(|_| ())
+    }
+
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        let backlog = cmp::min(backlog, c_int::max_value() as u32);
+        syscall!(
+            listen(self.socket, backlog as c_int),
+            PartialEq::eq,
+            SOCKET_ERROR
+        )?;
+        Ok(unsafe { TcpListener::from_raw_socket(self.into_raw_socket()) })
+    }
+
+    pub fn connect(self, addr: SocketAddr) -> io::Result<TcpStream> {
+        // Required for a future `connect_overlapped` operation to be executed
+        // successfully, see `TcpStream::connect`.
+        if !self.is_bound() {
+            self.bind(inaddr_any(addr))?;
+        }
+        let (raw_addr, raw_addr_length) = socket_addr(&addr);
+        if let Err(err) = syscall!(
+            connect(self.socket, raw_addr, raw_addr_length),
+            PartialEq::eq,
+            SOCKET_ERROR
+        ) {
+            // Connect hasn't finished, but that is fine.
+            if err.kind() != io::ErrorKind::WouldBlock {
+                return Err(err);
+            }
+        }
+        let stream = unsafe { net::TcpStream::from_raw_socket(self.into_raw_socket()) };
+        TcpStream::from_std(stream)
+    }
+
+    /// Returns true if the socket is bound to a local address.
+    fn is_bound(&self) -> bool {
+        let mut storage: SOCKADDR_STORAGE = unsafe { mem::zeroed() };
+        let mut length = size_of::<SOCKADDR_STORAGE>() as c_int;
+        // `getsockname` fails with `WSAEINVAL` for sockets that aren't bound.
+        syscall!(
+            getsockname(
+                self.socket,
+                &mut storage as *mut SOCKADDR_STORAGE as *mut SOCKADDR,
+                &mut length,
+            ),
+            PartialEq::eq,
+            SOCKET_ERROR
+        )
+        .is_ok()
+    }
+}
+
+impl Drop for TcpSocket {
+    fn drop(&mut self) {
+        let _ = unsafe { closesocket(self.socket) };
+    }
+}
+
+impl fmt::Debug for TcpSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TcpSocket")
+            .field("socket", &self.socket)
+            .finish()
+    }
+}
+
+impl FromRawSocket for TcpSocket {
+    unsafe fn from_raw_socket(socket: RawSocket) -> TcpSocket {
+        TcpSocket {
+            socket: socket as SOCKET,
+        }
+    }
+}
+
+impl IntoRawSocket for TcpSocket {
+    fn into_raw_socket(self) -> RawSocket {
+        let socket = self.socket;
+        // Don't close the socket.
+        mem::forget(self);
+        socket as RawSocket
+    }
+}
+
+impl AsRawSocket for TcpSocket {
+    fn as_raw_socket(&self) -> RawSocket {
+        self.socket as RawSocket
+    }
+}
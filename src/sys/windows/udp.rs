@@ -1,14 +1,29 @@
 use super::selector::SockState;
-use super::{new_socket, socket_addr, InternalState};
+use super::{
+    new_socket, set_only_v6, set_socket_buffer_size, set_socket_option, set_socket_tos,
+    set_socket_ttl, socket_addr, socket_buffer_size, socket_option, socket_tos, socket_ttl,
+    to_socket_addr, InternalState,
+};
+use crate::net::UdpSocketOpts;
 use crate::sys::windows::init;
 use crate::{event, poll, Interests, Registry, Token};
 
+use std::io::{IoSlice, IoSliceMut};
 use std::net::{self, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::os::windows::io::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
 use std::os::windows::raw::SOCKET as StdSocket; // winapi uses usize, stdlib uses u32/u64.
 use std::sync::{Arc, Mutex};
-use std::{fmt, io};
-use winapi::um::winsock2::{bind, closesocket, SOCKET_ERROR, SOCK_DGRAM};
+use std::{cmp, fmt, io, mem, ptr};
+use winapi::ctypes::c_int;
+use winapi::shared::minwindef::DWORD;
+use winapi::shared::ws2def::{
+    IPPROTO_IP, IPPROTO_IPV6, SOCKADDR, SOCKADDR_STORAGE, SOL_SOCKET, SO_RCVBUF, SO_REUSEADDR,
+    SO_SNDBUF, WSABUF,
+};
+use winapi::shared::ws2ipdef::{IPV6_MULTICAST_IF, IP_MULTICAST_IF};
+use winapi::um::winsock2::{
+    bind, closesocket, WSARecvFrom, WSASendTo, SOCKET, SOCKET_ERROR, SOCK_DGRAM,
+};
 
 pub struct UdpSocket {
     internal: Box<Mutex<Option<InternalState>>>,
@@ -16,25 +31,41 @@ pub struct UdpSocket {
 }
 
 impl UdpSocket {
-    pub fn bind(addr: SocketAddr) -> io::Result<UdpSocket> {
+    pub fn bind(addr: SocketAddr, opts: &UdpSocketOpts) -> io::Result<UdpSocket> {
+        if opts.reuse_port {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "SO_REUSEPORT is not supported on Windows",
+            ));
+        }
+
         init();
         new_socket(addr, SOCK_DGRAM).and_then(|socket| {
-            let (raw_addr, raw_addr_length) = socket_addr(&addr);
-            syscall!(
-                bind(socket, raw_addr, raw_addr_length,),
-                PartialEq::eq,
-                SOCKET_ERROR
-            )
-            .map_err(|err| {
-                // Close the socket if we hit an error, ignoring the error
-                // from closing since we can't pass back two errors.
-                let _ = unsafe { closesocket(socket) };
-                err
-            })
-            .map(|_| UdpSocket {
-                internal: Box::new(Mutex::new(None)),
-                inner: unsafe { net::UdpSocket::from_raw_socket(socket as StdSocket) },
-            })
+            let result = if opts.reuse_addr {
+                set_socket_option(socket as RawSocket, SOL_SOCKET, SO_REUSEADDR, 1 as c_int)
+            } else {
+                Ok(())
+            };
+            result
+                .and_then(|_| set_only_v6(socket as RawSocket, addr, opts.only_v6))
+                .and_then(|_| {
+                    let (raw_addr, raw_addr_length) = socket_addr(&addr);
+                    syscall!(
+                        bind(socket, raw_addr, raw_addr_length,),
+                        PartialEq::eq,
+                        SOCKET_ERROR
+                    )
+                })
+                .map_err(|err| {
+                    // Close the socket if we hit an error, ignoring the error
+                    // from closing since we can't pass back two errors.
+                    let _ = unsafe { closesocket(socket) };
+                    err
+                })
+                .map(|_| UdpSocket {
+                    internal: Box::new(Mutex::new(None)),
+                    inner: unsafe { net::UdpSocket::from_raw_socket(socket as StdSocket) },
+                })
         })
     }
 
@@ -68,6 +99,59 @@ impl UdpSocket {
         try_io!(self, peek_from, buf)
     }
 
+    pub fn send_to_vectored(&self, bufs: &[IoSlice<'_>], target: SocketAddr) -> io::Result<usize> {
+        let (raw_addr, raw_addr_length) = socket_addr(&target);
+        let mut sent: DWORD = 0;
+        let result = syscall!(
+            WSASendTo(
+                self.inner.as_raw_socket() as SOCKET,
+                // `IoSlice` is guaranteed to be ABI compatible with `WSABUF`.
+                bufs.as_ptr() as *mut WSABUF,
+                cmp::min(bufs.len(), DWORD::max_value() as usize) as DWORD,
+                &mut sent,
+                0,
+                raw_addr,
+                raw_addr_length,
+                ptr::null_mut(),
+                None,
+            ),
+            PartialEq::eq,
+            SOCKET_ERROR
+        )
+        .map(|_| sent as usize);
+        self.io_result(result)
+    }
+
+    pub fn recv_from_vectored(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> io::Result<(usize, SocketAddr)> {
+        let mut storage: SOCKADDR_STORAGE = unsafe { mem::zeroed() };
+        let mut storage_len = mem::size_of::<SOCKADDR_STORAGE>() as c_int;
+        let mut received: DWORD = 0;
+        let mut flags: DWORD = 0;
+        let result = syscall!(
+            WSARecvFrom(
+                self.inner.as_raw_socket() as SOCKET,
+                // `IoSliceMut` is guaranteed to be ABI compatible with `WSABUF`.
+                bufs.as_mut_ptr() as *mut WSABUF,
+                cmp::min(bufs.len(), DWORD::max_value() as usize) as DWORD,
+                &mut received,
+                &mut flags,
+                &mut storage as *mut SOCKADDR_STORAGE as *mut SOCKADDR,
+                &mut storage_len,
+                ptr::null_mut(),
+                None,
+            ),
+            PartialEq::eq,
+            SOCKET_ERROR
+        )
+        // This is safe because `WSARecvFrom` initialised the address.
+        .and_then(|_| unsafe { to_socket_addr(&storage) })
+        .map(|addr| (received as usize, addr));
+        self.io_result(result)
+    }
+
     pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
         try_io!(self, send, buf)
     }
@@ -116,12 +200,70 @@ impl UdpSocket {
         self.inner.set_multicast_loop_v6(on)
     }
 
+    pub fn multicast_if_v4(&self) -> io::Result<Ipv4Addr> {
+        // The address is in network byte order.
+        socket_option::<DWORD>(
+            self.inner.as_raw_socket(),
+            IPPROTO_IP as c_int,
+            IP_MULTICAST_IF,
+        )
+        .map(|addr| Ipv4Addr::from(addr.to_ne_bytes()))
+    }
+
+    pub fn set_multicast_if_v4(&self, interface: Ipv4Addr) -> io::Result<()> {
+        set_socket_option(
+            self.inner.as_raw_socket(),
+            IPPROTO_IP as c_int,
+            IP_MULTICAST_IF,
+            DWORD::from_ne_bytes(interface.octets()),
+        )
+    }
+
+    pub fn multicast_if_v6(&self) -> io::Result<u32> {
+        socket_option::<DWORD>(
+            self.inner.as_raw_socket(),
+            IPPROTO_IPV6 as c_int,
+            IPV6_MULTICAST_IF,
+        )
+    }
+
+    pub fn set_multicast_if_v6(&self, index: u32) -> io::Result<()> {
+        set_socket_option(
+            self.inner.as_raw_socket(),
+            IPPROTO_IPV6 as c_int,
+            IPV6_MULTICAST_IF,
+            index as DWORD,
+        )
+    }
+
     pub fn ttl(&self) -> io::Result<u32> {
-        self.inner.ttl()
+        socket_ttl(self.inner.as_raw_socket(), self.inner.local_addr()?)
     }
 
     pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
-        self.inner.set_ttl(ttl)
+        set_socket_ttl(self.inner.as_raw_socket(), self.inner.local_addr()?, ttl)
+    }
+
+    pub fn tos(&self) -> io::Result<u32> {
+        socket_tos(self.inner.as_raw_socket(), self.inner.local_addr()?)
+    }
+
+    pub fn set_tos(&self, tos: u32) -> io::Result<()> {
+        set_socket_tos(self.inner.as_raw_socket(), self.inner.local_addr()?, tos)
+    }
+
+    pub fn reuse_address(&self) -> io::Result<bool> {
+        socket_option::<c_int>(self.inner.as_raw_socket(), SOL_SOCKET, SO_REUSEADDR)
+            .map(|on| on != 0)
+    }
+
+    pub fn set_reuse_address(&self, on: bool) -> io::Result<()> {
+        set_socket_option(
+            self.inner.as_raw_socket(),
+            SOL_SOCKET,
+            SO_REUSEADDR,
+            on as c_int,
+        )
     }
 
     pub fn join_multicast_v4(&self, multiaddr: Ipv4Addr, interface: Ipv4Addr) -> io::Result<()> {
@@ -140,10 +282,37 @@ impl UdpSocket {
         self.inner.leave_multicast_v6(multiaddr, interface)
     }
 
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_socket_buffer_size(self.inner.as_raw_socket(), SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        socket_buffer_size(self.inner.as_raw_socket(), SO_RCVBUF)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_socket_buffer_size(self.inner.as_raw_socket(), SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        socket_buffer_size(self.inner.as_raw_socket(), SO_SNDBUF)
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
 
+    /// Same as `try_io`, but for the `result` of an I/O operation that
+    /// doesn't use `inner` directly.
+    fn io_result<T>(&self, result: io::Result<T>) -> io::Result<T> {
+        if let Err(ref e) = result {
+            if e.kind() == io::ErrorKind::WouldBlock {
+                self.io_blocked_reregister()?;
+            }
+        }
+        result
+    }
+
     // Used by `try_io` to register after an I/O operation blocked.
     fn io_blocked_reregister(&self) -> io::Result<()> {
         let internal = self.internal.lock().unwrap();
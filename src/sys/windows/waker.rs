@@ -25,4 +25,10 @@ impl Waker {
         let status = CompletionStatus::new(0, self.token.0, WAKER_OVERLAPPED);
         self.port.post(status)
     }
+
+    pub fn drain(&self) -> io::Result<()> {
+        // Every wake up posts a completion status, the selector coalesces the
+        // statuses returned by a single poll into a single event.
+        Ok(())
+    }
 }
@@ -1,3 +1,5 @@
+use std::ops;
+
 /// Associates readiness events with [`event::Source`]s.
 ///
 /// `Token` is a wrapper around `usize` and is used as an argument to
@@ -130,6 +132,85 @@
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Token(pub usize);
 
+impl Token {
+    /// Create a `Token` from a pointer.
+    ///
+    /// This allows per-connection state to be stored outside of a slab, e.g.
+    /// by using the pointer returned by [`Box::into_raw`] as token. Use
+    /// [`Token::as_ptr`] to get the pointer back when the token is returned in
+    /// an event.
+    ///
+    /// Note that creating the token is safe, but using the returned pointer
+    /// is only sound if the token was created by this function, using a
+    /// pointer to the same type, and the pointed to value is still alive. The
+    /// token only holds the address of the pointer, it doesn't keep the value
+    /// alive nor does it track its provenance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio::Token;
+    ///
+    /// struct Connection {
+    ///     bytes_read: usize,
+    /// }
+    ///
+    /// let connection = Box::new(Connection { bytes_read: 0 });
+    /// let token = Token::from_ptr(Box::into_raw(connection));
+    ///
+    /// // Use `token` to register an event source, when an event is received
+    /// // for it the connection state can be retrieved using the token.
+    ///
+    /// // Safety: the token was created from a `Box<Connection>` above which
+    /// // hasn't been dropped.
+    /// let mut connection = unsafe { Box::from_raw(token.as_ptr::<Connection>() as *mut Connection) };
+    /// connection.bytes_read += 10;
+    /// # assert_eq!(connection.bytes_read, 10);
+    /// ```
+    pub fn from_ptr<T>(ptr: *const T) -> Token {
+        Token(ptr as usize)
+    }
+
+    /// Returns the pointer stored in this token.
+    ///
+    /// The pointer is only valid if the token was created by
+    /// [`Token::from_ptr`] with a pointer to `T`, and the pointed to value
+    /// hasn't been dropped yet. See [`Token::from_ptr`] for an example.
+    pub fn as_ptr<T>(self) -> *const T {
+        self.0 as *const T
+    }
+
+    /// Returns the value of the token, same as `token.0`.
+    pub fn index(self) -> usize {
+        self.0
+    }
+
+    /// Returns an iterator over `len` sequential tokens, starting at `start`.
+    ///
+    /// The iterator never overflows, if `start + len` is larger than
+    /// `usize::MAX` it stops before `Token(usize::MAX)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio::Token;
+    ///
+    /// let tokens: Vec<Token> = Token::range(Token(5), 3).collect();
+    /// assert_eq!(tokens, [Token(5), Token(6), Token(7)]);
+    /// ```
+    pub fn range(start: Token, len: usize) -> impl Iterator<Item = Token> {
+        (start.0..start.0.saturating_add(len)).map(Token)
+    }
+}
+
+impl ops::Add<usize> for Token {
+    type Output = Token;
+
+    fn add(self, other: usize) -> Token {
+        Token(self.0 + other)
+    }
+}
+
 impl From<usize> for Token {
     fn from(val: usize) -> Token {
         Token(val)
@@ -1,6 +1,8 @@
 use crate::{poll, sys, Registry, Token};
 
 use std::io;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
 
 /// Waker allows cross-thread waking of [`Poll`].
 ///
@@ -16,10 +18,17 @@ use std::io;
 /// `Waker` events are only guaranteed to be delivered while the `Waker` value
 /// is alive.
 ///
+/// Wake ups coalesce: calling [`wake`] multiple times before [`Poll::poll`]
+/// returns results in a single event. Polling clears the wake ups, so the
+/// next call to `poll` only returns an event for the `Waker` if [`wake`] is
+/// called again.
+///
+/// [`Poll::poll`]: crate::Poll::poll
+///
 /// Only a single `Waker` should active per [`Poll`], if multiple threads need
-/// access to the `Waker` it can be shared via for example an `Arc`. What
-/// happens if multiple `Waker`s are registered with the same `Poll` is
-/// undefined.
+/// access to the `Waker` it can be cloned. Clones share the same underlying
+/// waker, so they don't count as multiple `Waker`s. What happens if multiple
+/// `Waker`s are created for the same `Poll` is undefined.
 ///
 /// # Implementation notes
 ///
@@ -38,7 +47,6 @@ use std::io;
 /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
 /// use std::thread;
 /// use std::time::Duration;
-/// use std::sync::Arc;
 ///
 /// use mio::{Events, Token, Poll, Waker};
 ///
@@ -47,7 +55,7 @@ use std::io;
 /// let mut poll = Poll::new()?;
 /// let mut events = Events::with_capacity(2);
 ///
-/// let waker = Arc::new(Waker::new(poll.registry(), WAKE_TOKEN)?);
+/// let waker = Waker::new(poll.registry(), WAKE_TOKEN)?;
 ///
 /// // We need to keep the Waker alive, so we'll create a clone for the
 /// // thread we create below.
@@ -73,15 +81,23 @@ use std::io;
 /// #     Ok(())
 /// # }
 /// ```
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Waker {
-    inner: sys::Waker,
+    inner: Arc<sys::Waker>,
 }
 
 impl Waker {
     /// Create a new `Waker`.
+    ///
+    /// See [`Poll::waker_handle`] for a shorthand.
+    ///
+    /// [`Poll::waker_handle`]: crate::Poll::waker_handle
     pub fn new(registry: &Registry, token: Token) -> io::Result<Waker> {
-        sys::Waker::new(poll::selector(&registry), token).map(|inner| Waker { inner })
+        sys::Waker::new(poll::selector(&registry), token).map(|inner| {
+            let inner = Arc::new(inner);
+            poll::set_waker(registry, token, &inner);
+            Waker { inner }
+        })
     }
 
     /// Wake up the [`Poll`] associated with this `Waker`.
@@ -90,4 +106,287 @@ impl Waker {
     pub fn wake(&self) -> io::Result<()> {
         self.inner.wake()
     }
+
+    /// Wake up the [`Poll`] associated with this `Waker` from within a signal
+    /// handler.
+    ///
+    /// This is the same as [`wake`], but it's guaranteed to be
+    /// async-signal-safe: it only uses async-signal-safe system calls (e.g.
+    /// `write` and `read` on the eventfd or pipe, `kevent` on platforms using
+    /// kqueue), doesn't allocate or take any locks and preserves `errno`. It
+    /// can be used to break out of [`Poll::poll`] when a signal, e.g.
+    /// `SIGINT`, is received.
+    ///
+    /// Note that the `Waker` must be reachable from the signal handler without
+    /// taking a lock, e.g. by storing a pointer to it in an `AtomicPtr`, and
+    /// must outlive the signal handler's installation.
+    ///
+    /// [`Poll`]: crate::Poll
+    /// [`wake`]: Waker::wake
+    /// [`Poll::poll`]: crate::Poll::poll
+    #[cfg(unix)]
+    pub fn wake_from_signal_handler(&self) -> io::Result<()> {
+        self.inner.wake_from_signal_handler()
+    }
+}
+
+/// Number of bits in a single word of [`PayloadWaker`]'s payload set.
+const WORD_BITS: usize = 32;
+
+/// A [`Waker`] that carries a small payload.
+///
+/// Each call to [`wake_with`] wakes the [`Poll`] associated with the waker,
+/// like [`Waker::wake`], and stores the `u8` value it's called with. After
+/// [`Poll::poll`] returns an event for the waker's token the stored values can
+/// be retrieved using [`drain_payloads`]. This allows a single waker to be used
+/// for multiple wakeup reasons.
+///
+/// [`wake_with`]: PayloadWaker::wake_with
+/// [`Poll`]: crate::Poll
+/// [`Poll::poll`]: crate::Poll::poll
+/// [`drain_payloads`]: PayloadWaker::drain_payloads
+///
+/// # Notes
+///
+/// The payloads are stored as a set, waking multiple times with the same
+/// value before the payloads are drained only returns the value once. The
+/// order in which values are passed to `wake_with` is not preserved either,
+/// payloads are always returned in ascending order.
+///
+/// The same notes as for [`Waker`] apply.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use mio::{Events, PayloadWaker, Poll, Token};
+///
+/// const WAKE_TOKEN: Token = Token(10);
+/// const SHUTDOWN: u8 = 1;
+/// const RELOAD: u8 = 2;
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(2);
+///
+/// let waker = PayloadWaker::new(poll.registry(), WAKE_TOKEN)?;
+/// waker.wake_with(RELOAD)?;
+///
+/// poll.poll(&mut events, None)?;
+/// for event in &events {
+///     if event.token() == WAKE_TOKEN {
+///         for payload in waker.drain_payloads() {
+///             match payload {
+///                 SHUTDOWN => println!("shutting down"),
+///                 RELOAD => println!("reloading"),
+///                 _ => unreachable!(),
+///             }
+///         }
+///     }
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PayloadWaker {
+    waker: Waker,
+    /// Set of payloads, bit `n` is set if `wake_with(n)` was called.
+    payloads: [AtomicU32; 256 / WORD_BITS],
+}
+
+impl PayloadWaker {
+    /// Create a new `PayloadWaker`.
+    pub fn new(registry: &Registry, token: Token) -> io::Result<PayloadWaker> {
+        Waker::new(registry, token).map(|waker| PayloadWaker {
+            waker,
+            payloads: Default::default(),
+        })
+    }
+
+    /// Wake up the [`Poll`] associated with this `PayloadWaker`, storing
+    /// `payload` to be returned by [`drain_payloads`].
+    ///
+    /// [`Poll`]: crate::Poll
+    /// [`drain_payloads`]: PayloadWaker::drain_payloads
+    pub fn wake_with(&self, payload: u8) -> io::Result<()> {
+        let payload = payload as usize;
+        self.payloads[payload / WORD_BITS].fetch_or(1 << (payload % WORD_BITS), Ordering::Release);
+        self.waker.wake()
+    }
+
+    /// Returns an iterator over all payloads stored since the last call to
+    /// `drain_payloads`, removing them.
+    pub fn drain_payloads(&self) -> Payloads {
+        let mut words = [0; 256 / WORD_BITS];
+        for (word, payloads) in words.iter_mut().zip(self.payloads.iter()) {
+            *word = payloads.swap(0, Ordering::Acquire);
+        }
+        Payloads { words, pos: 0 }
+    }
+}
+
+/// Iterator over the payloads of a [`PayloadWaker`].
+///
+/// This struct is created by [`PayloadWaker::drain_payloads`].
+#[derive(Debug, Clone)]
+pub struct Payloads {
+    words: [u32; 256 / WORD_BITS],
+    pos: usize,
+}
+
+impl Iterator for Payloads {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        while self.pos < 256 {
+            let payload = self.pos;
+            self.pos += 1;
+            if self.words[payload / WORD_BITS] & (1 << (payload % WORD_BITS)) != 0 {
+                return Some(payload as u8);
+            }
+        }
+        None
+    }
+}
+
+/// Maximum number of reasons a single [`WakerSet`] supports.
+const MAX_REASONS: usize = 32;
+
+/// A [`Waker`] shared by multiple, named wake reasons.
+///
+/// A `WakerSet` owns a single [`Waker`], and thus a single token, but hands
+/// out a [`WakeReason`] for every reason to wake the [`Poll`], e.g. "shutdown"
+/// or "new work". Waking using a reason wakes the [`Poll`] and marks the
+/// reason as signaled. After [`Poll::poll`] returns an event for the token,
+/// [`take_signaled`] returns the names of the reasons that were signaled.
+///
+/// [`Poll`]: crate::Poll
+/// [`Poll::poll`]: crate::Poll::poll
+/// [`take_signaled`]: WakerSet::take_signaled
+///
+/// # Notes
+///
+/// A `WakerSet` supports up to 32 reasons. Like wake ups, signaled reasons
+/// coalesce: waking with the same reason multiple times before calling
+/// [`take_signaled`] returns the reason only once.
+///
+/// The same notes as for [`Waker`] apply.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use mio::{Events, Poll, Token, WakerSet};
+///
+/// const WAKE_TOKEN: Token = Token(10);
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(2);
+///
+/// let wakers = WakerSet::new(poll.registry(), WAKE_TOKEN)?;
+/// let shutdown = wakers.reason("shutdown")?;
+/// let reload = wakers.reason("reload")?;
+///
+/// reload.wake()?;
+///
+/// poll.poll(&mut events, None)?;
+/// for event in &events {
+///     if event.token() == WAKE_TOKEN {
+///         for reason in wakers.take_signaled() {
+///             println!("woken because of {}", reason);
+///         }
+///     }
+/// }
+/// # drop(shutdown);
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct WakerSet {
+    inner: Arc<WakerSetInner>,
+}
+
+#[derive(Debug)]
+struct WakerSetInner {
+    waker: Waker,
+    /// Bit `n` is set if the reason at index `n` in `names` was signaled.
+    signaled: AtomicU32,
+    names: Mutex<Vec<String>>,
+}
+
+/// A single reason to wake a [`WakerSet`].
+///
+/// This struct is created by [`WakerSet::reason`]. It can be cloned, or sent
+/// to another thread, to wake the [`Poll`] associated with the set.
+///
+/// [`Poll`]: crate::Poll
+#[derive(Clone, Debug)]
+pub struct WakeReason {
+    inner: Arc<WakerSetInner>,
+    bit: u32,
+}
+
+impl WakerSet {
+    /// Create a new `WakerSet`.
+    pub fn new(registry: &Registry, token: Token) -> io::Result<WakerSet> {
+        Waker::new(registry, token).map(|waker| WakerSet {
+            inner: Arc::new(WakerSetInner {
+                waker,
+                signaled: AtomicU32::new(0),
+                names: Mutex::new(Vec::new()),
+            }),
+        })
+    }
+
+    /// Returns the wake reason named `name`, adding it to the set if it
+    /// doesn't exist yet.
+    ///
+    /// Returns an error of kind `InvalidInput` if the set already contains the
+    /// maximum of 32 reasons and `name` is not one of them.
+    pub fn reason(&self, name: &str) -> io::Result<WakeReason> {
+        let mut names = self.inner.names.lock().unwrap();
+        let index = match names.iter().position(|n| n == name) {
+            Some(index) => index,
+            None if names.len() < MAX_REASONS => {
+                names.push(name.to_owned());
+                names.len() - 1
+            }
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "`WakerSet` supports at most 32 reasons",
+                ))
+            }
+        };
+        Ok(WakeReason {
+            inner: self.inner.clone(),
+            bit: 1 << index,
+        })
+    }
+
+    /// Returns the names of the reasons signaled since the last call to
+    /// `take_signaled`, clearing them.
+    ///
+    /// The names are returned in the order in which the reasons were added
+    /// to the set.
+    pub fn take_signaled(&self) -> Vec<String> {
+        let signaled = self.inner.signaled.swap(0, Ordering::Acquire);
+        let names = self.inner.names.lock().unwrap();
+        names
+            .iter()
+            .enumerate()
+            .filter(|&(index, _)| signaled & (1 << index) != 0)
+            .map(|(_, name)| name.clone())
+            .collect()
+    }
+}
+
+impl WakeReason {
+    /// Wake up the [`Poll`] associated with the [`WakerSet`], marking this
+    /// reason as signaled.
+    ///
+    /// [`Poll`]: crate::Poll
+    pub fn wake(&self) -> io::Result<()> {
+        self.inner.signaled.fetch_or(self.bit, Ordering::Release);
+        self.inner.waker.wake()
+    }
 }
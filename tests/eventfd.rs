@@ -0,0 +1,67 @@
+#![cfg(any(target_os = "android", target_os = "linux"))]
+
+use mio::unix::EventFd;
+use mio::{Interests, Token, Trigger};
+
+mod util;
+
+use util::{
+    assert_send, assert_sync, assert_would_block, expect_events, expect_no_events, init_with_poll,
+    ExpectEvent,
+};
+
+const SEMAPHORE: Token = Token(0);
+
+#[test]
+fn is_send_and_sync() {
+    assert_send::<EventFd>();
+    assert_sync::<EventFd>();
+}
+
+#[test]
+fn semaphore() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let semaphore = EventFd::new().unwrap();
+    poll.registry()
+        .register_with_mode(&semaphore, SEMAPHORE, Interests::READABLE, Trigger::Level)
+        .unwrap();
+    assert_would_block(semaphore.read());
+
+    semaphore.post(3).unwrap();
+    for _ in 0..3 {
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(SEMAPHORE, Interests::READABLE)],
+        );
+        // Every read decrements the counter by one.
+        assert_eq!(semaphore.read().unwrap(), 1);
+    }
+
+    expect_no_events(&mut poll, &mut events);
+    assert_would_block(semaphore.read());
+}
+
+#[test]
+fn semaphore_edge_triggered() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let semaphore = EventFd::new().unwrap();
+    poll.registry()
+        .register(&semaphore, SEMAPHORE, Interests::READABLE)
+        .unwrap();
+
+    semaphore.post(2).unwrap();
+    semaphore.post(1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(SEMAPHORE, Interests::READABLE)],
+    );
+    for _ in 0..3 {
+        assert_eq!(semaphore.read().unwrap(), 1);
+    }
+    assert_would_block(semaphore.read());
+    expect_no_events(&mut poll, &mut events);
+}
@@ -8,6 +8,44 @@ fn is_tests() {
     assert!(Interests::WRITABLE.is_writable());
     assert!(!Interests::WRITABLE.is_aio());
     assert!(!Interests::WRITABLE.is_lio());
+    assert!(!Interests::WRITABLE.is_priority());
+    #[cfg(any(target_os = "android", target_os = "linux", target_os = "solaris"))]
+    {
+        assert!(Interests::PRIORITY.is_priority());
+        assert!(!Interests::PRIORITY.is_readable());
+    }
+}
+
+#[test]
+fn is_tests_combined() {
+    let interests = Interests::READABLE | Interests::WRITABLE;
+    assert!(interests.is_readable());
+    assert!(interests.is_writable());
+    assert!(!interests.is_priority());
+
+    // The predicates can be used in constants.
+    const READABLE: bool = Interests::WRITABLE.add(Interests::READABLE).is_readable();
+    const WRITABLE: bool = Interests::READABLE.is_writable();
+    assert!(READABLE);
+    assert!(!WRITABLE);
+}
+
+#[test]
+fn all() {
+    let interests = Interests::all();
+    assert!(interests.is_readable());
+    assert!(interests.is_writable());
+    assert!(!interests.is_aio());
+    assert!(!interests.is_lio());
+    #[cfg(any(target_os = "android", target_os = "linux", target_os = "solaris"))]
+    assert!(interests.is_priority());
+    #[cfg(not(any(target_os = "android", target_os = "linux", target_os = "solaris")))]
+    assert!(!interests.is_priority());
+
+    assert_eq!(
+        Interests::all() | Interests::READABLE | Interests::WRITABLE,
+        Interests::all()
+    );
 }
 
 #[test]
@@ -17,6 +55,26 @@ fn bit_or() {
     assert!(interests.is_writable());
 }
 
+#[test]
+fn remove() {
+    let interests = Interests::READABLE | Interests::WRITABLE;
+    assert_eq!(
+        interests.remove(Interests::WRITABLE),
+        Some(Interests::READABLE)
+    );
+    assert_eq!(
+        interests.remove(Interests::READABLE),
+        Some(Interests::WRITABLE)
+    );
+    assert_eq!(interests.remove(interests), None);
+    assert_eq!(Interests::READABLE.remove(Interests::READABLE), None);
+    // Removing interests not in the set is a no-op.
+    assert_eq!(
+        Interests::READABLE.remove(Interests::WRITABLE),
+        Some(Interests::READABLE)
+    );
+}
+
 #[test]
 fn fmt_debug() {
     assert_eq!(format!("{:?}", Interests::READABLE), "READABLE");
@@ -38,4 +96,38 @@ fn fmt_debug() {
     {
         assert_eq!(format!("{:?}", Interests::LIO), "LIO");
     }
+    #[cfg(any(target_os = "android", target_os = "linux", target_os = "solaris"))]
+    {
+        assert_eq!(format!("{:?}", Interests::PRIORITY), "PRIORITY");
+        assert_eq!(
+            format!("{:?}", Interests::READABLE | Interests::PRIORITY),
+            "READABLE | PRIORITY"
+        );
+    }
+}
+
+#[test]
+fn fmt_display() {
+    assert_eq!(format!("{}", Interests::READABLE), "READABLE");
+    assert_eq!(
+        format!("{}", Interests::READABLE | Interests::WRITABLE),
+        "READABLE | WRITABLE"
+    );
+}
+
+#[test]
+fn iter() {
+    let interests = Interests::READABLE | Interests::WRITABLE;
+    let all: Vec<Interests> = interests.iter().collect();
+    assert_eq!(all, vec![Interests::READABLE, Interests::WRITABLE]);
+
+    let all: Vec<Interests> = Interests::WRITABLE.iter().collect();
+    assert_eq!(all, vec![Interests::WRITABLE]);
+
+    #[cfg(any(target_os = "android", target_os = "linux", target_os = "solaris"))]
+    {
+        let interests = Interests::READABLE | Interests::PRIORITY;
+        let all: Vec<Interests> = interests.iter().collect();
+        assert_eq!(all, vec![Interests::READABLE, Interests::PRIORITY]);
+    }
 }
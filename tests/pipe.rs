@@ -0,0 +1,188 @@
+#![cfg(unix)]
+
+use std::env;
+use std::io::{Read, Write};
+use std::panic;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use mio::unix::pipe::{self, Receiver, Sender};
+use mio::{Interests, Token};
+
+mod util;
+
+use util::{
+    assert_send, assert_sync, assert_would_block, expect_events, expect_no_events, init_with_poll,
+    ExpectEvent,
+};
+
+const RECEIVER: Token = Token(0);
+const SENDER: Token = Token(1);
+
+const DATA1: &[u8] = b"Hello pipe!";
+
+#[test]
+fn is_send_and_sync() {
+    assert_send::<Sender>();
+    assert_sync::<Sender>();
+
+    assert_send::<Receiver>();
+    assert_sync::<Receiver>();
+}
+
+#[test]
+fn smoke() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut sender, mut receiver) = pipe::new().unwrap();
+
+    poll.registry()
+        .register(&receiver, RECEIVER, Interests::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&sender, SENDER, Interests::WRITABLE)
+        .unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(SENDER, Interests::WRITABLE)],
+    );
+
+    let mut buf = [0; 20];
+    assert_would_block(receiver.read(&mut buf));
+
+    let n = sender.write(DATA1).unwrap();
+    assert_eq!(n, DATA1.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(RECEIVER, Interests::READABLE)],
+    );
+
+    let n = receiver.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_would_block(receiver.read(&mut buf));
+}
+
+#[test]
+fn event_when_sender_is_dropped() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (sender, receiver) = pipe::new().unwrap();
+    poll.registry()
+        .register(&receiver, RECEIVER, Interests::READABLE)
+        .unwrap();
+
+    expect_no_events(&mut poll, &mut events);
+
+    drop(sender);
+
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    let event = events.iter().next().expect("no events");
+    assert_eq!(event.token(), RECEIVER);
+    assert!(event.is_read_closed());
+
+    // Reading returns end of file.
+    let mut buf = [0; 20];
+    assert_eq!((&receiver).read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+fn from_child_process_io() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let mut child = Command::new("cat")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start `cat` process");
+
+    let mut sender = Sender::from(child.stdin.take().unwrap());
+    sender.set_nonblocking(true).unwrap();
+    let mut receiver = Receiver::from(child.stdout.take().unwrap());
+    receiver.set_nonblocking(true).unwrap();
+
+    poll.registry()
+        .register(&receiver, RECEIVER, Interests::READABLE)
+        .unwrap();
+
+    let n = sender.write(DATA1).unwrap();
+    assert_eq!(n, DATA1.len());
+    drop(sender);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(RECEIVER, Interests::READABLE)],
+    );
+
+    let mut buf = [0; 20];
+    let n = receiver.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+
+    assert!(child.wait().unwrap().success());
+}
+
+/// Environment variable set when `recreate_poll_after_fork` re-executes the
+/// test binary to run the test in a single-threaded process.
+const FORK_TEST_ENV: &str = "MIO_TEST_RECREATE_POLL_AFTER_FORK";
+
+#[test]
+fn recreate_poll_after_fork() {
+    // Forking the multithreaded test harness could deadlock the child on a lock
+    // held by another thread, e.g. in `malloc`. So instead run the test in a
+    // new process, which runs only this test.
+    if env::var_os(FORK_TEST_ENV).is_none() {
+        let status = Command::new(env::current_exe().unwrap())
+            .args(&["recreate_poll_after_fork", "--exact", "--test-threads=1"])
+            .env(FORK_TEST_ENV, "1")
+            .status()
+            .expect("failed to start test process");
+        assert!(status.success(), "test process failed: {}", status);
+        return;
+    }
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let (_sender1, receiver1) = pipe::new().unwrap();
+    poll.registry()
+        .register(&receiver1, RECEIVER, Interests::READABLE)
+        .unwrap();
+    let (mut sender2, receiver2) = pipe::new().unwrap();
+
+    match unsafe { libc::fork() } {
+        -1 => panic!("unable to fork: {}", std::io::Error::last_os_error()),
+        0 => {
+            // Never return to the test harness from the child process.
+            let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                poll.recreate_after_fork().unwrap();
+                poll.registry()
+                    .register(&receiver2, SENDER, Interests::READABLE)
+                    .unwrap();
+
+                sender2.write_all(DATA1).unwrap();
+                expect_events(
+                    &mut poll,
+                    &mut events,
+                    vec![ExpectEvent::new(SENDER, Interests::READABLE)],
+                );
+                // `receiver1` isn't registered with the new selector.
+                assert!(events.iter().all(|event| event.token() == SENDER));
+            }));
+            unsafe { libc::_exit(if result.is_ok() { 0 } else { 1 }) };
+        }
+        pid => {
+            let mut status = 0;
+            assert_eq!(unsafe { libc::waitpid(pid, &mut status, 0) }, pid);
+            // Zero means the child exited normally with a zero exit code.
+            assert_eq!(status, 0, "child process failed");
+        }
+    }
+
+    // The parent's selector is unaffected by the child registering
+    // `receiver2`, even though it's readable now.
+    expect_no_events(&mut poll, &mut events);
+}
@@ -1,15 +1,19 @@
 use mio::net::{TcpListener, TcpStream};
 use mio::{event, Events, Interests, Poll, Registry, Token};
 
+use std::io::Write;
 use std::net;
 use std::sync::{Arc, Barrier, Mutex};
 use std::thread::{self, sleep};
-use std::time::Duration;
-use std::{fmt, io};
+use std::time::{Duration, Instant};
+use std::{fmt, io, ptr};
 
 mod util;
 
-use util::{any_local_address, assert_send, assert_sync, init};
+use util::{
+    any_local_address, assert_send, assert_sync, expect_events, expect_no_events, init,
+    init_with_poll, ExpectEvent,
+};
 
 #[test]
 fn is_send_and_sync() {
@@ -83,6 +87,395 @@ fn zero_duration_polls_events() {
     drop(listener);
 }
 
+#[test]
+fn poll_deadline_in_the_past() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+
+    // Subtracting from `Instant::now()` could underflow, so take the deadline
+    // first and let it pass.
+    let deadline = Instant::now();
+    sleep(Duration::from_millis(10));
+    let start = Instant::now();
+    poll.poll_deadline(&mut events, deadline).unwrap();
+    assert!(start.elapsed() < Duration::from_millis(100));
+    assert!(events.is_empty());
+}
+
+#[test]
+fn poll_deadline_waits_until_deadline() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+
+    let deadline = Instant::now() + Duration::from_millis(50);
+    poll.poll_deadline(&mut events, deadline).unwrap();
+    assert!(Instant::now() >= deadline);
+    assert!(events.is_empty());
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn poll_sub_millisecond_timeout_rounds_up() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // epoll only supports millisecond timeouts, the timeout must be rounded up
+    // rather than down to zero.
+    let start = Instant::now();
+    poll.poll(&mut events, Some(Duration::from_micros(100)))
+        .unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(1));
+    assert!(events.is_empty());
+}
+
+#[test]
+fn events_len_capacity_and_clear() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(128);
+    assert!(events.capacity() >= 128);
+    assert_eq!(events.len(), 0);
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&stream, Token(0), Interests::WRITABLE)
+        .unwrap();
+
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    assert_eq!(events.len(), events.iter().count());
+    assert!(events.len() >= 1);
+
+    let capacity = events.capacity();
+    events.clear();
+    assert_eq!(events.len(), 0);
+    assert!(events.is_empty());
+    assert_eq!(events.capacity(), capacity);
+}
+
+#[test]
+fn events_drain() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let streams: Vec<TcpStream> = (0..3)
+        .map(|n| {
+            let stream = TcpStream::connect(addr).unwrap();
+            poll.registry()
+                .register(&stream, Token(n), Interests::WRITABLE)
+                .unwrap();
+            stream
+        })
+        .collect();
+    sleep(Duration::from_millis(10));
+
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    let expected: Vec<Token> = events.iter().map(|event| event.token()).collect();
+    assert!(!expected.is_empty());
+
+    let drained: Vec<event::Event> = events.drain().collect();
+    let got: Vec<Token> = drained.iter().map(|event| event.token()).collect();
+    assert_eq!(got, expected);
+    assert!(events.is_empty());
+    assert_eq!(events.capacity(), 16);
+
+    // Dropping the iterator early also removes all events.
+    poll.registry()
+        .reregister(&streams[0], Token(0), Interests::WRITABLE)
+        .unwrap();
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    assert!(!events.is_empty());
+    drop(events.drain());
+    assert!(events.is_empty());
+
+    drop(listener);
+}
+
+#[test]
+fn events_iter_rotating() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let streams: Vec<TcpStream> = (0..4)
+        .map(|n| {
+            let stream = TcpStream::connect(addr).unwrap();
+            poll.registry()
+                .register(&stream, Token(n), Interests::WRITABLE)
+                .unwrap();
+            stream
+        })
+        .collect();
+
+    // The streams can become writable in different calls to poll, so keep
+    // appending events until there's one for every stream.
+    for _ in 0..3 {
+        poll.poll_append(&mut events, Some(Duration::from_millis(500)))
+            .unwrap();
+        if events.len() >= streams.len() {
+            break;
+        }
+    }
+    let expected: Vec<Token> = events.iter().map(|event| event.token()).collect();
+    assert!(!expected.is_empty());
+
+    // Every event is yielded once per iterator, but each iterator starts at
+    // the next event.
+    let mut first_counts = vec![0; streams.len()];
+    for n in 0..expected.len() * 10 {
+        let got: Vec<Token> = events.iter_rotating().map(|event| event.token()).collect();
+        assert_eq!(got.len(), expected.len());
+        assert_eq!(got[0], expected[n % expected.len()]);
+        let mut sorted = got.clone();
+        sorted.sort();
+        let mut sorted_expected = expected.clone();
+        sorted_expected.sort();
+        assert_eq!(sorted, sorted_expected);
+        first_counts[got[0].0] += 1;
+    }
+    for token in &expected {
+        assert_eq!(first_counts[token.0], 10);
+    }
+
+    events.clear();
+    assert_eq!(events.iter_rotating().count(), 0);
+
+    drop(listener);
+}
+
+#[test]
+fn events_get() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(16);
+
+    assert!(events.get(0).is_none());
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let streams: Vec<TcpStream> = (0..3)
+        .map(|n| {
+            let stream = TcpStream::connect(addr).unwrap();
+            poll.registry()
+                .register(&stream, Token(n), Interests::WRITABLE)
+                .unwrap();
+            stream
+        })
+        .collect();
+
+    // The streams can become writable in different calls to poll, so keep
+    // appending events until there's one for every stream.
+    for _ in 0..3 {
+        poll.poll_append(&mut events, Some(Duration::from_millis(500)))
+            .unwrap();
+        if events.len() >= streams.len() {
+            break;
+        }
+    }
+    assert!(!events.is_empty());
+
+    let first = events.iter().next().unwrap();
+    assert!(ptr::eq(events.get(0).unwrap(), first));
+    for (index, event) in events.iter().enumerate() {
+        assert!(ptr::eq(events.get(index).unwrap(), event));
+    }
+    assert!(events.get(events.len()).is_none());
+
+    events.clear();
+    assert!(events.get(0).is_none());
+
+    drop(streams);
+}
+
+#[test]
+fn events_iter_ready() {
+    let (mut poll, mut events) = init_with_poll();
+
+    assert_eq!(events.iter_ready().count(), 0);
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&stream, Token(0), Interests::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(Token(0), Interests::WRITABLE)],
+    );
+
+    let ready: Vec<(Token, Interests)> = events.iter_ready().collect();
+    assert_eq!(ready, vec![(Token(0), Interests::WRITABLE)]);
+
+    drop(stream);
+}
+
+#[test]
+fn poll_count() {
+    let (mut poll, mut events) = init_with_poll();
+
+    assert_eq!(
+        poll.poll_count(&mut events, Some(Duration::from_millis(10)))
+            .unwrap(),
+        0
+    );
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let streams: Vec<TcpStream> = (0..2)
+        .map(|n| {
+            let stream = TcpStream::connect(addr).unwrap();
+            poll.registry()
+                .register(&stream, Token(n), Interests::WRITABLE)
+                .unwrap();
+            stream
+        })
+        .collect();
+
+    // The events could be returned by multiple polls.
+    let mut total = 0;
+    for _ in 0..3 {
+        let n = poll
+            .poll_count(&mut events, Some(Duration::from_millis(500)))
+            .unwrap();
+        assert_eq!(n, events.len());
+        total += n;
+        if total >= 2 {
+            break;
+        }
+    }
+    assert_eq!(total, 2);
+
+    drop(streams);
+}
+
+#[test]
+fn poll_zero_capacity_events() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(0);
+
+    let err = poll
+        .poll(&mut events, Some(Duration::from_millis(0)))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    let err = poll
+        .poll_append(&mut events, Some(Duration::from_millis(0)))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn poll_append() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(2);
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let connect = |poll: &Poll, n| {
+        let stream = TcpStream::connect(addr).unwrap();
+        poll.registry()
+            .register(&stream, Token(n), Interests::WRITABLE)
+            .unwrap();
+        stream
+    };
+    let tokens = |events: &Events| events.iter().map(|e| e.token()).collect::<Vec<_>>();
+
+    let stream0 = connect(&poll, 0);
+    poll.poll_append(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    assert_eq!(tokens(&events), vec![Token(0)]);
+
+    let stream1 = connect(&poll, 1);
+    let stream2 = connect(&poll, 2);
+    sleep(Duration::from_millis(10));
+
+    // Only one of the two new events fits.
+    poll.poll_append(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    let got = tokens(&events);
+    assert_eq!(got.len(), 2);
+    assert_eq!(got[0], Token(0));
+
+    // A full `Events` should return without waiting or dropping any events.
+    poll.poll_append(&mut events, None).unwrap();
+    assert_eq!(tokens(&events), got);
+
+    // The remaining event should be returned by the next poll.
+    let expected = if got[1] == Token(1) {
+        Token(2)
+    } else {
+        Token(1)
+    };
+    events.clear();
+    poll.poll_append(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    assert_eq!(tokens(&events), vec![expected]);
+
+    drop((stream0, stream1, stream2));
+    drop(listener);
+}
+
+#[test]
+#[cfg(feature = "poll-stats")]
+fn poll_stats() {
+    init();
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(1);
+    assert_eq!(poll.stats(), mio::PollStats::default());
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let streams: Vec<TcpStream> = (0..3)
+        .map(|n| {
+            let stream = TcpStream::connect(addr).unwrap();
+            poll.registry()
+                .register(&stream, Token(n), Interests::WRITABLE)
+                .unwrap();
+            stream
+        })
+        .collect();
+    sleep(Duration::from_millis(10));
+
+    // Every poll can only return one of the three events.
+    for _ in 0..3 {
+        poll.poll(&mut events, Some(Duration::from_secs(1)))
+            .unwrap();
+    }
+    // Nothing fits in a full `Events`.
+    poll.poll_append(&mut events, None).unwrap();
+
+    let stats = poll.stats();
+    assert_eq!(stats.polls, 4);
+    assert_eq!(stats.events_returned, 3);
+    assert_eq!(stats.buffer_full_count, 4);
+
+    drop(streams);
+    drop(listener);
+}
+
 #[test]
 fn poll_closes_fd() {
     init();
@@ -197,6 +590,98 @@ fn registry_behind_arc() {
     handle2.join().unwrap();
 }
 
+#[test]
+fn registry_try_clone_register_from_thread() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let registry = poll.registry().try_clone().unwrap();
+    let handle = thread::spawn(move || {
+        let listener = TcpListener::bind(any_local_address()).unwrap();
+        registry
+            .register(&listener, Token(0), Interests::READABLE)
+            .unwrap();
+        listener
+    });
+    let listener = handle.join().unwrap();
+
+    let stream = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(Token(0), Interests::READABLE)],
+    );
+
+    drop(stream);
+}
+
+#[test]
+fn registry_deregister_from_thread_while_polling() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    let mut sender = net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (stream, _) = loop {
+        match listener.accept() {
+            Ok(accepted) => break accepted,
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+                sleep(Duration::from_millis(10))
+            }
+            Err(err) => panic!("unexpected error accepting connection: {}", err),
+        }
+    };
+    poll.registry()
+        .register(&stream, Token(0), Interests::READABLE)
+        .unwrap();
+
+    let registry = poll.registry().try_clone().unwrap();
+    let handle = thread::spawn(move || {
+        // Give the main thread some time to block in `poll`.
+        sleep(Duration::from_millis(100));
+        registry.deregister(&stream).unwrap();
+        // The source is deregistered, this must not wake up the poll.
+        sender.write_all(b"Hello world!").unwrap();
+        (stream, sender)
+    });
+
+    // On Windows the poll may return early, without events, because
+    // deregistering cancels the pending poll operation.
+    poll.poll(&mut events, Some(Duration::from_millis(500)))
+        .unwrap();
+    assert!(events.is_empty());
+
+    let (_stream, _sender) = handle.join().unwrap();
+    expect_no_events(&mut poll, &mut events);
+}
+
+#[test]
+fn register_scoped_deregisters_on_drop() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    {
+        let registration = poll
+            .registry()
+            .register_scoped(&listener, Token(0), Interests::READABLE)
+            .unwrap();
+        assert_eq!(poll.registry().source_count(), 1);
+
+        let stream = net::TcpStream::connect(addr).unwrap();
+        expect_events(
+            &mut poll,
+            &mut events,
+            vec![ExpectEvent::new(Token(0), Interests::READABLE)],
+        );
+        let _ = registration.source().accept().unwrap();
+        drop(stream);
+    }
+    assert_eq!(poll.registry().source_count(), 0);
+
+    // The listener is deregistered, so the connection doesn't trigger an event.
+    let _stream = net::TcpStream::connect(addr).unwrap();
+    expect_no_events(&mut poll, &mut events);
+}
+
 // On kqueue platforms registering twice (not *re*registering) works.
 #[test]
 #[cfg(any(target_os = "linux", target_os = "windows"))]
@@ -259,6 +744,42 @@ impl event::Source for TestEventSource {
     }
 }
 
+#[test]
+fn registry_clone_stored_alongside_poll() {
+    let (mut poll, mut events) = init_with_poll();
+    let registry = poll.registry().try_clone().unwrap();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    registry
+        .register(&listener, Token(0), Interests::READABLE)
+        .unwrap();
+
+    let mut streams = Vec::new();
+    let mut accepted = Vec::new();
+    while accepted.len() < 3 {
+        streams.push(net::TcpStream::connect(addr).unwrap());
+        poll.poll(&mut events, Some(Duration::from_millis(500)))
+            .unwrap();
+        for event in &events {
+            if event.token() == Token(0) {
+                while let Ok((stream, _)) = listener.accept() {
+                    let token = Token(accepted.len() + 1);
+                    registry
+                        .register(&stream, token, Interests::READABLE)
+                        .unwrap();
+                    accepted.push(stream);
+                }
+            }
+        }
+    }
+
+    for stream in &accepted {
+        registry.deregister(stream).unwrap();
+    }
+    drop(streams);
+}
+
 #[test]
 fn poll_registration() {
     init();
@@ -342,6 +863,48 @@ fn poll_erroneous_registration() {
     assert_error(registry.deregister(&source), "deregister");
 }
 
+#[test]
+fn register_all_rolls_back_on_error() {
+    init();
+    let poll = Poll::new().unwrap();
+    let registry = poll.registry();
+
+    let first = TestEventSource::new();
+    let second = ErroneousTestEventSource;
+    let third = TestEventSource::new();
+    assert_error(
+        registry.register_all(&[
+            (&first, Token(0), Interests::READABLE),
+            (&second, Token(1), Interests::READABLE),
+            (&third, Token(2), Interests::WRITABLE),
+        ]),
+        "register",
+    );
+
+    {
+        let first = first.0.lock().unwrap();
+        assert_eq!(first.registrations, vec![(Token(0), Interests::READABLE)]);
+        assert_eq!(first.deregister_count, 1);
+    }
+    {
+        let third = third.0.lock().unwrap();
+        assert!(third.registrations.is_empty());
+        assert_eq!(third.deregister_count, 0);
+    }
+
+    registry
+        .register_all(&[
+            (&first, Token(0), Interests::READABLE),
+            (&third, Token(2), Interests::WRITABLE),
+        ])
+        .unwrap();
+    assert_eq!(first.0.lock().unwrap().registrations.len(), 2);
+    assert_eq!(
+        third.0.lock().unwrap().registrations,
+        vec![(Token(2), Interests::WRITABLE)]
+    );
+}
+
 /// Assert that `result` is an error and the formatted error (via
 /// `fmt::Display`) equals `expected_msg`.
 pub fn assert_error<T, E: fmt::Display>(result: Result<T, E>, expected_msg: &str) {
@@ -355,3 +918,323 @@ pub fn assert_error<T, E: fmt::Display>(result: Result<T, E>, expected_msg: &str
         ),
     }
 }
+
+#[test]
+fn registry_source_count() {
+    init();
+    let poll = Poll::new().unwrap();
+    let registry = poll.registry();
+    assert_eq!(registry.source_count(), 0);
+
+    let sources = [
+        TestEventSource::new(),
+        TestEventSource::new(),
+        TestEventSource::new(),
+    ];
+    for (n, source) in sources.iter().enumerate() {
+        registry
+            .register(source, Token(n), Interests::READABLE)
+            .unwrap();
+    }
+    assert_eq!(registry.source_count(), 3);
+
+    registry
+        .reregister(&sources[0], Token(0), Interests::WRITABLE)
+        .unwrap();
+    assert_eq!(registry.source_count(), 3);
+
+    registry.deregister(&sources[1]).unwrap();
+    assert_eq!(registry.source_count(), 2);
+
+    // Failed (de)registrations aren't counted.
+    let erroneous = ErroneousTestEventSource;
+    assert!(registry
+        .register(&erroneous, Token(10), Interests::READABLE)
+        .is_err());
+    assert!(registry.deregister(&erroneous).is_err());
+    assert_eq!(registry.source_count(), 2);
+
+    // The count is shared with cloned registries.
+    let cloned = registry.try_clone().unwrap();
+    cloned.deregister(&sources[0]).unwrap();
+    assert_eq!(registry.source_count(), 1);
+    assert_eq!(cloned.source_count(), 1);
+
+    // The count never underflows.
+    for source in &sources {
+        registry.deregister(source).unwrap();
+    }
+    assert_eq!(registry.source_count(), 0);
+}
+
+#[test]
+#[cfg(unix)]
+fn poll_clear() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let stream1 = TcpStream::connect(addr).unwrap();
+    let stream2 = TcpStream::connect(addr).unwrap();
+    poll.registry()
+        .register(&stream1, Token(1), Interests::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&stream2, Token(2), Interests::READABLE)
+        .unwrap();
+    assert_eq!(poll.registry().source_count(), 2);
+
+    poll.clear().unwrap();
+    assert_eq!(poll.registry().source_count(), 0);
+
+    // Both streams are readable, but no longer registered.
+    for _ in 0..2 {
+        let (mut peer, _) = listener.accept().unwrap();
+        peer.write_all(b"hello").unwrap();
+    }
+    expect_no_events(&mut poll, &mut events);
+
+    // Sources can be registered again.
+    poll.registry()
+        .register(&stream1, Token(1), Interests::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(Token(1), Interests::READABLE)],
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn poll_clear_cloned_registry() {
+    let (mut poll, mut events) = init_with_poll();
+    let registry = poll.registry().try_clone().unwrap();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    registry
+        .register(&stream, Token(1), Interests::READABLE)
+        .unwrap();
+    assert_eq!(poll.registry().source_count(), 1);
+
+    // Registrations made using the cloned registry are dropped as well.
+    poll.clear().unwrap();
+    assert_eq!(registry.source_count(), 0);
+
+    let (mut peer, _) = listener.accept().unwrap();
+    peer.write_all(b"hello").unwrap();
+    expect_no_events(&mut poll, &mut events);
+}
+
+#[test]
+#[cfg(feature = "timers")]
+fn set_timeout() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let start = Instant::now();
+    poll.registry()
+        .set_timeout(Token(1), Duration::from_millis(30));
+
+    let event = loop {
+        poll.poll(&mut events, Some(Duration::from_secs(1)))
+            .unwrap();
+        if let Some(event) = events.iter().next() {
+            break *event;
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(1),
+            "timeout not fired"
+        );
+    };
+    let elapsed = start.elapsed();
+    assert!(
+        elapsed >= Duration::from_millis(30),
+        "elapsed: {:?}",
+        elapsed
+    );
+    assert!(
+        elapsed < Duration::from_millis(500),
+        "elapsed: {:?}",
+        elapsed
+    );
+    assert_eq!(event.token(), Token(1));
+    assert!(event.is_timeout());
+    assert!(!event.is_readable());
+    assert!(!event.is_writable());
+
+    // A timeout fires only once.
+    poll.poll(&mut events, Some(Duration::from_millis(50)))
+        .unwrap();
+    assert!(events.is_empty());
+}
+
+#[test]
+fn poll_timeout_ms() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let start = Instant::now();
+    poll.poll_timeout_ms(&mut events, Some(0)).unwrap();
+    assert!(start.elapsed() < Duration::from_millis(50));
+    assert!(events.is_empty());
+
+    let waker = poll.waker_handle(Token(10)).unwrap();
+    let handle = thread::spawn(move || {
+        sleep(Duration::from_millis(100));
+        waker.wake().unwrap();
+    });
+
+    let start = Instant::now();
+    poll.poll_timeout_ms(&mut events, None).unwrap();
+    // The waker thread started sleeping before `start`, so allow some slack.
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    let event = events.iter().next().expect("expected a waker event");
+    assert_eq!(event.token(), Token(10));
+    assert!(event.is_readable());
+    handle.join().unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn poll_nested_in_epoll() {
+    use std::os::unix::io::AsRawFd;
+
+    init();
+    let poll = Poll::new().unwrap();
+    let fd = poll.as_raw_fd();
+    assert!(fd >= 0);
+
+    let outer = unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) };
+    assert!(outer >= 0, "unable to create epoll instance");
+    let mut event = libc::epoll_event {
+        events: libc::EPOLLIN as u32,
+        u64: 1,
+    };
+    assert_eq!(
+        unsafe { libc::epoll_ctl(outer, libc::EPOLL_CTL_ADD, fd, &mut event) },
+        0
+    );
+
+    let mut ready = [libc::epoll_event { events: 0, u64: 0 }; 1];
+    let n = unsafe { libc::epoll_wait(outer, ready.as_mut_ptr(), 1, 0) };
+    assert_eq!(n, 0);
+
+    // Once `poll` has an event ready the nested fd becomes readable.
+    let waker = poll.waker_handle(Token(0)).unwrap();
+    waker.wake().unwrap();
+    let n = unsafe { libc::epoll_wait(outer, ready.as_mut_ptr(), 1, 1000) };
+    assert_eq!(n, 1);
+    let token = ready[0].u64;
+    assert_eq!(token, 1);
+
+    assert_eq!(unsafe { libc::close(outer) }, 0);
+}
+
+#[cfg(unix)]
+fn interrupt_after(delay: Duration) -> thread::JoinHandle<()> {
+    extern "C" fn handler(_: libc::c_int) {}
+
+    let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+    let handler: extern "C" fn(libc::c_int) = handler;
+    action.sa_sigaction = handler as libc::sighandler_t;
+    // No `SA_RESTART`, we want the system call to be interrupted.
+    action.sa_flags = 0;
+    assert_eq!(
+        unsafe { libc::sigaction(libc::SIGUSR1, &action, std::ptr::null_mut()) },
+        0
+    );
+
+    let thread = unsafe { libc::pthread_self() } as usize;
+    thread::spawn(move || {
+        sleep(delay);
+        let res = unsafe { libc::pthread_kill(thread as libc::pthread_t, libc::SIGUSR1) };
+        assert_eq!(res, 0);
+    })
+}
+
+#[test]
+#[cfg(unix)]
+fn poll_retries_on_interrupt() {
+    let (mut poll, mut events) = init_with_poll();
+
+    const TIMEOUT: Duration = Duration::from_millis(200);
+    let start = Instant::now();
+    let handle = interrupt_after(Duration::from_millis(150));
+    poll.poll(&mut events, Some(TIMEOUT)).unwrap();
+    let elapsed = start.elapsed();
+
+    assert!(events.is_empty());
+    // If the timeout was reset after the interrupt we would poll for at least
+    // 350 milliseconds.
+    assert!(
+        elapsed >= TIMEOUT && elapsed < TIMEOUT + Duration::from_millis(120),
+        "polled for {:?}",
+        elapsed
+    );
+    handle.join().unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn poll_append_retries_on_interrupt() {
+    let (mut poll, mut events) = init_with_poll();
+
+    const TIMEOUT: Duration = Duration::from_millis(200);
+    let start = Instant::now();
+    let handle = interrupt_after(Duration::from_millis(150));
+    poll.poll_append(&mut events, Some(TIMEOUT)).unwrap();
+    let elapsed = start.elapsed();
+
+    assert!(events.is_empty());
+    assert!(
+        elapsed >= TIMEOUT && elapsed < TIMEOUT + Duration::from_millis(120),
+        "polled for {:?}",
+        elapsed
+    );
+    handle.join().unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn poll_interruptible() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let start = Instant::now();
+    let handle = interrupt_after(Duration::from_millis(50));
+    let err = poll
+        .poll_interruptible(&mut events, Some(Duration::from_secs(5)))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+    assert!(start.elapsed() < Duration::from_secs(5));
+    handle.join().unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn is_fatal_poll_error() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let handle = interrupt_after(Duration::from_millis(50));
+    let err = poll
+        .poll_interruptible(&mut events, Some(Duration::from_secs(5)))
+        .unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EINTR));
+    assert!(!mio::is_fatal_poll_error(&err));
+    handle.join().unwrap();
+
+    assert!(!mio::is_fatal_poll_error(&io::Error::from_raw_os_error(
+        libc::ENOMEM
+    )));
+    assert!(mio::is_fatal_poll_error(&io::Error::from_raw_os_error(
+        libc::EBADF
+    )));
+
+    // Errors not originating from the OS are not fatal.
+    let err = poll
+        .poll(
+            &mut Events::with_capacity(0),
+            Some(Duration::from_millis(0)),
+        )
+        .unwrap_err();
+    assert!(!mio::is_fatal_poll_error(&err));
+}
@@ -264,3 +264,24 @@ fn registering_after_deregistering() {
         .unwrap();
     assert!(events.is_empty());
 }
+
+#[test]
+fn reregister_unregistered_source() {
+    init();
+
+    let poll = Poll::new().unwrap();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    let err = poll
+        .registry()
+        .reregister(&stream, Token(0), Interests::READABLE)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+    // A failed reregister must not prevent a later registration.
+    poll.registry()
+        .register(&stream, Token(0), Interests::READABLE)
+        .unwrap();
+}
@@ -0,0 +1,66 @@
+#![cfg(any(target_os = "android", target_os = "linux"))]
+
+use std::{mem, ptr};
+
+use mio::unix::SignalFd;
+use mio::{Interests, Token};
+
+mod util;
+
+use util::{
+    assert_send, assert_sync, expect_events, expect_no_events, init_with_poll, ExpectEvent,
+};
+
+const SIGNAL: Token = Token(0);
+
+#[test]
+fn is_send_and_sync() {
+    assert_send::<SignalFd>();
+    assert_sync::<SignalFd>();
+}
+
+#[test]
+fn receive_signal() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // The signal must be blocked to be delivered to the `SignalFd`. Only this
+    // thread blocks it, so the signal is raised in this thread.
+    let mut mask = unsafe { mem::zeroed() };
+    unsafe {
+        libc::sigemptyset(&mut mask);
+        libc::sigaddset(&mut mask, libc::SIGUSR1);
+        assert_eq!(
+            libc::pthread_sigmask(libc::SIG_BLOCK, &mask, ptr::null_mut()),
+            0
+        );
+    }
+
+    let signals = SignalFd::new(&[libc::SIGUSR1]).unwrap();
+    poll.registry()
+        .register(&signals, SIGNAL, Interests::READABLE)
+        .unwrap();
+    assert!(signals.read_signal().unwrap().is_none());
+    expect_no_events(&mut poll, &mut events);
+
+    assert_eq!(unsafe { libc::raise(libc::SIGUSR1) }, 0);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(SIGNAL, Interests::READABLE)],
+    );
+
+    let info = signals.read_signal().unwrap().expect("missing signal");
+    assert_eq!(info.signal(), libc::SIGUSR1);
+    assert_eq!(info.pid(), unsafe { libc::getpid() });
+    assert_eq!(info.uid(), unsafe { libc::getuid() });
+    assert!(signals.read_signal().unwrap().is_none());
+
+    unsafe {
+        libc::pthread_sigmask(libc::SIG_UNBLOCK, &mask, ptr::null_mut());
+    }
+}
+
+#[test]
+fn invalid_signal() {
+    assert!(SignalFd::new(&[-1]).is_err());
+}
@@ -0,0 +1,93 @@
+#![cfg(unix)]
+
+use std::os::unix::io::RawFd;
+
+use mio::unix::SourceFd;
+use mio::{Interests, Token};
+
+mod util;
+
+use util::{expect_events, expect_no_events, init_with_poll, ExpectEvent};
+
+const ID: Token = Token(0);
+
+const DATA: &[u8] = b"Hello SourceFd!";
+
+/// Create a non-blocking pipe using libc directly, returning the read and
+/// write end.
+fn raw_pipe() -> (RawFd, RawFd) {
+    let mut fds: [RawFd; 2] = [-1, -1];
+    assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+    for &fd in &fds {
+        assert_ne!(
+            unsafe { libc::fcntl(fd, libc::F_SETFL, libc::O_NONBLOCK) },
+            -1
+        );
+    }
+    (fds[0], fds[1])
+}
+
+fn write(fd: RawFd, data: &[u8]) {
+    let n = unsafe { libc::write(fd, data.as_ptr() as *const libc::c_void, data.len()) };
+    assert_eq!(n, data.len() as isize);
+}
+
+fn read(fd: RawFd) -> Vec<u8> {
+    let mut buf = [0; 64];
+    let n = unsafe { libc::read(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) };
+    assert!(n >= 0, "unable to read from pipe");
+    buf[..n as usize].to_vec()
+}
+
+fn close(fd: RawFd) {
+    assert_eq!(unsafe { libc::close(fd) }, 0);
+}
+
+#[test]
+fn register_raw_fd() {
+    let (mut poll, mut events) = init_with_poll();
+    let (receiver, sender) = raw_pipe();
+
+    poll.registry()
+        .register(&SourceFd(&receiver), ID, Interests::READABLE)
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    write(sender, DATA);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID, Interests::READABLE)],
+    );
+    assert_eq!(read(receiver), DATA);
+
+    close(receiver);
+    close(sender);
+}
+
+#[test]
+fn reregister_and_deregister_raw_fd() {
+    let (mut poll, mut events) = init_with_poll();
+    let (receiver, sender) = raw_pipe();
+
+    poll.registry()
+        .register(&SourceFd(&sender), ID, Interests::READABLE)
+        .unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    poll.registry()
+        .reregister(&SourceFd(&sender), ID, Interests::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID, Interests::WRITABLE)],
+    );
+
+    poll.registry().deregister(&SourceFd(&sender)).unwrap();
+    write(sender, DATA);
+    expect_no_events(&mut poll, &mut events);
+
+    close(receiver);
+    close(sender);
+}
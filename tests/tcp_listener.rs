@@ -1,11 +1,15 @@
 use std::io::{self, Read};
-use std::net::{self, SocketAddr};
+use std::net::{self, Ipv4Addr, SocketAddr};
 #[cfg(unix)]
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
 use std::sync::{Arc, Barrier};
 use std::thread;
+use std::time::Duration;
 
-use mio::net::TcpListener;
+use mio::net::{
+    is_transient_accept_error as net_is_transient_accept_error, TcpListener, TcpListenerOpts,
+    TcpStream,
+};
 use mio::{Interests, Poll, Token};
 
 mod util;
@@ -37,14 +41,23 @@ fn tcp_listener_ipv6() {
 #[test]
 fn tcp_listener_std() {
     smoke_test_tcp_listener(any_local_address(), |addr| {
+        // `std::net::TcpListener`s are blocking by default, `from_std` puts it
+        // in non-blocking mode.
         let listener = net::TcpListener::bind(addr).unwrap();
-        // `std::net::TcpListener`s are blocking by default, so make sure it is in
-        // non-blocking mode before wrapping in a Mio equivalent.
-        assert_ok!(listener.set_nonblocking(true));
-        Ok(TcpListener::from_std(listener))
+        TcpListener::from_std(listener)
     });
 }
 
+#[test]
+fn from_std_sets_non_blocking() {
+    init();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let listener = TcpListener::from_std(listener).unwrap();
+    // A blocking listener would block here forever.
+    assert_would_block(listener.accept());
+}
+
 fn smoke_test_tcp_listener<F>(addr: SocketAddr, make_listener: F)
 where
     F: FnOnce(SocketAddr) -> io::Result<TcpListener>,
@@ -343,6 +356,325 @@ fn try_clone_different_poll() {
     thread_handle2.join().expect("unable to join thread");
 }
 
+#[test]
+fn bind_with_backlog() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let opts = TcpListenerOpts {
+        backlog: 1,
+        ..TcpListenerOpts::default()
+    };
+    let listener = TcpListener::bind_with(any_local_address(), opts).unwrap();
+    let address = listener.local_addr().unwrap();
+
+    poll.registry()
+        .register(&listener, ID1, Interests::READABLE)
+        .unwrap();
+
+    const N_CONNECTIONS: usize = 2;
+    let barrier = Arc::new(Barrier::new(2));
+    let thread_barrier = barrier.clone();
+    let thread_handle = thread::spawn(move || {
+        // Make the connections without waiting for them to be accepted, the
+        // second one won't fit in the backlog.
+        let streams: Vec<net::TcpStream> = (0..N_CONNECTIONS)
+            .map(|_| net::TcpStream::connect(address).unwrap())
+            .collect();
+        thread_barrier.wait();
+        drop(streams);
+    });
+
+    let mut accepted = 0;
+    for _ in 0..10 {
+        poll.poll(&mut events, Some(std::time::Duration::from_millis(500)))
+            .unwrap();
+        if !events.is_empty() {
+            loop {
+                match listener.accept() {
+                    Ok(_) => accepted += 1,
+                    Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                    Err(err) => panic!("unexpected error accepting connection: {}", err),
+                }
+            }
+        }
+
+        if accepted == N_CONNECTIONS {
+            break;
+        }
+    }
+    assert_eq!(accepted, N_CONNECTIONS);
+
+    barrier.wait();
+    thread_handle.join().expect("unable to join thread");
+}
+
+#[test]
+#[cfg(not(any(windows, target_os = "solaris")))]
+fn bind_reuse_port() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let opts = TcpListenerOpts {
+        reuse_port: true,
+        ..TcpListenerOpts::default()
+    };
+    let listener1 = TcpListener::bind_with(any_local_address(), opts).unwrap();
+    let address = listener1.local_addr().unwrap();
+    let listener2 = TcpListener::bind_with(address, opts).unwrap();
+    assert_eq!(listener2.local_addr().unwrap(), address);
+
+    poll.registry()
+        .register(&listener1, ID1, Interests::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&listener2, ID2, Interests::READABLE)
+        .unwrap();
+
+    const N_CONNECTIONS: usize = 32;
+    let streams: Vec<net::TcpStream> = (0..N_CONNECTIONS)
+        .map(|_| net::TcpStream::connect(address).unwrap())
+        .collect();
+
+    let mut accepted = [0; 2];
+    for _ in 0..10 {
+        poll.poll(&mut events, Some(std::time::Duration::from_millis(500)))
+            .unwrap();
+        for event in events.iter() {
+            let (index, listener) = match event.token() {
+                ID1 => (0, &listener1),
+                ID2 => (1, &listener2),
+                token => panic!("unexpected token: {:?}", token),
+            };
+            loop {
+                match listener.accept() {
+                    Ok(_) => accepted[index] += 1,
+                    Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                    Err(err) => panic!("unexpected error accepting connection: {}", err),
+                }
+            }
+        }
+
+        if accepted[0] + accepted[1] == N_CONNECTIONS {
+            break;
+        }
+    }
+    assert_eq!(accepted[0] + accepted[1], N_CONNECTIONS);
+
+    // Linux balances the connections between the listeners, other platforms
+    // may deliver all connections to a single listener.
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    assert!(accepted[0] > 0 && accepted[1] > 0, "{:?}", accepted);
+
+    drop(streams);
+}
+
+#[test]
+#[cfg(windows)]
+fn bind_reuse_port_unsupported() {
+    init();
+
+    let opts = TcpListenerOpts {
+        reuse_port: true,
+        ..TcpListenerOpts::default()
+    };
+    assert!(TcpListener::bind_with(any_local_address(), opts).is_err());
+}
+
+#[test]
+#[cfg(unix)]
+fn accept_with_flags() {
+    fn flags(stream: &TcpStream) -> (bool, bool) {
+        let fd = stream.as_raw_fd();
+        let fd_flags = unsafe { libc::fcntl(fd, libc::F_GETFD) };
+        let status_flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+        assert!(fd_flags != -1 && status_flags != -1);
+        (
+            fd_flags & libc::FD_CLOEXEC != 0,
+            status_flags & libc::O_NONBLOCK != 0,
+        )
+    }
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+
+    for &(cloexec, nonblocking) in &[(false, true), (true, false), (false, false), (true, true)] {
+        let client = net::TcpStream::connect(address).unwrap();
+        // Give the connection time to show up in the accept queue.
+        let mut accepted = listener.accept_with_flags(cloexec, nonblocking);
+        for _ in 0..10 {
+            match accepted {
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+                    thread::sleep(Duration::from_millis(10));
+                    accepted = listener.accept_with_flags(cloexec, nonblocking);
+                }
+                _ => break,
+            }
+        }
+        let (stream, peer) = accepted.unwrap();
+        assert_eq!(peer, client.local_addr().unwrap());
+        assert_eq!(flags(&stream), (cloexec, nonblocking));
+    }
+
+    // `accept` sets both flags.
+    let _client = net::TcpStream::connect(address).unwrap();
+    thread::sleep(Duration::from_millis(10));
+    let (stream, _) = listener.accept().unwrap();
+    assert_eq!(flags(&stream), (true, true));
+}
+
+#[test]
+fn is_transient_accept_error() {
+    #[cfg(unix)]
+    let (transient, fatal) = (
+        [libc::ECONNABORTED, libc::EMFILE, libc::ENFILE],
+        [libc::EBADF, libc::EINVAL, libc::ENOTSOCK],
+    );
+    #[cfg(windows)]
+    let (transient, fatal) = ([10054, 10024, 10055], [10009, 10022, 10038]);
+
+    for &code in &transient {
+        let err = io::Error::from_raw_os_error(code);
+        assert!(net_is_transient_accept_error(&err), "{}", err);
+    }
+    for &code in &fatal {
+        let err = io::Error::from_raw_os_error(code);
+        assert!(!net_is_transient_accept_error(&err), "{}", err);
+    }
+    let err = io::Error::from(io::ErrorKind::WouldBlock);
+    assert!(!net_is_transient_accept_error(&err));
+}
+
+#[test]
+fn accept_after_reset_connection() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    poll.registry()
+        .register(&listener, ID1, Interests::READABLE)
+        .unwrap();
+
+    // Reset the first connection before it's accepted.
+    let stream = net::TcpStream::connect(address).unwrap();
+    let stream = TcpStream::from_std(stream).unwrap();
+    stream.set_linger(Some(Duration::from_secs(0))).unwrap();
+    drop(stream);
+
+    let stream = net::TcpStream::connect(address).unwrap();
+    let stream_address = stream.local_addr().unwrap();
+
+    // The listener must remain usable to accept the second connection.
+    'outer: loop {
+        poll.poll(&mut events, Some(Duration::from_millis(500)))
+            .unwrap();
+        assert!(!events.is_empty(), "no events for second connection");
+        loop {
+            match listener.accept() {
+                Ok((_, peer_address)) if peer_address == stream_address => break 'outer,
+                Ok(_) => continue,
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(ref err) if net_is_transient_accept_error(err) => continue,
+                Err(err) => panic!("unexpected error accepting connection: {}", err),
+            }
+        }
+    }
+
+    drop(stream);
+}
+
+#[test]
+// OpenBSD doesn't support dual-stack sockets.
+#[cfg(not(target_os = "openbsd"))]
+fn bind_dual_stack() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let opts = TcpListenerOpts {
+        only_v6: Some(false),
+        ..TcpListenerOpts::default()
+    };
+    let listener = TcpListener::bind_with("[::]:0".parse().unwrap(), opts).unwrap();
+    let port = listener.local_addr().unwrap().port();
+    poll.registry()
+        .register(&listener, ID1, Interests::READABLE)
+        .unwrap();
+
+    let stream = net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+
+    let (_, peer_address) = listener.accept().unwrap();
+    match peer_address {
+        SocketAddr::V6(address) => {
+            assert_eq!(address.ip().to_ipv4(), Some(Ipv4Addr::LOCALHOST));
+            assert_eq!(address.port(), stream.local_addr().unwrap().port());
+        }
+        SocketAddr::V4(address) => panic!("unexpected IPv4 peer address: {}", address),
+    }
+}
+
+#[test]
+fn bind_only_v6() {
+    let opts = TcpListenerOpts {
+        only_v6: Some(true),
+        ..TcpListenerOpts::default()
+    };
+    let listener = TcpListener::bind_with("[::]:0".parse().unwrap(), opts).unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    assert!(net::TcpStream::connect(("127.0.0.1", port)).is_err());
+    assert!(net::TcpStream::connect(("::1", port)).is_ok());
+
+    // Ignored for IPv4 addresses.
+    assert!(TcpListener::bind_with(any_local_address(), opts).is_ok());
+}
+
+#[test]
+fn accept_iter() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    poll.registry()
+        .register(&listener, ID1, Interests::READABLE)
+        .unwrap();
+
+    // Queue a number of connections before accepting any of them.
+    const N_CONNECTIONS: usize = 3;
+    let streams: Vec<net::TcpStream> = (0..N_CONNECTIONS)
+        .map(|_| net::TcpStream::connect(address).unwrap())
+        .collect();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+
+    // Connections may take a moment to show up in the accept queue.
+    let mut peers = Vec::new();
+    for _ in 0..10 {
+        for result in listener.accept_iter() {
+            let (_stream, peer) = result.unwrap();
+            peers.push(peer);
+        }
+        if peers.len() == N_CONNECTIONS {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    assert_eq!(peers.len(), N_CONNECTIONS);
+    for stream in &streams {
+        assert!(peers.contains(&stream.local_addr().unwrap()));
+    }
+
+    // The iterator stopped because all connections are accepted.
+    assert_would_block(listener.accept());
+    assert!(listener.accept_iter().next().is_none());
+}
+
 /// Start `n_connections` connections to `address`. If a `barrier` is provided
 /// it will wait on it after each connection is made before it is dropped.
 fn start_connections(
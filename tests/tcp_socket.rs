@@ -0,0 +1,130 @@
+use std::io::{Read, Write};
+use std::net;
+
+use mio::net::TcpSocket;
+use mio::{Interests, Token};
+
+mod util;
+
+use util::{
+    any_local_address, any_local_ipv6_address, assert_send, assert_sync, expect_events,
+    init_with_poll, ExpectEvent,
+};
+
+const DATA: &[u8] = b"Hello world!";
+
+const ID1: Token = Token(0);
+const ID2: Token = Token(1);
+
+#[test]
+fn is_send_and_sync() {
+    assert_send::<TcpSocket>();
+    assert_sync::<TcpSocket>();
+}
+
+#[test]
+fn listen() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.set_reuseaddr(true).unwrap();
+    socket.bind(any_local_address()).unwrap();
+    let listener = socket.listen(128).unwrap();
+    let address = listener.local_addr().unwrap();
+    assert_ne!(address.port(), 0);
+
+    poll.registry()
+        .register(&listener, ID1, Interests::READABLE)
+        .unwrap();
+
+    let mut stream = net::TcpStream::connect(address).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+
+    let (mut accepted, peer_address) = listener.accept().unwrap();
+    assert_eq!(peer_address, stream.local_addr().unwrap());
+
+    stream.write_all(DATA).unwrap();
+    poll.registry()
+        .register(&accepted, ID2, Interests::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interests::READABLE)],
+    );
+    let mut buf = [0; 20];
+    let n = accepted.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA);
+}
+
+#[test]
+fn listen_ipv6() {
+    let socket = TcpSocket::new_v6().unwrap();
+    socket.bind(any_local_ipv6_address()).unwrap();
+    let listener = socket.listen(128).unwrap();
+    assert!(listener.local_addr().unwrap().is_ipv6());
+}
+
+#[test]
+fn connect_from_bound_address() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+
+    // Use a free port as local address of the connection.
+    let local_address = net::TcpListener::bind(any_local_address())
+        .and_then(|listener| listener.local_addr())
+        .unwrap();
+
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.set_reuseaddr(true).unwrap();
+    socket.bind(local_address).unwrap();
+    let stream = socket.connect(listener.local_addr().unwrap()).unwrap();
+
+    poll.registry()
+        .register(&stream, ID1, Interests::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::WRITABLE)],
+    );
+
+    assert_eq!(stream.local_addr().unwrap(), local_address);
+    let (_, peer_address) = listener.accept().unwrap();
+    assert_eq!(peer_address, local_address);
+}
+
+#[test]
+fn bind_wrong_address_family() {
+    let socket = TcpSocket::new_v4().unwrap();
+    assert!(socket.bind(any_local_ipv6_address()).is_err());
+}
+
+#[test]
+#[cfg(not(any(windows, target_os = "solaris")))]
+fn reuseport() {
+    let socket1 = TcpSocket::new_v4().unwrap();
+    socket1.set_reuseport(true).unwrap();
+    socket1.bind(any_local_address()).unwrap();
+    let listener1 = socket1.listen(128).unwrap();
+    let address = listener1.local_addr().unwrap();
+
+    let socket2 = TcpSocket::new_v4().unwrap();
+    socket2.set_reuseport(true).unwrap();
+    socket2.bind(address).unwrap();
+    let listener2 = socket2.listen(128).unwrap();
+    assert_eq!(listener2.local_addr().unwrap(), address);
+}
+
+#[test]
+#[cfg(windows)]
+fn reuseport_unsupported() {
+    let socket = TcpSocket::new_v4().unwrap();
+    assert!(socket.set_reuseport(true).is_err());
+    socket.set_reuseport(false).unwrap();
+}
@@ -1,14 +1,20 @@
 use log::warn;
 use std::io::{self, IoSlice, IoSliceMut, Read, Write};
+#[cfg(unix)]
+use std::mem;
 use std::net::{self, Shutdown, SocketAddr};
 #[cfg(unix)]
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
+#[cfg(unix)]
+use std::ptr;
+#[cfg(unix)]
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{mpsc::channel, Arc, Barrier};
 use std::thread;
 use std::time::Duration;
 
-use mio::net::TcpStream;
-use mio::{Interests, Token};
+use mio::net::{advance_slices, TcpKeepalive, TcpStream};
+use mio::{event, Interests, Registry, Token, Trigger};
 
 #[macro_use]
 mod util;
@@ -49,14 +55,39 @@ fn tcp_stream_ipv6() {
 #[cfg_attr(windows, ignore = "fails on Windows, see #1078")]
 fn tcp_stream_std() {
     smoke_test_tcp_stream(any_local_address(), |addr| {
+        // `std::net::TcpStream`s are blocking by default, `from_std` puts it
+        // in non-blocking mode.
         let stream = net::TcpStream::connect(addr).unwrap();
-        // `std::net::TcpStream`s are blocking by default, so make sure it is
-        // in non-blocking mode before wrapping in a Mio equivalent.
-        assert_ok!(stream.set_nonblocking(true));
-        Ok(TcpStream::from_std(stream))
+        TcpStream::from_std(stream)
     });
 }
 
+#[test]
+fn connect_from() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+
+    // Use a free port as source address of the connection.
+    let local_address = net::TcpListener::bind(any_local_address())
+        .and_then(|listener| listener.local_addr())
+        .unwrap();
+
+    let stream = TcpStream::connect_from(local_address, listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&stream, ID1, Interests::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::WRITABLE)],
+    );
+
+    assert_eq!(stream.local_addr().unwrap(), local_address);
+    let (_, peer_address) = listener.accept().unwrap();
+    assert_eq!(peer_address, local_address);
+}
+
 fn smoke_test_tcp_stream<F>(addr: SocketAddr, make_stream: F)
 where
     F: FnOnce(SocketAddr) -> io::Result<TcpStream>,
@@ -134,6 +165,80 @@ where
     handle.join().expect("unable to join thread");
 }
 
+#[test]
+fn into_std() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&stream, ID1, Interests::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::WRITABLE)],
+    );
+    poll.registry().deregister(&stream).unwrap();
+
+    let local_address = stream.local_addr().unwrap();
+    let mut stream = stream.into_std().unwrap();
+    assert_eq!(stream.local_addr().unwrap(), local_address);
+    // The stream is still in non-blocking mode.
+    let mut buf = [0; 20];
+    assert_would_block(stream.read(&mut buf));
+    stream.set_nonblocking(false).unwrap();
+
+    let (mut peer, _) = listener.accept().unwrap();
+    peer.write_all(DATA1).unwrap();
+    stream.read_exact(&mut buf[..DATA1_LEN]).unwrap();
+    assert_eq!(&buf[..DATA1_LEN], DATA1);
+}
+
+#[test]
+fn register_clones_under_multiple_tokens() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let writer = stream.try_clone().unwrap();
+    poll.registry()
+        .register(&stream, ID1, Interests::READABLE)
+        .unwrap();
+    poll.registry()
+        .register(&writer, ID2, Interests::WRITABLE)
+        .unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interests::WRITABLE)],
+    );
+
+    let (mut peer, _) = listener.accept().unwrap();
+    peer.write_all(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+    // Every token only receives the events it's interested in.
+    for event in events.iter() {
+        match event.token() {
+            ID1 => assert!(!event.is_writable()),
+            ID2 => assert!(!event.is_readable()),
+            token => panic!("unexpected token: {:?}", token),
+        }
+    }
+
+    let mut buf = [0; 20];
+    let n = (&stream).read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+
+    poll.registry().deregister(&stream).unwrap();
+    poll.registry().deregister(&writer).unwrap();
+}
+
 #[test]
 fn try_clone() {
     let (mut poll, mut events) = init_with_poll();
@@ -208,6 +313,11 @@ fn set_get_ttl() {
     assert_eq!(stream.ttl().unwrap(), TTL);
     assert!(stream.take_error().unwrap().is_none());
 
+    // Values that don't fit in the TTL field are rejected.
+    let err = stream.set_ttl(256).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(stream.ttl().unwrap(), TTL);
+
     barrier.wait();
     thread_handle.join().expect("unable to join thread");
 }
@@ -306,6 +416,307 @@ fn get_nodelay_without_previous_set() {
     thread_handle.join().expect("unable to join thread");
 }
 
+#[test]
+#[cfg(unix)]
+fn set_nodelay_before_connected() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let barrier = Arc::new(Barrier::new(2));
+    let (thread_handle, address) = start_listener(1, Some(barrier.clone()), false);
+
+    let mut stream = TcpStream::connect(address).unwrap();
+
+    // On Unix the option can be set while the connect is still in progress.
+    stream.set_nodelay(true).unwrap();
+
+    poll.registry()
+        .register(&stream, ID1, Interests::WRITABLE)
+        .expect("unable to register TCP stream");
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::WRITABLE)],
+    );
+
+    assert!(stream.nodelay().unwrap());
+    // Setting the option must not change the non-blocking mode.
+    let mut buf = [0; 16];
+    assert_would_block(stream.read(&mut buf));
+    assert!(stream.take_error().unwrap().is_none());
+
+    barrier.wait();
+    thread_handle.join().expect("unable to join thread");
+}
+
+#[test]
+fn is_connected() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    poll.registry()
+        .register(&stream, ID1, Interests::WRITABLE)
+        .expect("unable to register TCP stream");
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::WRITABLE)],
+    );
+
+    assert!(stream.is_connected().unwrap());
+}
+
+#[test]
+fn poll_connected_across_writable_events() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&stream, ID1, Interests::WRITABLE)
+        .unwrap();
+
+    // Only check the stream after a writable event, as an event loop would.
+    let mut connected = false;
+    for _ in 0..10 {
+        poll.poll(&mut events, Some(Duration::from_millis(500)))
+            .unwrap();
+        if events.iter().any(|event| event.token() == ID1) && stream.poll_connected().unwrap() {
+            connected = true;
+            break;
+        }
+    }
+    assert!(connected, "connect didn't complete");
+
+    // Once connected it stays connected.
+    assert!(stream.poll_connected().unwrap());
+    let (peer, _) = listener.accept().unwrap();
+    assert_eq!(peer.peer_addr().unwrap(), stream.local_addr().unwrap());
+}
+
+#[test]
+fn is_connected_connection_refused() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // Nothing is listening on the address once the listener is dropped.
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    drop(listener);
+
+    let stream = match TcpStream::connect(address) {
+        Ok(stream) => stream,
+        // Some platforms report the error right away.
+        Err(ref err) if err.kind() == io::ErrorKind::ConnectionRefused => return,
+        Err(err) => panic!("unexpected error connecting: {}", err),
+    };
+
+    poll.registry()
+        .register(&stream, ID1, Interests::WRITABLE)
+        .expect("unable to register TCP stream");
+
+    // Windows retries the connect a couple of times, so this can take a while.
+    for _ in 0..10 {
+        match stream.is_connected() {
+            Ok(false) => poll
+                .poll(&mut events, Some(Duration::from_millis(500)))
+                .unwrap(),
+            Ok(true) => panic!("unexpectedly connected"),
+            Err(err) => {
+                assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+                return;
+            }
+        }
+    }
+    panic!("connect didn't fail");
+}
+
+#[test]
+fn set_get_linger() {
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    assert_eq!(stream.linger().unwrap(), None);
+
+    stream.set_linger(Some(Duration::from_secs(5))).unwrap();
+    assert_eq!(stream.linger().unwrap(), Some(Duration::from_secs(5)));
+
+    // Sub-second durations are rounded up.
+    stream.set_linger(Some(Duration::from_millis(500))).unwrap();
+    assert_eq!(stream.linger().unwrap(), Some(Duration::from_secs(1)));
+
+    stream.set_linger(None).unwrap();
+    assert_eq!(stream.linger().unwrap(), None);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_get_quickack() {
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    stream.set_quickack(true).unwrap();
+    assert!(stream.quickack().unwrap());
+
+    stream.set_quickack(false).unwrap();
+    assert!(!stream.quickack().unwrap());
+}
+
+#[test]
+#[cfg(not(any(target_os = "android", target_os = "linux")))]
+fn set_get_quickack_unsupported() {
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    assert!(stream.set_quickack(true).is_err());
+    assert!(stream.quickack().is_err());
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_get_mark() {
+    if !has_cap_net_admin() {
+        return;
+    }
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    stream.set_mark(123).unwrap();
+    assert_eq!(stream.mark().unwrap(), 123);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_mark_permission_denied() {
+    if has_cap_net_admin() {
+        return;
+    }
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let err = stream.set_mark(123).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+}
+
+/// Returns `true` if the process has `CAP_NET_ADMIN` in its effective set,
+/// which is required to set `SO_MARK`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn has_cap_net_admin() -> bool {
+    const CAP_NET_ADMIN: u64 = 12;
+    std::fs::read_to_string("/proc/self/status")
+        .unwrap()
+        .lines()
+        .find(|line| line.starts_with("CapEff:"))
+        .and_then(|line| u64::from_str_radix(line["CapEff:".len()..].trim(), 16).ok())
+        .map_or(false, |caps| caps & (1 << CAP_NET_ADMIN) != 0)
+}
+
+#[test]
+fn set_get_keepalive() {
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    assert_eq!(stream.keepalive().unwrap(), None);
+
+    let keepalive = TcpKeepalive::new()
+        .with_time(Duration::from_secs(10))
+        .with_interval(Duration::from_secs(5))
+        .with_retries(3);
+    assert_eq!(keepalive.time(), Some(Duration::from_secs(10)));
+    assert_eq!(keepalive.interval(), Some(Duration::from_secs(5)));
+    assert_eq!(keepalive.retries(), Some(3));
+    stream.set_keepalive(Some(keepalive)).unwrap();
+
+    // Not all parameters can be retrieved on all platforms.
+    #[allow(unused_variables)]
+    let got = stream.keepalive().unwrap().expect("keepalive not enabled");
+    #[cfg(not(any(windows, target_os = "openbsd", target_os = "solaris")))]
+    assert_eq!(got.time(), Some(Duration::from_secs(10)));
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    assert_eq!(got, keepalive);
+    #[cfg(windows)]
+    assert_eq!(got, TcpKeepalive::new());
+
+    // Only enabling `SO_KEEPALIVE` must be supported everywhere.
+    stream.set_keepalive(Some(TcpKeepalive::new())).unwrap();
+    assert!(stream.keepalive().unwrap().is_some());
+
+    stream.set_keepalive(None).unwrap();
+    assert_eq!(stream.keepalive().unwrap(), None);
+}
+
+#[test]
+fn set_get_buffer_sizes() {
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+
+    const SIZE: usize = 256 * 1024;
+    stream.set_recv_buffer_size(SIZE).unwrap();
+    assert!(stream.recv_buffer_size().unwrap() >= SIZE);
+    stream.set_send_buffer_size(SIZE).unwrap();
+    assert!(stream.send_buffer_size().unwrap() >= SIZE);
+}
+
+#[test]
+fn zero_linger_resets_connection() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    poll.registry()
+        .register(&stream, ID1, Interests::WRITABLE)
+        .expect("unable to register TCP stream");
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::WRITABLE)],
+    );
+
+    stream.set_linger(Some(Duration::from_secs(0))).unwrap();
+    assert_eq!(stream.linger().unwrap(), Some(Duration::from_secs(0)));
+    drop(stream);
+
+    // Closing the stream should have sent a RST, rather than a FIN.
+    let mut buf = [0; 16];
+    let err = peer.read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionReset);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn priority_out_of_band_data() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (peer, _) = listener.accept().unwrap();
+
+    poll.registry()
+        .register(&stream, ID1, Interests::PRIORITY)
+        .expect("unable to register TCP stream");
+    expect_no_events(&mut poll, &mut events);
+
+    let n = unsafe {
+        libc::send(
+            peer.as_raw_fd(),
+            DATA1.as_ptr() as *const libc::c_void,
+            DATA1.len(),
+            libc::MSG_OOB,
+        )
+    };
+    assert_eq!(n, DATA1_LEN as isize);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::PRIORITY)],
+    );
+}
+
 #[test]
 fn shutdown_read() {
     let (mut poll, mut events) = init_with_poll();
@@ -523,6 +934,159 @@ fn reregistering() {
     thread_handle.join().expect("unable to join thread");
 }
 
+#[test]
+fn edge_triggered() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    poll.registry()
+        .register_with_mode(&stream, ID1, Interests::READABLE, Trigger::Edge)
+        .expect("unable to register TCP stream");
+
+    peer.write_all(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+
+    // Only read part of the data, this shouldn't trigger another event.
+    let mut buf = [0; 4];
+    assert_eq!(stream.read(&mut buf).unwrap(), buf.len());
+    expect_no_events(&mut poll, &mut events);
+
+    // Until new data arrives.
+    peer.write_all(DATA2).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+}
+
+#[test]
+#[cfg_attr(windows, ignore = "level-triggered registration is ignored on Windows")]
+fn level_triggered() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    poll.registry()
+        .register_with_mode(&stream, ID1, Interests::READABLE, Trigger::Level)
+        .expect("unable to register TCP stream");
+
+    peer.write_all(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+
+    // Only read part of the data, the stream is still readable so we should
+    // get another event.
+    let mut buf = [0; 4];
+    assert_eq!(stream.read(&mut buf).unwrap(), buf.len());
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+
+    // After reading everything the stream is no longer ready.
+    let mut buf = [0; DATA1_LEN];
+    assert_eq!(stream.read(&mut buf).unwrap(), DATA1_LEN - 4);
+    expect_no_events(&mut poll, &mut events);
+}
+
+/// Registers the stream using `Registry::register`, rather than forwarding the
+/// registry to the stream.
+struct NestedRegister(TcpStream);
+
+impl event::Source for NestedRegister {
+    fn register(&self, registry: &Registry, token: Token, interests: Interests) -> io::Result<()> {
+        registry.register(&self.0, token, interests)
+    }
+
+    fn reregister(
+        &self,
+        registry: &Registry,
+        token: Token,
+        interests: Interests,
+    ) -> io::Result<()> {
+        registry.reregister(&self.0, token, interests)
+    }
+
+    fn deregister(&self, registry: &Registry) -> io::Result<()> {
+        registry.deregister(&self.0)
+    }
+}
+
+#[test]
+fn trigger_mode_not_inherited_by_nested_register() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    let source = NestedRegister(stream);
+    poll.registry()
+        .register_with_mode(&source, ID1, Interests::READABLE, Trigger::Level)
+        .expect("unable to register source");
+
+    // `Registry::register` registers the stream edge-triggered, so we only
+    // get a single event even though the data isn't read.
+    peer.write_all(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+    expect_no_events(&mut poll, &mut events);
+}
+
+#[test]
+#[cfg_attr(windows, ignore = "oneshot registration is ignored on Windows")]
+fn oneshot_triggered() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    poll.registry()
+        .register_with_mode(&stream, ID1, Interests::READABLE, Trigger::Oneshot)
+        .expect("unable to register TCP stream");
+
+    peer.write_all(DATA1).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+
+    // The source is disabled after the first event, even if more data arrives.
+    peer.write_all(DATA2).unwrap();
+    expect_no_events(&mut poll, &mut events);
+
+    // Until it's rearmed, the stream is still readable so we should get
+    // another event.
+    poll.registry()
+        .reregister_with_mode(&stream, ID1, Interests::READABLE, Trigger::Oneshot)
+        .expect("unable to reregister TCP stream");
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+    expect_no_events(&mut poll, &mut events);
+}
+
 #[test]
 fn no_events_after_deregister() {
     let (mut poll, mut events) = init_with_poll();
@@ -588,6 +1152,26 @@ fn tcp_shutdown_client_read_close_event() {
     handle.join().expect("failed to join thread");
 }
 
+#[test]
+#[cfg(all(feature = "os-ext", any(target_os = "android", target_os = "linux")))]
+fn raw_ready_read_closed() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (peer, _) = listener.accept().unwrap();
+    poll.registry()
+        .register(&stream, ID1, Interests::READABLE)
+        .unwrap();
+
+    peer.shutdown(Shutdown::Write).unwrap();
+    expect_readiness!(poll, events, is_read_closed);
+
+    let event = events.iter().find(|event| event.is_read_closed()).unwrap();
+    assert_ne!(event.raw_ready() & libc::EPOLLRDHUP as u32, 0);
+    assert_ne!(event.raw_ready() & libc::EPOLLIN as u32, 0);
+}
+
 #[test]
 #[cfg_attr(windows, ignore = "fails; client write_closed events are not found")]
 #[cfg_attr(
@@ -677,6 +1261,339 @@ fn tcp_shutdown_client_both_close_event() {
     handle.join().expect("failed to join thread");
 }
 
+#[test]
+#[cfg_attr(
+    windows,
+    ignore = "fails on Windows; the connect error isn't returned by take_error"
+)]
+fn connect_error() {
+    let (mut poll, mut events) = init_with_poll();
+
+    // Find a port that nothing is listening on.
+    let address = {
+        let listener = net::TcpListener::bind(any_local_address()).unwrap();
+        listener.local_addr().unwrap()
+    };
+
+    let stream = match TcpStream::connect(address) {
+        Ok(stream) => stream,
+        // Some platforms refuse the connection immediately.
+        Err(ref err) if err.kind() == io::ErrorKind::ConnectionRefused => return,
+        Err(err) => panic!("unexpected error connecting: {}", err),
+    };
+    poll.registry()
+        .register(&stream, ID1, Interests::WRITABLE)
+        .unwrap();
+
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    let event = events.iter().next().expect("no events");
+    assert_eq!(event.token(), ID1);
+    assert!(event.is_error(), "not an error event: {:?}", event);
+
+    let err = stream.take_error().unwrap().expect("no error");
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+    assert!(stream.take_error().unwrap().is_none());
+}
+
+#[test]
+fn read_blocking_handshake() {
+    const HANDSHAKE: &[u8] = b"HELLO";
+
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    let barrier = Arc::new(Barrier::new(2));
+    let barrier2 = barrier.clone();
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        // Give the client time to block on reading the handshake.
+        thread::sleep(Duration::from_millis(100));
+        stream.write_all(HANDSHAKE).unwrap();
+        barrier2.wait();
+        stream.write_all(DATA1).unwrap();
+        barrier2.wait();
+    });
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    let mut buf = [0; 16];
+    let n = stream.read_blocking(&mut buf).unwrap();
+    assert_eq!(&buf[..n], HANDSHAKE);
+
+    // Back to non-blocking, event-driven reads.
+    assert_would_block(stream.read(&mut buf));
+    poll.registry()
+        .register(&stream, ID1, Interests::READABLE)
+        .unwrap();
+    barrier.wait();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+    let n = stream.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+
+    barrier.wait();
+    handle.join().unwrap();
+}
+
+#[test]
+fn peek_vectored() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let address = listener.local_addr().unwrap();
+    let barrier = Arc::new(Barrier::new(2));
+    let barrier2 = barrier.clone();
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut data = DATA1.to_vec();
+        data.extend_from_slice(DATA2);
+        stream.write_all(&data).unwrap();
+        barrier2.wait();
+    });
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    poll.registry()
+        .register(&stream, ID1, Interests::READABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+
+    let mut peek1 = [0; DATA1_LEN];
+    let mut peek2 = [0; DATA2_LEN];
+    let mut bufs = [IoSliceMut::new(&mut peek1), IoSliceMut::new(&mut peek2)];
+    let n = stream.peek_vectored(&mut bufs).unwrap();
+    assert_eq!(n, DATA1.len() + DATA2.len());
+    assert_eq!(&peek1, DATA1);
+    assert_eq!(&peek2, DATA2);
+
+    // The peeked data must still be available for reading.
+    let mut read1 = [0; DATA1_LEN];
+    let mut read2 = [0; DATA2_LEN];
+    let mut bufs = [IoSliceMut::new(&mut read1), IoSliceMut::new(&mut read2)];
+    let n = stream.read_vectored(&mut bufs).unwrap();
+    assert_eq!(n, DATA1.len() + DATA2.len());
+    assert_eq!(read1, peek1);
+    assert_eq!(read2, peek2);
+
+    barrier.wait();
+    handle.join().unwrap();
+}
+
+#[test]
+fn try_read_try_write() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    poll.registry()
+        .register(&stream, ID1, Interests::READABLE | Interests::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::WRITABLE)],
+    );
+
+    let mut buf = [0; 20];
+    assert_eq!(stream.try_read(&mut buf).unwrap(), None);
+
+    assert_eq!(stream.try_write(DATA1).unwrap(), Some(DATA1.len()));
+    peer.read_exact(&mut buf[..DATA1_LEN]).unwrap();
+    assert_eq!(&buf[..DATA1_LEN], DATA1);
+
+    peer.write_all(DATA2).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+    assert_eq!(stream.try_read(&mut buf).unwrap(), Some(DATA2.len()));
+    assert_eq!(&buf[..DATA2_LEN], DATA2);
+    assert_eq!(stream.try_read(&mut buf).unwrap(), None);
+
+    // Errors other than `WouldBlock` are returned as is.
+    stream.shutdown(Shutdown::Write).unwrap();
+    assert!(stream.try_write(DATA1).is_err());
+}
+
+#[test]
+fn read_available() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+    poll.registry()
+        .register(&stream, ID1, Interests::READABLE)
+        .unwrap();
+
+    let mut buf = Vec::new();
+    assert_eq!(stream.read_available(&mut buf).unwrap(), 0);
+    assert!(buf.is_empty());
+
+    // More than a single read, to check `buf` is grown.
+    let payload: Vec<u8> = (0..10_000).map(|i| i as u8).collect();
+    peer.write_all(&payload).unwrap();
+
+    // Keep the existing contents of `buf`.
+    buf.extend_from_slice(DATA1);
+    let mut total = 0;
+    while total < payload.len() {
+        expect_readiness!(poll, events, is_readable);
+        total += stream.read_available(&mut buf).unwrap();
+    }
+    assert_eq!(total, payload.len());
+    assert_eq!(&buf[..DATA1_LEN], DATA1);
+    assert_eq!(&buf[DATA1_LEN..], &*payload);
+
+    // Stops at end of file as well.
+    peer.write_all(DATA2).unwrap();
+    drop(peer);
+    expect_readiness!(poll, events, is_read_closed);
+    buf.clear();
+    assert_eq!(stream.read_available(&mut buf).unwrap(), DATA2_LEN);
+    assert_eq!(buf, DATA2);
+}
+
+#[test]
+fn cached_peer_addr() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let stream = TcpStream::connect(addr).unwrap();
+    poll.registry()
+        .register(&stream, ID1, Interests::READABLE)
+        .unwrap();
+    let (peer, _) = listener.accept().unwrap();
+    assert_eq!(stream.cached_peer_addr().unwrap(), addr);
+
+    // Resetting the connection causes `peer_addr` to fail, but the cached
+    // address is still returned.
+    let peer = TcpStream::from_std(peer).unwrap();
+    peer.set_linger(Some(Duration::from_secs(0))).unwrap();
+    drop(peer);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+    assert!(stream.peer_addr().is_err());
+    assert_eq!(stream.cached_peer_addr().unwrap(), addr);
+}
+
+#[test]
+fn tcp_shutdown_twice() {
+    let (mut poll, mut events) = init_with_poll();
+    let barrier = Arc::new(Barrier::new(2));
+
+    let (handle, sockaddr) = start_listener(1, Some(barrier.clone()), true);
+    let stream = assert_ok!(TcpStream::connect(sockaddr));
+
+    assert_ok!(poll.registry().register(&stream, ID1, Interests::WRITABLE));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::WRITABLE)],
+    );
+
+    assert_ok!(stream.shutdown(Shutdown::Both));
+    assert_ok!(stream.shutdown(Shutdown::Both));
+    assert_ok!(stream.shutdown(Shutdown::Write));
+
+    barrier.wait();
+    handle.join().expect("failed to join thread");
+}
+
+#[test]
+#[cfg(unix)]
+fn write_after_shutdown_does_not_raise_sigpipe() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&stream, ID1, Interests::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::WRITABLE)],
+    );
+    stream.shutdown(Shutdown::Write).unwrap();
+
+    // The test harness ignores `SIGPIPE`, so count the signals raised using a
+    // handler installed for the duration of the test.
+    static SIGPIPES: AtomicUsize = AtomicUsize::new(0);
+    extern "C" fn count_sigpipe(_: libc::c_int) {
+        SIGPIPES.fetch_add(1, Ordering::SeqCst);
+    }
+    let mut action: libc::sigaction = unsafe { mem::zeroed() };
+    action.sa_sigaction = count_sigpipe as libc::sighandler_t;
+    let mut old_action: libc::sigaction = unsafe { mem::zeroed() };
+    unsafe {
+        libc::sigemptyset(&mut action.sa_mask);
+        assert_eq!(libc::sigaction(libc::SIGPIPE, &action, &mut old_action), 0);
+    }
+
+    let err = stream.write(DATA1).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    let err = stream
+        .write_vectored(&[IoSlice::new(DATA1), IoSlice::new(DATA2)])
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+
+    unsafe { libc::sigaction(libc::SIGPIPE, &old_action, ptr::null_mut()) };
+    assert_eq!(SIGPIPES.load(Ordering::SeqCst), 0, "writing raised SIGPIPE");
+}
+
+#[test]
+fn write_vectored_advance_slices() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let listener = net::TcpListener::bind(any_local_address()).unwrap();
+    let mut stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    poll.registry()
+        .register(&stream, ID1, Interests::WRITABLE)
+        .unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::WRITABLE)],
+    );
+
+    let mut slices = [IoSlice::new(DATA1), IoSlice::new(DATA2)];
+    let mut bufs = &mut slices[..];
+
+    // Write the first buffer and part of the second, as a partial
+    // `write_vectored` could.
+    stream.write_all(DATA1).unwrap();
+    stream.write_all(&DATA2[..3]).unwrap();
+    advance_slices(&mut bufs, DATA1_LEN + 3);
+    assert_eq!(bufs.len(), 1);
+    assert_eq!(&*bufs[0], &DATA2[3..]);
+
+    while !bufs.is_empty() {
+        let n = stream.write_vectored(bufs).unwrap();
+        advance_slices(&mut bufs, n);
+    }
+
+    let mut buf = [0; DATA1_LEN + DATA2_LEN];
+    peer.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf[..DATA1_LEN], DATA1);
+    assert_eq!(&buf[DATA1_LEN..], DATA2);
+}
+
 /// Start a listener that accepts `n_connections` connections on the returned
 /// address. It echos back any data it reads from the connection before
 /// accepting another one.
@@ -0,0 +1,83 @@
+#![cfg(any(target_os = "android", target_os = "linux"))]
+
+use std::time::{Duration, Instant};
+
+use mio::unix::TimerFd;
+use mio::{Interests, Token};
+
+mod util;
+
+use util::{
+    assert_send, assert_sync, assert_would_block, expect_events, expect_no_events, init_with_poll,
+    ExpectEvent,
+};
+
+const TIMER: Token = Token(0);
+
+#[test]
+fn is_send_and_sync() {
+    assert_send::<TimerFd>();
+    assert_sync::<TimerFd>();
+}
+
+#[test]
+fn one_shot() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let timer = TimerFd::new().unwrap();
+    poll.registry()
+        .register(&timer, TIMER, Interests::READABLE)
+        .unwrap();
+    assert_would_block(timer.read());
+
+    let start = Instant::now();
+    timer.set(Duration::from_millis(50), None).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TIMER, Interests::READABLE)],
+    );
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert_eq!(timer.read().unwrap(), 1);
+
+    // A one-shot timer doesn't expire again.
+    expect_no_events(&mut poll, &mut events);
+    assert_would_block(timer.read());
+}
+
+#[test]
+fn interval() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let timer = TimerFd::new().unwrap();
+    poll.registry()
+        .register(&timer, TIMER, Interests::READABLE)
+        .unwrap();
+
+    timer
+        .set(Duration::from_millis(10), Some(Duration::from_millis(10)))
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(50));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TIMER, Interests::READABLE)],
+    );
+    assert!(timer.read().unwrap() >= 2);
+}
+
+#[test]
+fn disarm() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let timer = TimerFd::new().unwrap();
+    poll.registry()
+        .register(&timer, TIMER, Interests::READABLE)
+        .unwrap();
+
+    timer.set(Duration::from_millis(10), None).unwrap();
+    timer.set(Duration::from_secs(0), None).unwrap();
+    std::thread::sleep(Duration::from_millis(20));
+    expect_no_events(&mut poll, &mut events);
+    assert_would_block(timer.read());
+}
@@ -0,0 +1,50 @@
+use mio::Token;
+
+use std::rc::Rc;
+
+#[test]
+fn ptr_round_trip() {
+    let value = Box::new(String::from("connection state"));
+    let ptr = Box::into_raw(value);
+    let token = Token::from_ptr(ptr);
+    assert_eq!(token, Token(ptr as usize));
+    assert_eq!(token.as_ptr::<String>(), ptr as *const String);
+
+    let value = unsafe { Box::from_raw(token.as_ptr::<String>() as *mut String) };
+    assert_eq!(*value, "connection state");
+}
+
+#[test]
+fn ptr_round_trip_keeps_value_alive() {
+    let value = Rc::new(1);
+    let token = Token::from_ptr(Rc::into_raw(value.clone()));
+    assert_eq!(Rc::strong_count(&value), 2);
+
+    let clone = unsafe { Rc::from_raw(token.as_ptr::<i32>()) };
+    assert!(Rc::ptr_eq(&value, &clone));
+    drop(clone);
+    assert_eq!(Rc::strong_count(&value), 1);
+}
+
+#[test]
+fn arithmetic() {
+    let base = Token(10);
+    assert_eq!(base + 0, base);
+    assert_eq!(base + 5, Token(15));
+    assert_eq!((base + 5).index(), 15);
+}
+
+#[test]
+fn range() {
+    let tokens: Vec<Token> = Token::range(Token(5), 3).collect();
+    assert_eq!(tokens, vec![Token(5), Token(6), Token(7)]);
+
+    assert_eq!(Token::range(Token(5), 0).count(), 0);
+
+    // Doesn't overflow.
+    let tokens: Vec<Token> = Token::range(Token(usize::max_value() - 2), 5).collect();
+    assert_eq!(
+        tokens,
+        vec![Token(usize::max_value() - 2), Token(usize::max_value() - 1)]
+    );
+}
@@ -1,5 +1,5 @@
-use std::io::ErrorKind;
-use std::net::{self, IpAddr, SocketAddr};
+use std::io::{ErrorKind, IoSlice, IoSliceMut};
+use std::net::{self, IpAddr, Ipv4Addr, SocketAddr};
 #[cfg(unix)]
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
 use std::str;
@@ -10,7 +10,9 @@ use std::time::Duration;
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use log::{debug, info};
 
-use mio::net::UdpSocket;
+#[cfg(any(target_os = "android", target_os = "linux"))]
+use mio::net::RecvFlags;
+use mio::net::{RecvMsg, SendMsg, UdpSocket, UdpSocketOpts};
 use mio::{Events, Interests, Poll, Registry, Token};
 
 mod util;
@@ -51,19 +53,46 @@ fn unconnected_udp_socket_ipv6() {
 
 #[test]
 fn unconnected_udp_socket_std() {
+    // `std::net::UdpSocket`s are blocking by default, `from_std` puts them in
+    // non-blocking mode.
     let socket1 = net::UdpSocket::bind(any_local_address()).unwrap();
     let socket2 = net::UdpSocket::bind(any_local_address()).unwrap();
 
-    // `std::net::UdpSocket`s are blocking by default, so make sure they are
-    // in non-blocking mode before wrapping in a Mio equivalent.
-    assert_ok!(socket1.set_nonblocking(true));
-    assert_ok!(socket2.set_nonblocking(true));
-
-    let socket1 = UdpSocket::from_std(socket1);
-    let socket2 = UdpSocket::from_std(socket2);
+    let socket1 = UdpSocket::from_std(socket1).unwrap();
+    let socket2 = UdpSocket::from_std(socket2).unwrap();
     smoke_test_unconnected_udp_socket(socket1, socket2);
 }
 
+#[test]
+fn from_std_sets_non_blocking() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket = net::UdpSocket::bind(any_local_address()).unwrap();
+    socket.set_nonblocking(false).unwrap();
+    let socket = UdpSocket::from_std(socket).unwrap();
+    let address = socket.local_addr().unwrap();
+
+    // A blocking socket would block here forever.
+    let mut buf = [0; 20];
+    assert_would_block(socket.recv_from(&mut buf));
+
+    poll.registry()
+        .register(&socket, ID1, Interests::READABLE)
+        .unwrap();
+
+    let sender = net::UdpSocket::bind(any_local_address()).unwrap();
+    sender.send_to(DATA1, address).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+
+    let (n, from) = socket.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(from, sender.local_addr().unwrap());
+}
+
 fn smoke_test_unconnected_udp_socket(socket1: UdpSocket, socket2: UdpSocket) {
     let (mut poll, mut events) = init_with_poll();
 
@@ -126,6 +155,207 @@ fn smoke_test_unconnected_udp_socket(socket1: UdpSocket, socket2: UdpSocket) {
     assert!(socket2.take_error().unwrap().is_none());
 }
 
+#[test]
+fn send_to_recv_from_vectored() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+
+    poll.registry()
+        .register(&socket2, ID2, Interests::READABLE)
+        .expect("unable to register UDP socket");
+
+    let bufs = [IoSlice::new(&DATA1[..5]), IoSlice::new(&DATA1[5..])];
+    assert_eq!(
+        socket1.send_to_vectored(&bufs, address2).unwrap(),
+        DATA1.len()
+    );
+    // Should be received as a separate datagram.
+    assert_eq!(socket1.send_to(DATA2, address2).unwrap(), DATA2.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interests::READABLE)],
+    );
+
+    let mut buf1 = [0; 4];
+    let mut buf2 = [0; 20];
+    let mut bufs = [IoSliceMut::new(&mut buf1), IoSliceMut::new(&mut buf2)];
+    let (n, address) = socket2.recv_from_vectored(&mut bufs).unwrap();
+    assert_eq!(n, DATA1.len());
+    assert_eq!(address, address1);
+    assert_eq!(&buf1, &DATA1[..4]);
+    assert_eq!(&buf2[..n - 4], &DATA1[4..]);
+
+    let mut buf = [0; 20];
+    let (n, address) = socket2.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA2);
+    assert_eq!(address, address1);
+
+    assert_would_block(socket2.recv_from_vectored(&mut [IoSliceMut::new(&mut buf)]));
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn recv_from_with_info() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let socket2 = UdpSocket::bind("0.0.0.0:0".parse().unwrap()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let port = socket2.local_addr().unwrap().port();
+    socket2.set_recv_pktinfo(true).unwrap();
+
+    poll.registry()
+        .register(&socket2, ID2, Interests::READABLE)
+        .expect("unable to register UDP socket");
+
+    let destination1 = SocketAddr::new(Ipv4Addr::new(127, 0, 0, 1).into(), port);
+    let destination2 = SocketAddr::new(Ipv4Addr::new(127, 0, 0, 2).into(), port);
+    assert_eq!(socket1.send_to(DATA1, destination1).unwrap(), DATA1.len());
+    assert_eq!(socket1.send_to(DATA2, destination2).unwrap(), DATA2.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interests::READABLE)],
+    );
+
+    let mut buf = [0; 20];
+    for (data, destination) in &[(DATA1, destination1), (DATA2, destination2)] {
+        let (n, address, info) = socket2.recv_from_with_info(&mut buf).unwrap();
+        assert_eq!(&buf[..n], *data);
+        assert_eq!(address, address1);
+        assert_eq!(info.destination(), destination.ip());
+        assert!(info.interface_index() > 0);
+    }
+
+    assert_would_block(socket2.recv_from_with_info(&mut buf));
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn recv_from_with_flags() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+
+    poll.registry()
+        .register(&socket2, ID2, Interests::READABLE)
+        .expect("unable to register UDP socket");
+
+    let data = [1; 100];
+    assert_eq!(socket1.send_to(&data, address2).unwrap(), data.len());
+    assert_eq!(socket1.send_to(&data, address2).unwrap(), data.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interests::READABLE)],
+    );
+
+    let mut buf = [0; 10];
+    let flags = RecvFlags::PEEK | RecvFlags::TRUNC;
+    let (n, address, result) = socket2.recv_from_with_flags(&mut buf, flags).unwrap();
+    assert_eq!(n, buf.len());
+    assert_eq!(address, address1);
+    assert!(result.is_truncated());
+    assert_eq!(result.datagram_len(), Some(data.len()));
+
+    // Without `TRUNC` the full length is unknown.
+    let (n, address, result) = socket2
+        .recv_from_with_flags(&mut buf, RecvFlags::default())
+        .unwrap();
+    assert_eq!(n, buf.len());
+    assert_eq!(address, address1);
+    assert!(result.is_truncated());
+    assert_eq!(result.datagram_len(), None);
+
+    let mut buf = [0; 128];
+    let (n, address, result) = socket2
+        .recv_from_with_flags(&mut buf, RecvFlags::TRUNC)
+        .unwrap();
+    assert_eq!(&buf[..n], &data[..]);
+    assert_eq!(address, address1);
+    assert!(!result.is_truncated());
+    assert_eq!(result.datagram_len(), Some(data.len()));
+
+    assert_would_block(socket2.recv_from_with_flags(&mut buf, RecvFlags::default()));
+}
+
+#[test]
+fn send_mmsg_recv_mmsg() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+
+    poll.registry()
+        .register(&socket2, ID2, Interests::READABLE)
+        .expect("unable to register UDP socket");
+
+    let data: [&[u8]; 4] = [b"one", b"two", b"three", b"four"];
+    let msgs: Vec<SendMsg> = data.iter().map(|d| SendMsg::new(d, address2)).collect();
+    assert_eq!(socket1.send_mmsg(&msgs).unwrap(), data.len());
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID2, Interests::READABLE)],
+    );
+
+    let mut bufs = [[0; 64]; 8];
+    let mut msgs: Vec<RecvMsg> = bufs.iter_mut().map(|buf| RecvMsg::new(buf)).collect();
+    assert_eq!(socket2.recv_mmsg(&mut msgs).unwrap(), data.len());
+    for (msg, expected) in msgs.iter().zip(data.iter()) {
+        assert_eq!(msg.data(), *expected);
+        assert_eq!(msg.addr(), Some(address1));
+    }
+    for msg in &msgs[data.len()..] {
+        assert!(msg.data().is_empty());
+        assert_eq!(msg.addr(), None);
+    }
+
+    assert_would_block(socket2.recv_mmsg(&mut msgs));
+}
+
+#[test]
+fn try_send_to_try_recv_from() {
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+    let socket2 = UdpSocket::bind(any_local_address()).unwrap();
+    let address1 = socket1.local_addr().unwrap();
+    let address2 = socket2.local_addr().unwrap();
+
+    let mut buf = [0; 20];
+    assert_eq!(socket2.try_recv_from(&mut buf).unwrap(), None);
+
+    assert_eq!(
+        socket1.try_send_to(DATA1, address2).unwrap(),
+        Some(DATA1.len())
+    );
+    // Wait for the datagram to arrive.
+    thread::sleep(Duration::from_millis(10));
+    assert_eq!(
+        socket2.try_recv_from(&mut buf).unwrap(),
+        Some((DATA1.len(), address1))
+    );
+    assert_eq!(&buf[..DATA1.len()], DATA1);
+    assert_eq!(socket2.try_recv_from(&mut buf).unwrap(), None);
+
+    socket2.connect(address1).unwrap();
+    assert_eq!(socket2.try_recv(&mut buf).unwrap(), None);
+    assert_eq!(socket2.try_send(DATA2).unwrap(), Some(DATA2.len()));
+}
+
 #[test]
 fn set_get_ttl() {
     let socket1 = UdpSocket::bind(any_local_address()).unwrap();
@@ -137,6 +367,27 @@ fn set_get_ttl() {
     assert!(socket1.take_error().unwrap().is_none());
 }
 
+#[test]
+fn set_get_ttl_ipv6() {
+    let socket1 = UdpSocket::bind(any_local_ipv6_address()).unwrap();
+
+    // On IPv6 sockets the TTL maps to the unicast hop limit.
+    socket1.set_ttl(1).unwrap();
+    assert_eq!(socket1.ttl().unwrap(), 1);
+    assert!(socket1.take_error().unwrap().is_none());
+}
+
+#[test]
+fn set_ttl_out_of_range() {
+    let socket1 = UdpSocket::bind(any_local_address()).unwrap();
+
+    socket1.set_ttl(1).unwrap();
+    let err = socket1.set_ttl(256).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    // The previous value should be left untouched.
+    assert_eq!(socket1.ttl().unwrap(), 1);
+}
+
 #[test]
 fn get_ttl_without_previous_set() {
     let socket1 = UdpSocket::bind(any_local_address()).unwrap();
@@ -184,13 +435,8 @@ fn connected_udp_socket_std() {
     socket1.connect(address2).unwrap();
     socket2.connect(address1).unwrap();
 
-    // `std::net::UdpSocket`s are blocking by default, so make sure they are
-    // in non-blocking mode before wrapping in a Mio equivalent.
-    assert_ok!(socket1.set_nonblocking(true));
-    assert_ok!(socket2.set_nonblocking(true));
-
-    let socket1 = UdpSocket::from_std(socket1);
-    let socket2 = UdpSocket::from_std(socket2);
+    let socket1 = UdpSocket::from_std(socket1).unwrap();
+    let socket2 = UdpSocket::from_std(socket2).unwrap();
 
     smoke_test_connected_udp_socket(socket1, socket2);
 }
@@ -898,3 +1144,357 @@ pub fn multicast() {
         }
     }
 }
+
+#[cfg_attr(
+    target_os = "android",
+    ignore = "Multicast doesn't work on Android 64bit"
+)]
+#[test]
+fn multicast_join_while_registered() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let group: Ipv4Addr = "227.1.1.102".parse().unwrap();
+    let any = Ipv4Addr::UNSPECIFIED;
+
+    let rx = UdpSocket::bind(SocketAddr::new(any.into(), 0)).unwrap();
+    let port = rx.local_addr().unwrap().port();
+    poll.registry()
+        .register(&rx, ID1, Interests::READABLE)
+        .unwrap();
+
+    // Joining the group shouldn't require reregistering the socket.
+    rx.join_multicast_v4(group, any).unwrap();
+
+    let tx = UdpSocket::bind(SocketAddr::new(any.into(), 0)).unwrap();
+    tx.set_multicast_loop_v4(true).unwrap();
+    assert!(tx.multicast_loop_v4().unwrap());
+    tx.set_multicast_ttl_v4(1).unwrap();
+    assert_eq!(tx.multicast_ttl_v4().unwrap(), 1);
+
+    tx.send_to(DATA1, SocketAddr::new(group.into(), port))
+        .unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+
+    let mut buf = [0; 20];
+    let (n, _) = rx.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+
+    rx.leave_multicast_v4(group, any).unwrap();
+}
+
+#[cfg_attr(
+    target_os = "android",
+    ignore = "Multicast doesn't work on Android 64bit"
+)]
+#[test]
+fn multicast_if_v4() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let group: Ipv4Addr = "227.1.1.103".parse().unwrap();
+    let any = Ipv4Addr::UNSPECIFIED;
+    let localhost = Ipv4Addr::LOCALHOST;
+
+    let rx = UdpSocket::bind(SocketAddr::new(any.into(), 0)).unwrap();
+    let port = rx.local_addr().unwrap().port();
+    poll.registry()
+        .register(&rx, ID1, Interests::READABLE)
+        .unwrap();
+    rx.join_multicast_v4(group, localhost).unwrap();
+
+    let tx = UdpSocket::bind(SocketAddr::new(localhost.into(), 0)).unwrap();
+    assert_eq!(tx.multicast_if_v4().unwrap(), any);
+    tx.set_multicast_if_v4(localhost).unwrap();
+    assert_eq!(tx.multicast_if_v4().unwrap(), localhost);
+
+    tx.send_to(DATA1, SocketAddr::new(group.into(), port))
+        .unwrap();
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+
+    let mut buf = [0; 20];
+    let (n, address) = rx.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(address, tx.local_addr().unwrap());
+
+    rx.leave_multicast_v4(group, localhost).unwrap();
+}
+
+#[test]
+fn set_get_multicast_if_v6() {
+    let socket = UdpSocket::bind(any_local_ipv6_address()).unwrap();
+    assert_eq!(socket.multicast_if_v6().unwrap(), 0);
+    // The loopback interface, which always has index 1 on Linux.
+    #[cfg(target_os = "linux")]
+    {
+        socket.set_multicast_if_v6(1).unwrap();
+        assert_eq!(socket.multicast_if_v6().unwrap(), 1);
+    }
+    socket.set_multicast_if_v6(0).unwrap();
+    assert_eq!(socket.multicast_if_v6().unwrap(), 0);
+}
+
+#[test]
+fn set_broadcast_while_registered() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let rx = UdpSocket::bind(SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0)).unwrap();
+    let port = rx.local_addr().unwrap().port();
+    let tx = UdpSocket::bind(SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0)).unwrap();
+    poll.registry()
+        .register(&tx, ID1, Interests::WRITABLE)
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::WRITABLE)],
+    );
+
+    let target = SocketAddr::new(Ipv4Addr::BROADCAST.into(), port);
+    assert!(!tx.broadcast().unwrap());
+    #[cfg(unix)]
+    {
+        let err = tx.send_to(DATA1, target).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+    }
+
+    tx.set_broadcast(true).unwrap();
+    assert!(tx.broadcast().unwrap());
+    match tx.send_to(DATA1, target) {
+        Ok(n) => assert_eq!(n, DATA1.len()),
+        Err(ref err) if err.kind() == ErrorKind::PermissionDenied => {
+            panic!("unexpected error sending to broadcast address: {}", err)
+        }
+        // Some environments don't have a route to the broadcast address.
+        Err(err) => info!("unable to send to broadcast address: {}", err),
+    }
+
+    tx.set_broadcast(false).unwrap();
+    assert!(!tx.broadcast().unwrap());
+}
+
+#[test]
+fn set_get_buffer_sizes() {
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+
+    const SIZE: usize = 256 * 1024;
+    socket.set_recv_buffer_size(SIZE).unwrap();
+    assert!(socket.recv_buffer_size().unwrap() >= SIZE);
+    socket.set_send_buffer_size(SIZE).unwrap();
+    assert!(socket.send_buffer_size().unwrap() >= SIZE);
+}
+
+#[test]
+fn set_get_tos() {
+    // DSCP Expedited Forwarding (46).
+    const TOS: u32 = 46 << 2;
+
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+    socket.set_tos(TOS).unwrap();
+    assert_eq!(socket.tos().unwrap(), TOS);
+
+    let err = socket.set_tos(256).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert_eq!(socket.tos().unwrap(), TOS);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_get_mark() {
+    if !has_cap_net_admin() {
+        return;
+    }
+
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+    socket.set_mark(123).unwrap();
+    assert_eq!(socket.mark().unwrap(), 123);
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn set_mark_permission_denied() {
+    if has_cap_net_admin() {
+        return;
+    }
+
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+    let err = socket.set_mark(123).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+}
+
+/// Returns `true` if the process has `CAP_NET_ADMIN` in its effective set,
+/// which is required to set `SO_MARK`.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn has_cap_net_admin() -> bool {
+    const CAP_NET_ADMIN: u64 = 12;
+    std::fs::read_to_string("/proc/self/status")
+        .unwrap()
+        .lines()
+        .find(|line| line.starts_with("CapEff:"))
+        .and_then(|line| u64::from_str_radix(line["CapEff:".len()..].trim(), 16).ok())
+        .map_or(false, |caps| caps & (1 << CAP_NET_ADMIN) != 0)
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn bind_device() {
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+    assert_eq!(socket.device().unwrap(), None);
+
+    // Binding to a device requires `CAP_NET_RAW`, which root usually has.
+    if unsafe { libc::geteuid() } == 0 {
+        socket.bind_device(Some("lo")).unwrap();
+        assert_eq!(socket.device().unwrap(), Some(b"lo".to_vec()));
+
+        socket.bind_device(None).unwrap();
+        assert_eq!(socket.device().unwrap(), None);
+    }
+}
+
+#[test]
+fn set_get_tos_ipv6() {
+    const TOS: u32 = 46 << 2;
+
+    let socket = UdpSocket::bind(any_local_ipv6_address()).unwrap();
+    socket.set_tos(TOS).unwrap();
+    assert_eq!(socket.tos().unwrap(), TOS);
+}
+
+// Binding to a link-local unicast address requires an interface with such an
+// address, so this uses the link-local all-nodes multicast address on the
+// loopback interface, which always has index 1 on Linux.
+#[test]
+#[cfg(target_os = "linux")]
+fn ipv6_scope_id_round_trips() {
+    let addr: SocketAddr = "[ff02::1%1]:0".parse().unwrap();
+    let socket = UdpSocket::bind(addr).unwrap();
+    let local_addr = socket.local_addr().unwrap();
+    assert_eq!(local_addr.ip(), addr.ip());
+    match local_addr {
+        SocketAddr::V6(local_addr) => assert_eq!(local_addr.scope_id(), 1),
+        local_addr => panic!("unexpected address: {}", local_addr),
+    }
+}
+
+#[test]
+fn peek_from_does_not_consume_datagram() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let sender = UdpSocket::bind(any_local_address()).unwrap();
+    let receiver = UdpSocket::bind(any_local_address()).unwrap();
+    let sender_address = sender.local_addr().unwrap();
+
+    poll.registry()
+        .register(&receiver, ID1, Interests::READABLE)
+        .unwrap();
+
+    sender
+        .send_to(DATA1, receiver.local_addr().unwrap())
+        .unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+
+    let mut buf = [0; 20];
+    for _ in 0..2 {
+        let (n, address) = receiver.peek_from(&mut buf).unwrap();
+        assert_eq!(&buf[..n], DATA1);
+        assert_eq!(address, sender_address);
+    }
+
+    let (n, address) = receiver.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    assert_eq!(address, sender_address);
+
+    assert_would_block(receiver.peek_from(&mut buf));
+    assert_would_block(receiver.recv_from(&mut buf));
+}
+
+#[test]
+// OpenBSD doesn't support dual-stack sockets.
+#[cfg(not(target_os = "openbsd"))]
+fn bind_dual_stack() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let opts = UdpSocketOpts {
+        only_v6: Some(false),
+        ..UdpSocketOpts::default()
+    };
+    let socket = UdpSocket::bind_with("[::]:0".parse().unwrap(), opts).unwrap();
+    let port = socket.local_addr().unwrap().port();
+    poll.registry()
+        .register(&socket, ID1, Interests::READABLE)
+        .unwrap();
+
+    let sender = net::UdpSocket::bind(any_local_address()).unwrap();
+    sender.send_to(DATA1, ("127.0.0.1", port)).unwrap();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(ID1, Interests::READABLE)],
+    );
+
+    let mut buf = [0; 20];
+    let (n, address) = socket.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], DATA1);
+    match address {
+        SocketAddr::V6(address) => {
+            assert_eq!(address.ip().to_ipv4(), Some(Ipv4Addr::LOCALHOST));
+            assert_eq!(address.port(), sender.local_addr().unwrap().port());
+        }
+        SocketAddr::V4(address) => panic!("unexpected IPv4 source address: {}", address),
+    }
+}
+
+#[test]
+fn bind_with_options() {
+    let opts = UdpSocketOpts {
+        only_v6: Some(true),
+        reuse_addr: true,
+        ..UdpSocketOpts::default()
+    };
+    let socket = UdpSocket::bind_with("[::1]:0".parse().unwrap(), opts).unwrap();
+    assert!(socket.local_addr().unwrap().is_ipv6());
+
+    // `only_v6` is ignored for IPv4 addresses.
+    let socket = UdpSocket::bind_with(any_local_address(), opts).unwrap();
+    assert!(socket.local_addr().unwrap().is_ipv4());
+
+    let opts = UdpSocketOpts {
+        reuse_port: true,
+        ..UdpSocketOpts::default()
+    };
+    let result = UdpSocket::bind_with(any_local_address(), opts);
+    if cfg!(any(windows, target_os = "solaris")) {
+        assert!(result.is_err());
+    } else {
+        assert!(result.is_ok());
+    }
+}
+
+#[test]
+fn set_get_reuse_address() {
+    let opts = UdpSocketOpts {
+        reuse_addr: true,
+        ..UdpSocketOpts::default()
+    };
+    let socket = UdpSocket::bind_with(any_local_address(), opts).unwrap();
+    assert!(socket.reuse_address().unwrap());
+
+    let socket = UdpSocket::bind(any_local_address()).unwrap();
+    assert!(!socket.reuse_address().unwrap());
+    socket.set_reuse_address(true).unwrap();
+    assert!(socket.reuse_address().unwrap());
+    socket.set_reuse_address(false).unwrap();
+    assert!(!socket.reuse_address().unwrap());
+}
@@ -36,13 +36,23 @@ fn unix_listener_smoke() {
 fn unix_listener_from_std() {
     smoke_test(|path| {
         let listener = assert_ok!(net::UnixListener::bind(path));
-        // `std::os::unix::net::UnixStream`s are blocking by default, so make sure
-        // it is in non-blocking mode before wrapping in a Mio equivalent.
-        assert_ok!(listener.set_nonblocking(true));
-        Ok(UnixListener::from_std(listener))
+        // `from_std` puts the listener in non-blocking mode.
+        assert_ok!(listener.set_nonblocking(false));
+        UnixListener::from_std(listener)
     })
 }
 
+#[test]
+fn unix_listener_from_std_sets_non_blocking() {
+    let dir = assert_ok!(TempDir::new("unix_listener"));
+    let path = dir.path().join("any");
+
+    let listener = assert_ok!(net::UnixListener::bind(&path));
+    let listener = assert_ok!(UnixListener::from_std(listener));
+    // A blocking listener would block here forever.
+    assert_would_block(listener.accept());
+}
+
 #[test]
 fn unix_listener_try_clone_same_poll() {
     let (mut poll, mut events) = init_with_poll();
@@ -184,6 +194,110 @@ fn unix_listener_local_addr() {
     assert_ok!(handle.join());
 }
 
+#[test]
+fn unix_listener_accept_with_local() {
+    let (mut poll, mut events) = init_with_poll();
+    let dir = assert_ok!(TempDir::new("unix_listener"));
+    let path = dir.path().join("any");
+
+    let listener = assert_ok!(UnixListener::bind(&path));
+    assert_ok!(poll
+        .registry()
+        .register(&listener, TOKEN_1, Interests::READABLE));
+
+    let _clients: Vec<net::UnixStream> = (0..2)
+        .map(|_| assert_ok!(net::UnixStream::connect(&path)))
+        .collect();
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interests::READABLE)],
+    );
+
+    // The second call uses the cached local address.
+    for _ in 0..2 {
+        let (stream, peer_addr, local_addr) = assert_ok!(listener.accept_with_local());
+        assert_eq!(local_addr.as_pathname(), Some(path.as_path()));
+        assert!(peer_addr.is_unnamed());
+        assert_eq!(
+            assert_ok!(stream.local_addr()).as_pathname(),
+            Some(path.as_path())
+        );
+    }
+    assert_would_block(listener.accept_with_local());
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn unix_listener_accept_abstract_addr() {
+    use mio::net::UnixStream;
+    use mio::unix::SocketAddr;
+
+    let name = format!("mio-abstract-accept-{}", std::process::id());
+    let address = assert_ok!(SocketAddr::from_abstract_name(name.as_bytes()));
+    assert!(address.is_abstract());
+
+    let listener = assert_ok!(UnixListener::bind_addr(&address));
+    let (mut poll, mut events) = init_with_poll();
+    assert_ok!(poll
+        .registry()
+        .register(&listener, TOKEN_1, Interests::READABLE));
+
+    let _client = assert_ok!(UnixStream::connect_addr(&address));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interests::READABLE)],
+    );
+
+    let (stream, peer_addr) = assert_ok!(listener.accept());
+    // The client didn't bind to an address.
+    assert!(peer_addr.is_unnamed());
+    assert!(!peer_addr.is_abstract());
+    assert_eq!(peer_addr.as_abstract_name(), None);
+
+    let local_addr = assert_ok!(stream.local_addr());
+    assert!(local_addr.is_abstract());
+    assert_eq!(local_addr.as_abstract_name(), Some(name.as_bytes()));
+    assert_eq!(local_addr.as_pathname(), None);
+}
+
+#[test]
+fn unix_listener_accept_non_blocking_cloexec() {
+    let (mut poll, mut events) = init_with_poll();
+    let barrier = Arc::new(Barrier::new(2));
+    let dir = assert_ok!(TempDir::new("unix_listener"));
+    let path = dir.path().join("any");
+
+    let listener = assert_ok!(UnixListener::bind(&path));
+    assert_ok!(poll
+        .registry()
+        .register(&listener, TOKEN_1, Interests::READABLE));
+
+    let handle = open_connections(path, 1, barrier.clone());
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interests::READABLE)],
+    );
+
+    let (mut stream, _) = assert_ok!(listener.accept());
+    let fd = stream.as_raw_fd();
+    let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+    assert_ne!(flags, -1);
+    assert_ne!(flags & libc::O_NONBLOCK, 0, "stream is not non-blocking");
+    let flags = unsafe { libc::fcntl(fd, libc::F_GETFD) };
+    assert_ne!(flags, -1);
+    assert_ne!(flags & libc::FD_CLOEXEC, 0, "stream is not close-on-exec");
+
+    // No data was written, so reading must not block.
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    assert_would_block(stream.read(&mut buf));
+
+    barrier.wait();
+    assert_ok!(handle.join());
+}
+
 #[test]
 fn unix_listener_register() {
     let (mut poll, mut events) = init_with_poll();
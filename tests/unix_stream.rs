@@ -3,7 +3,8 @@
 mod util;
 
 use log::warn;
-use mio::net::UnixStream;
+use mio::net::{UnixListener, UnixStream};
+use mio::unix::SocketAddr;
 use mio::{Interests, Token};
 use std::io::{self, IoSlice, IoSliceMut, Read, Write};
 use std::net::Shutdown;
@@ -39,6 +40,67 @@ fn unix_stream_smoke() {
     smoke_test(|path| UnixStream::connect(path));
 }
 
+#[test]
+fn unix_stream_connect_addr() {
+    let (mut poll, mut events) = init_with_poll();
+    let dir = assert_ok!(TempDir::new("unix"));
+    let path = dir.path().join("any");
+
+    let address = assert_ok!(SocketAddr::from_pathname(&path));
+    assert_eq!(address.as_pathname(), Some(&*path));
+    let listener = assert_ok!(UnixListener::bind_addr(&address));
+    let stream = assert_ok!(UnixStream::connect_addr(&address));
+
+    assert_ok!(poll
+        .registry()
+        .register(&stream, TOKEN_1, Interests::WRITABLE));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interests::WRITABLE)],
+    );
+
+    assert_ok!(listener.accept());
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn unix_stream_connect_abstract_addr() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let name = format!("mio-abstract-{}", std::process::id());
+    let address = assert_ok!(SocketAddr::from_abstract_name(name.as_bytes()));
+    assert_eq!(address.as_abstract_name(), Some(name.as_bytes()));
+    assert_eq!(address.as_pathname(), None);
+
+    let listener = assert_ok!(UnixListener::bind_addr(&address));
+    let local_address = assert_ok!(listener.local_addr());
+    assert_eq!(local_address.as_abstract_name(), Some(name.as_bytes()));
+
+    let mut stream = assert_ok!(UnixStream::connect_addr(&address));
+    assert_ok!(poll.registry().register(
+        &stream,
+        TOKEN_1,
+        Interests::READABLE | Interests::WRITABLE
+    ));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interests::WRITABLE)],
+    );
+
+    let (mut accepted, _) = assert_ok!(listener.accept());
+    assert_eq!(assert_ok!(accepted.write(DATA1)), DATA1_LEN);
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interests::READABLE)],
+    );
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    assert_eq!(assert_ok!(stream.read(&mut buf)), DATA1_LEN);
+    assert_eq!(&buf[..DATA1_LEN], DATA1);
+}
+
 #[test]
 fn unix_stream_connect() {
     let (mut poll, mut events) = init_with_poll();
@@ -128,6 +190,40 @@ fn unix_stream_pair() {
     assert_eq!(read, wrote, "unequal reads and writes");
 }
 
+#[test]
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+fn unix_stream_seqpacket_pair() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (mut s1, mut s2) = assert_ok!(UnixStream::seqpacket_pair());
+    assert_ok!(poll.registry().register(&s2, TOKEN_2, Interests::READABLE));
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    assert_would_block(s2.read(&mut buf));
+
+    assert_eq!(assert_ok!(s1.write(&DATA1)), DATA1_LEN);
+    assert_eq!(assert_ok!(s1.write(&DATA2)), DATA2_LEN);
+
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_2, Interests::READABLE)],
+    );
+
+    // Message boundaries are preserved.
+    let read = assert_ok!(s2.read(&mut buf));
+    assert_eq!(&buf[..read], DATA1);
+    let read = assert_ok!(s2.read(&mut buf));
+    assert_eq!(&buf[..read], DATA2);
+    assert_would_block(s2.read(&mut buf));
+}
+
+#[test]
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+fn unix_stream_seqpacket_pair_unsupported() {
+    assert!(UnixStream::seqpacket_pair().is_err());
+}
+
 #[test]
 fn unix_stream_try_clone() {
     let (mut poll, mut events) = init_with_poll();
@@ -190,6 +286,61 @@ fn unix_stream_peer_addr() {
     assert_ok!(handle.join());
 }
 
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn unix_stream_peer_cred() {
+    let (stream1, stream2) = assert_ok!(UnixStream::pair());
+
+    let uid = unsafe { libc::getuid() };
+    let gid = unsafe { libc::getgid() };
+    let pid = unsafe { libc::getpid() };
+    for stream in &[stream1, stream2] {
+        let cred = assert_ok!(stream.peer_cred());
+        assert_eq!(cred.uid, uid);
+        assert_eq!(cred.gid, gid);
+        assert_eq!(cred.pid, Some(pid));
+    }
+}
+
+#[test]
+fn unix_stream_event_readiness() {
+    let (mut poll, mut events) = init_with_poll();
+
+    let (s1, mut s2) = assert_ok!(UnixStream::pair());
+    assert_ok!(poll
+        .registry()
+        .register(&s1, TOKEN_1, Interests::READABLE | Interests::WRITABLE));
+
+    // Nothing to read yet, so the stream is only writable.
+    assert_ok!(poll.poll(&mut events, Some(Duration::from_millis(500))));
+    let s1_events: Vec<_> = events.iter().filter(|e| e.token() == TOKEN_1).collect();
+    assert!(s1_events.iter().any(|event| event.is_writable()));
+    assert!(!s1_events.iter().any(|event| event.is_readable()));
+
+    assert_ok!(s2.write_all(DATA1));
+    expect_events(
+        &mut poll,
+        &mut events,
+        vec![ExpectEvent::new(TOKEN_1, Interests::READABLE)],
+    );
+}
+
+#[test]
+fn unix_stream_try_read_try_write() {
+    let (stream1, stream2) = assert_ok!(UnixStream::pair());
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    assert_eq!(assert_ok!(stream2.try_read(&mut buf)), None);
+
+    assert_eq!(assert_ok!(stream1.try_write(DATA1)), Some(DATA1_LEN));
+    assert_eq!(assert_ok!(stream2.try_read(&mut buf)), Some(DATA1_LEN));
+    assert_eq!(&buf[..DATA1_LEN], DATA1);
+    assert_eq!(assert_ok!(stream2.try_read(&mut buf)), None);
+
+    drop(stream1);
+    assert_eq!(assert_ok!(stream2.try_read(&mut buf)), Some(0));
+}
+
 #[test]
 fn unix_stream_shutdown_read() {
     let (mut poll, mut events) = init_with_poll();
@@ -454,6 +605,7 @@ where
     );
 
     let mut buf = [0; DEFAULT_BUF_SIZE];
+    assert_would_block(stream.peek(&mut buf));
     assert_would_block(stream.read(&mut buf));
 
     let wrote = assert_ok!(stream.write(&DATA1));
@@ -465,6 +617,10 @@ where
         vec![ExpectEvent::new(TOKEN_1, Interests::READABLE)],
     );
 
+    let read = assert_ok!(stream.peek(&mut buf));
+    assert_eq!(read, DATA1_LEN);
+    assert_eq!(&buf[..read], DATA1);
+
     let read = assert_ok!(stream.read(&mut buf));
     assert_eq!(read, DATA1_LEN);
     assert_eq!(&buf[..read], DATA1);
@@ -212,6 +212,7 @@ impl ExpectEvent {
             // In maths terms that is p -> q, which is the same  as !p || q.
             (!self.interests.is_readable() || event.is_readable()) &&
             (!self.interests.is_writable() || event.is_writable()) &&
+            (!self.interests.is_priority() || event.is_priority()) &&
             (!self.interests.is_aio() || event.is_aio()) &&
             (!self.interests.is_lio() || event.is_lio())
     }
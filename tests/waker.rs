@@ -1,8 +1,9 @@
+use std::io;
 use std::sync::{Arc, Barrier};
 use std::thread;
 use std::time::Duration;
 
-use mio::{Events, Poll, Token, Waker};
+use mio::{Events, PayloadWaker, Poll, Token, WakeReason, Waker, WakerSet};
 
 mod util;
 
@@ -12,6 +13,14 @@ use util::{assert_send, assert_sync, expect_no_events, init};
 fn is_send_and_sync() {
     assert_send::<Waker>();
     assert_sync::<Waker>();
+
+    assert_send::<PayloadWaker>();
+    assert_sync::<PayloadWaker>();
+
+    assert_send::<WakerSet>();
+    assert_sync::<WakerSet>();
+    assert_send::<WakeReason>();
+    assert_sync::<WakeReason>();
 }
 
 #[test]
@@ -28,6 +37,23 @@ fn waker() {
     expect_waker_event(&mut poll, &mut events, token);
 }
 
+#[test]
+fn waker_events_contains_only() {
+    init();
+
+    let mut poll = Poll::new().expect("unable to create new Poll instance");
+    let mut events = Events::with_capacity(10);
+    assert!(!events.contains_only(Token(10)));
+
+    let token = Token(10);
+    let waker = Waker::new(poll.registry(), token).expect("unable to create waker");
+
+    waker.wake().expect("unable to wake");
+    expect_waker_event(&mut poll, &mut events, token);
+    assert!(events.contains_only(token));
+    assert!(!events.contains_only(Token(11)));
+}
+
 #[test]
 fn waker_multiple_wakeups_same_thread() {
     init();
@@ -44,6 +70,32 @@ fn waker_multiple_wakeups_same_thread() {
     expect_waker_event(&mut poll, &mut events, token);
 }
 
+#[test]
+fn waker_wakeups_coalesce() {
+    init();
+
+    let mut poll = Poll::new().expect("unable to create new Poll instance");
+    let mut events = Events::with_capacity(10);
+
+    let token = Token(10);
+    let waker = Waker::new(poll.registry(), token).expect("unable to create waker");
+
+    for _ in 0..3 {
+        waker.wake().expect("unable to wake");
+    }
+    expect_waker_event(&mut poll, &mut events, token);
+    assert_eq!(events.iter().count(), 1);
+
+    // The wake ups are cleared by polling.
+    expect_no_events(&mut poll, &mut events);
+
+    // But the waker can be used again.
+    waker.wake().expect("unable to wake");
+    expect_waker_event(&mut poll, &mut events, token);
+    assert_eq!(events.iter().count(), 1);
+    expect_no_events(&mut poll, &mut events);
+}
+
 #[test]
 fn waker_wakeup_different_thread() {
     init();
@@ -106,6 +158,166 @@ fn waker_multiple_wakeups_different_thread() {
     handle2.join().unwrap();
 }
 
+#[test]
+fn waker_handle_clones_different_threads() {
+    init();
+
+    let mut poll = Poll::new().expect("unable to create new Poll instance");
+    let mut events = Events::with_capacity(10);
+
+    let token = Token(10);
+    let waker = poll.waker_handle(token).expect("unable to create waker");
+    let waker1 = waker.clone();
+    let waker2 = waker.clone();
+
+    let barrier = Arc::new(Barrier::new(2));
+    let barrier2 = barrier.clone();
+
+    let handle1 = thread::spawn(move || {
+        waker1.wake().expect("unable to wake");
+    });
+    let handle2 = thread::spawn(move || {
+        barrier2.wait();
+        waker2.wake().expect("unable to wake");
+    });
+
+    // Event from the first clone.
+    expect_waker_event(&mut poll, &mut events, token);
+    handle1.join().unwrap();
+
+    // Event from the second clone, after the first clone is dropped.
+    barrier.wait();
+    expect_waker_event(&mut poll, &mut events, token);
+    handle2.join().unwrap();
+
+    expect_no_events(&mut poll, &mut events);
+}
+
+#[test]
+#[cfg(unix)]
+fn waker_wake_from_signal_handler() {
+    use std::ptr;
+    use std::sync::atomic::{AtomicPtr, Ordering};
+
+    static WAKER: AtomicPtr<Waker> = AtomicPtr::new(ptr::null_mut());
+
+    extern "C" fn handler(_: libc::c_int) {
+        let waker = WAKER.load(Ordering::Acquire);
+        if let Some(waker) = unsafe { waker.as_ref() } {
+            waker.wake_from_signal_handler().expect("unable to wake");
+        }
+    }
+
+    init();
+
+    let mut poll = Poll::new().expect("unable to create new Poll instance");
+    let mut events = Events::with_capacity(10);
+
+    let token = Token(10);
+    let waker = poll.waker_handle(token).expect("unable to create waker");
+    WAKER.store(&waker as *const Waker as *mut Waker, Ordering::Release);
+
+    let handler: extern "C" fn(libc::c_int) = handler;
+    let old_handler = unsafe { libc::signal(libc::SIGALRM, handler as libc::sighandler_t) };
+    assert_ne!(old_handler, libc::SIG_ERR);
+
+    // `raise` only returns after the signal handler ran.
+    assert_eq!(unsafe { libc::raise(libc::SIGALRM) }, 0);
+    expect_waker_event(&mut poll, &mut events, token);
+
+    unsafe { libc::signal(libc::SIGALRM, old_handler) };
+    WAKER.store(ptr::null_mut(), Ordering::Release);
+
+    expect_no_events(&mut poll, &mut events);
+}
+
+#[test]
+fn payload_waker() {
+    init();
+
+    let mut poll = Poll::new().expect("unable to create new Poll instance");
+    let mut events = Events::with_capacity(10);
+
+    let token = Token(10);
+    let waker = PayloadWaker::new(poll.registry(), token).expect("unable to create waker");
+    assert_eq!(waker.drain_payloads().count(), 0);
+
+    for payload in &[3, 1, 2, 255, 1] {
+        waker.wake_with(*payload).expect("unable to wake");
+    }
+    expect_waker_event(&mut poll, &mut events, token);
+
+    // Payloads are coalesced and returned in ascending order.
+    let payloads: Vec<u8> = waker.drain_payloads().collect();
+    assert_eq!(payloads, vec![1, 2, 3, 255]);
+    assert_eq!(waker.drain_payloads().count(), 0);
+}
+
+#[test]
+fn payload_waker_different_thread() {
+    init();
+
+    let mut poll = Poll::new().expect("unable to create new Poll instance");
+    let mut events = Events::with_capacity(10);
+
+    let token = Token(10);
+    let waker = PayloadWaker::new(poll.registry(), token).expect("unable to create waker");
+    let waker = Arc::new(waker);
+    let waker1 = Arc::clone(&waker);
+    let handle = thread::spawn(move || {
+        waker1.wake_with(0).expect("unable to wake");
+    });
+
+    handle.join().unwrap();
+    expect_waker_event(&mut poll, &mut events, token);
+    assert_eq!(waker.drain_payloads().collect::<Vec<_>>(), vec![0]);
+}
+
+#[test]
+fn waker_set() {
+    init();
+
+    let mut poll = Poll::new().expect("unable to create new Poll instance");
+    let mut events = Events::with_capacity(10);
+
+    let token = Token(10);
+    let wakers = WakerSet::new(poll.registry(), token).expect("unable to create waker");
+    let shutdown = wakers.reason("shutdown").unwrap();
+    let reload = wakers.reason("reload").unwrap();
+    let work = wakers.reason("work").unwrap();
+    assert!(wakers.take_signaled().is_empty());
+
+    let handle = thread::spawn(move || {
+        work.wake().expect("unable to wake");
+        work.wake().expect("unable to wake");
+    });
+    shutdown.wake().expect("unable to wake");
+    handle.join().unwrap();
+    expect_waker_event(&mut poll, &mut events, token);
+
+    // Reasons are coalesced and returned in the order they were added.
+    assert_eq!(wakers.take_signaled(), vec!["shutdown", "work"]);
+    assert!(wakers.take_signaled().is_empty());
+
+    // Asking for an existing reason returns the same reason.
+    wakers
+        .reason("reload")
+        .unwrap()
+        .wake()
+        .expect("unable to wake");
+    expect_waker_event(&mut poll, &mut events, token);
+    assert_eq!(wakers.take_signaled(), vec!["reload"]);
+    drop(reload);
+
+    // At most 32 reasons are supported, existing reasons can still be used.
+    for n in 3..32 {
+        wakers.reason(&n.to_string()).unwrap();
+    }
+    let err = wakers.reason("too many").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert!(wakers.reason("work").is_ok());
+}
+
 fn expect_waker_event(poll: &mut Poll, events: &mut Events, token: Token) {
     poll.poll(events, Some(Duration::from_millis(100))).unwrap();
     assert!(!events.is_empty());